@@ -0,0 +1,183 @@
+## Layout
+
+site-name = Quentin Tarantino
+site-page-title = Quentin Tarantino Films
+site-description = Explore the filmography of Quentin Tarantino.
+nav-movies = Movies
+nav-interviews = Interviews
+nav-quotes = Quotes
+nav-language = Language
+footer-rights = © Quentin Tarantino Films. All rights reserved.
+feed-title = What's new
+
+## Home
+
+home-page-title = Home - My Awesome Site
+home-heading = Welcome to My Awesome Site
+
+## Lists
+
+pagination-label = Pages
+pagination-previous = « Previous
+pagination-next = Next »
+pagination-page = Page { $page } of { $last }
+action-edit = Edit
+action-delete = Delete
+
+## Movies
+
+movies-page-title = Filmography - Quentin Tarantino Films
+movies-heading = Filmography
+movies-add = + Add a movie
+movies-none = No movies yet.
+movies-none-matching = No movies match these filters.
+filter-search = Search
+filter-search-placeholder = Title or plot
+filter-genre = Genre
+filter-decade = Decade
+filter-decade-option = { $decade }s
+filter-rating = Rating
+filter-rating-option = { $rating }+
+filter-any = Any
+filter-submit = Filter
+
+movie-page-title = { $title } - Quentin Tarantino Films
+movie-release-year = Release Year:
+movie-release-date = Release Date:
+movie-genre = Genre:
+movie-directors = Directors:
+movie-directors-none = No directors listed.
+movie-producers = Producers:
+movie-producers-none = No producers listed.
+movie-cast = Cast:
+movie-cast-none = No cast listed.
+movie-writers = Writers:
+movie-writers-none = No writers listed.
+movie-runtime = Runtime:
+movie-runtime-minutes = { $minutes } mins
+movie-rating = Rating:
+movie-plot = Plot:
+movie-budget = Budget:
+movie-trailer = Trailer
+movie-trailer-title = { $title } Trailer
+movie-stills = Stills
+movie-poster-alt = Poster of { $title }
+movie-still-alt = Still from { $title }
+movie-awards-and-nominations = Awards and Nominations
+movie-nominations = Nominations
+movie-nominations-none = No nominations listed.
+movie-awards = Awards
+movie-awards-none = No awards listed.
+movie-category = Category:
+
+## Quotes
+
+quotes-page-title = Quentin Tarantino Quotes
+quotes-heading = Quotes
+quotes-add = + Add a quote
+quote-author = - Quentin Tarantino
+
+## Interviews
+
+interviews-page-title = Interviews - Quentin Tarantino Films
+interviews-heading = Interviews
+interviews-intro = Explore insightful conversations and interviews with Quentin Tarantino
+interviews-add = + Add an interview
+interviews-none = No interviews available at the moment.
+
+## Forms
+
+form-page-title = { $heading } - Quentin Tarantino Films
+movie-form-add = Add a movie
+movie-form-edit = Edit { $title }
+quote-form-add = Add a quote
+quote-form-edit = Edit quote
+interview-form-add = Add an interview
+interview-form-edit = Edit interview
+form-has-errors = Some fields need fixing; see the messages below them.
+form-clear-row = Clear a row to remove it.
+form-more-rows = + More rows
+form-add-row = + Add row
+form-remove-row = Remove
+form-save = Save
+form-cancel = Cancel
+field-title = Title
+field-release-year = Release year
+field-release-date = Release date
+field-genre = Genre
+field-runtime = Runtime (minutes)
+field-rating = Rating (0-10)
+field-budget = Budget
+field-plot = Plot
+field-image-url = Poster URL
+field-trailer-id = YouTube trailer id
+field-production = Production
+field-directors = Directors
+field-producers = Producers
+field-writers = Writers
+field-actors = Cast
+field-awards = Awards
+field-nominations = Nominations
+field-award = Award
+field-category = Category
+field-year = Year
+field-recipient = Recipient
+field-nominee = Nominee
+field-quote = Quote
+field-description = Description
+field-video-id = YouTube video id
+
+confirm-delete-page-title = Delete { $kind ->
+        [movie] movie
+        [quote] quote
+       *[interview] interview
+    } - Quentin Tarantino Films
+confirm-delete-heading = Delete this { $kind ->
+        [movie] movie
+        [quote] quote
+       *[interview] interview
+    }?
+confirm-delete-warning = "{ $label }" will be removed for good.
+
+## Errors
+
+error-page-title = Error { $code } - Quentin Tarantino Films
+error-title-not-found = Page Not Found
+error-title-client = That Didn't Work
+error-title-server = Something Went Wrong
+error-zed = "Zed's dead, baby. Zed's dead."
+error-details = Details:
+error-go-back = Go Back
+error-home = Home Page
+error-dismiss = Dismiss
+error-common-issues = Common issues:
+error-suggestion-moved = The page you're looking for might have been moved or deleted
+error-suggestion-typos = Check the URL for typos
+error-suggestion-refresh = Try refreshing the page
+error-suggestion-support = If the problem persists, please contact support
+error-not-found = { $kind ->
+        [movie] Movie
+        [quote] Quote
+       *[interview] Interview
+    } with ID { $id } not found
+error-no-page = There is no page at { $path }
+error-invalid-form = Some fields need fixing
+error-database = A database error occurred
+error-render = Failed to render page
+error-internal = An internal error occurred
+
+## Dates
+
+date-long = { $month } { $day }, { $year }
+month-1 = January
+month-2 = February
+month-3 = March
+month-4 = April
+month-5 = May
+month-6 = June
+month-7 = July
+month-8 = August
+month-9 = September
+month-10 = October
+month-11 = November
+month-12 = December
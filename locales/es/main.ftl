@@ -0,0 +1,183 @@
+## Layout
+
+site-name = Quentin Tarantino
+site-page-title = Las películas de Quentin Tarantino
+site-description = Explora la filmografía de Quentin Tarantino.
+nav-movies = Películas
+nav-interviews = Entrevistas
+nav-quotes = Citas
+nav-language = Idioma
+footer-rights = © Quentin Tarantino Films. Todos los derechos reservados.
+feed-title = Novedades
+
+## Home
+
+home-page-title = Inicio - Las películas de Quentin Tarantino
+home-heading = Bienvenido al sitio
+
+## Lists
+
+pagination-label = Páginas
+pagination-previous = « Anterior
+pagination-next = Siguiente »
+pagination-page = Página { $page } de { $last }
+action-edit = Editar
+action-delete = Eliminar
+
+## Movies
+
+movies-page-title = Filmografía - Las películas de Quentin Tarantino
+movies-heading = Filmografía
+movies-add = + Añadir una película
+movies-none = Todavía no hay películas.
+movies-none-matching = Ninguna película coincide con estos filtros.
+filter-search = Buscar
+filter-search-placeholder = Título o argumento
+filter-genre = Género
+filter-decade = Década
+filter-decade-option = Años { $decade }
+filter-rating = Puntuación
+filter-rating-option = { $rating }+
+filter-any = Cualquiera
+filter-submit = Filtrar
+
+movie-page-title = { $title } - Las películas de Quentin Tarantino
+movie-release-year = Año de estreno:
+movie-release-date = Fecha de estreno:
+movie-genre = Género:
+movie-directors = Dirección:
+movie-directors-none = No figura ningún director.
+movie-producers = Producción:
+movie-producers-none = No figura ningún productor.
+movie-cast = Reparto:
+movie-cast-none = No figura ningún reparto.
+movie-writers = Guion:
+movie-writers-none = No figura ningún guionista.
+movie-runtime = Duración:
+movie-runtime-minutes = { $minutes } min
+movie-rating = Puntuación:
+movie-plot = Argumento:
+movie-budget = Presupuesto:
+movie-trailer = Tráiler
+movie-trailer-title = Tráiler de { $title }
+movie-stills = Fotogramas
+movie-poster-alt = Póster de { $title }
+movie-still-alt = Fotograma de { $title }
+movie-awards-and-nominations = Premios y nominaciones
+movie-nominations = Nominaciones
+movie-nominations-none = No figura ninguna nominación.
+movie-awards = Premios
+movie-awards-none = No figura ningún premio.
+movie-category = Categoría:
+
+## Quotes
+
+quotes-page-title = Citas de Quentin Tarantino
+quotes-heading = Citas
+quotes-add = + Añadir una cita
+quote-author = - Quentin Tarantino
+
+## Interviews
+
+interviews-page-title = Entrevistas - Las películas de Quentin Tarantino
+interviews-heading = Entrevistas
+interviews-intro = Conversaciones y entrevistas reveladoras con Quentin Tarantino
+interviews-add = + Añadir una entrevista
+interviews-none = No hay entrevistas disponibles por el momento.
+
+## Forms
+
+form-page-title = { $heading } - Las películas de Quentin Tarantino
+movie-form-add = Añadir una película
+movie-form-edit = Editar { $title }
+quote-form-add = Añadir una cita
+quote-form-edit = Editar la cita
+interview-form-add = Añadir una entrevista
+interview-form-edit = Editar la entrevista
+form-has-errors = Hay campos que corregir; mira los mensajes debajo de ellos.
+form-clear-row = Vacía una fila para quitarla.
+form-more-rows = + Más filas
+form-add-row = + Añadir fila
+form-remove-row = Quitar
+form-save = Guardar
+form-cancel = Cancelar
+field-title = Título
+field-release-year = Año de estreno
+field-release-date = Fecha de estreno
+field-genre = Género
+field-runtime = Duración (minutos)
+field-rating = Puntuación (0-10)
+field-budget = Presupuesto
+field-plot = Argumento
+field-image-url = URL del póster
+field-trailer-id = Id de YouTube del tráiler
+field-production = Producción
+field-directors = Dirección
+field-producers = Producción
+field-writers = Guion
+field-actors = Reparto
+field-awards = Premios
+field-nominations = Nominaciones
+field-award = Premio
+field-category = Categoría
+field-year = Año
+field-recipient = Galardonado
+field-nominee = Nominado
+field-quote = Cita
+field-description = Descripción
+field-video-id = Id del vídeo de YouTube
+
+confirm-delete-page-title = Eliminar { $kind ->
+        [movie] la película
+        [quote] la cita
+       *[interview] la entrevista
+    } - Las películas de Quentin Tarantino
+confirm-delete-heading = ¿Eliminar { $kind ->
+        [movie] esta película
+        [quote] esta cita
+       *[interview] esta entrevista
+    }?
+confirm-delete-warning = «{ $label }» se eliminará para siempre.
+
+## Errors
+
+error-page-title = Error { $code } - Las películas de Quentin Tarantino
+error-title-not-found = Página no encontrada
+error-title-client = Eso no funcionó
+error-title-server = Algo salió mal
+error-zed = «Zed ha muerto, nena. Zed ha muerto.»
+error-details = Detalles:
+error-go-back = Volver
+error-home = Inicio
+error-dismiss = Cerrar
+error-common-issues = Problemas habituales:
+error-suggestion-moved = Puede que la página que buscas se haya movido o eliminado
+error-suggestion-typos = Revisa que la dirección no tenga errores
+error-suggestion-refresh = Prueba a recargar la página
+error-suggestion-support = Si el problema continúa, contacta con soporte
+error-not-found = No se encontró { $kind ->
+        [movie] ninguna película
+        [quote] ninguna cita
+       *[interview] ninguna entrevista
+    } con el id { $id }
+error-no-page = No hay ninguna página en { $path }
+error-invalid-form = Hay campos que corregir
+error-database = Se produjo un error de base de datos
+error-render = No se pudo mostrar la página
+error-internal = Se produjo un error interno
+
+## Dates
+
+date-long = { $day } de { $month } de { $year }
+month-1 = enero
+month-2 = febrero
+month-3 = marzo
+month-4 = abril
+month-5 = mayo
+month-6 = junio
+month-7 = julio
+month-8 = agosto
+month-9 = septiembre
+month-10 = octubre
+month-11 = noviembre
+month-12 = diciembre
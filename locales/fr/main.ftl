@@ -0,0 +1,183 @@
+## Layout
+
+site-name = Quentin Tarantino
+site-page-title = Les films de Quentin Tarantino
+site-description = Découvrez la filmographie de Quentin Tarantino.
+nav-movies = Films
+nav-interviews = Entretiens
+nav-quotes = Citations
+nav-language = Langue
+footer-rights = © Quentin Tarantino Films. Tous droits réservés.
+feed-title = Nouveautés
+
+## Home
+
+home-page-title = Accueil - Les films de Quentin Tarantino
+home-heading = Bienvenue sur le site
+
+## Lists
+
+pagination-label = Pages
+pagination-previous = « Précédente
+pagination-next = Suivante »
+pagination-page = Page { $page } sur { $last }
+action-edit = Modifier
+action-delete = Supprimer
+
+## Movies
+
+movies-page-title = Filmographie - Les films de Quentin Tarantino
+movies-heading = Filmographie
+movies-add = + Ajouter un film
+movies-none = Aucun film pour l'instant.
+movies-none-matching = Aucun film ne correspond à ces filtres.
+filter-search = Recherche
+filter-search-placeholder = Titre ou intrigue
+filter-genre = Genre
+filter-decade = Décennie
+filter-decade-option = Années { $decade }
+filter-rating = Note
+filter-rating-option = { $rating }+
+filter-any = Tous
+filter-submit = Filtrer
+
+movie-page-title = { $title } - Les films de Quentin Tarantino
+movie-release-year = Année de sortie :
+movie-release-date = Date de sortie :
+movie-genre = Genre :
+movie-directors = Réalisation :
+movie-directors-none = Aucun réalisateur indiqué.
+movie-producers = Production :
+movie-producers-none = Aucun producteur indiqué.
+movie-cast = Distribution :
+movie-cast-none = Aucune distribution indiquée.
+movie-writers = Scénario :
+movie-writers-none = Aucun scénariste indiqué.
+movie-runtime = Durée :
+movie-runtime-minutes = { $minutes } min
+movie-rating = Note :
+movie-plot = Intrigue :
+movie-budget = Budget :
+movie-trailer = Bande-annonce
+movie-trailer-title = Bande-annonce de { $title }
+movie-stills = Photos du film
+movie-poster-alt = Affiche de { $title }
+movie-still-alt = Photo de { $title }
+movie-awards-and-nominations = Prix et nominations
+movie-nominations = Nominations
+movie-nominations-none = Aucune nomination indiquée.
+movie-awards = Prix
+movie-awards-none = Aucun prix indiqué.
+movie-category = Catégorie :
+
+## Quotes
+
+quotes-page-title = Citations de Quentin Tarantino
+quotes-heading = Citations
+quotes-add = + Ajouter une citation
+quote-author = - Quentin Tarantino
+
+## Interviews
+
+interviews-page-title = Entretiens - Les films de Quentin Tarantino
+interviews-heading = Entretiens
+interviews-intro = Des conversations et des entretiens éclairants avec Quentin Tarantino
+interviews-add = + Ajouter un entretien
+interviews-none = Aucun entretien disponible pour le moment.
+
+## Forms
+
+form-page-title = { $heading } - Les films de Quentin Tarantino
+movie-form-add = Ajouter un film
+movie-form-edit = Modifier { $title }
+quote-form-add = Ajouter une citation
+quote-form-edit = Modifier la citation
+interview-form-add = Ajouter un entretien
+interview-form-edit = Modifier l'entretien
+form-has-errors = Certains champs sont à corriger ; voyez les messages en dessous.
+form-clear-row = Videz une ligne pour la retirer.
+form-more-rows = + Plus de lignes
+form-add-row = + Ajouter une ligne
+form-remove-row = Retirer
+form-save = Enregistrer
+form-cancel = Annuler
+field-title = Titre
+field-release-year = Année de sortie
+field-release-date = Date de sortie
+field-genre = Genre
+field-runtime = Durée (minutes)
+field-rating = Note (0-10)
+field-budget = Budget
+field-plot = Intrigue
+field-image-url = URL de l'affiche
+field-trailer-id = Identifiant YouTube de la bande-annonce
+field-production = Production
+field-directors = Réalisation
+field-producers = Production
+field-writers = Scénario
+field-actors = Distribution
+field-awards = Prix
+field-nominations = Nominations
+field-award = Prix
+field-category = Catégorie
+field-year = Année
+field-recipient = Lauréat
+field-nominee = Nommé
+field-quote = Citation
+field-description = Description
+field-video-id = Identifiant de la vidéo YouTube
+
+confirm-delete-page-title = Supprimer { $kind ->
+        [movie] le film
+        [quote] la citation
+       *[interview] l'entretien
+    } - Les films de Quentin Tarantino
+confirm-delete-heading = Supprimer { $kind ->
+        [movie] ce film
+        [quote] cette citation
+       *[interview] cet entretien
+    } ?
+confirm-delete-warning = « { $label } » sera supprimé définitivement.
+
+## Errors
+
+error-page-title = Erreur { $code } - Les films de Quentin Tarantino
+error-title-not-found = Page introuvable
+error-title-client = Ça n'a pas marché
+error-title-server = Quelque chose s'est mal passé
+error-zed = « Zed est mort, bébé. Zed est mort. »
+error-details = Détails :
+error-go-back = Retour
+error-home = Accueil
+error-dismiss = Fermer
+error-common-issues = Causes fréquentes :
+error-suggestion-moved = La page que vous cherchez a peut-être été déplacée ou supprimée
+error-suggestion-typos = Vérifiez que l'adresse ne contient pas de faute
+error-suggestion-refresh = Essayez de recharger la page
+error-suggestion-support = Si le problème persiste, contactez l'assistance
+error-not-found = { $kind ->
+        [movie] Aucun film
+        [quote] Aucune citation
+       *[interview] Aucun entretien
+    } avec l'identifiant { $id }
+error-no-page = Il n'y a pas de page à l'adresse { $path }
+error-invalid-form = Certains champs sont à corriger
+error-database = Une erreur de base de données est survenue
+error-render = La page n'a pas pu être affichée
+error-internal = Une erreur interne est survenue
+
+## Dates
+
+date-long = { $day } { $month } { $year }
+month-1 = janvier
+month-2 = février
+month-3 = mars
+month-4 = avril
+month-5 = mai
+month-6 = juin
+month-7 = juillet
+month-8 = août
+month-9 = septembre
+month-10 = octobre
+month-11 = novembre
+month-12 = décembre
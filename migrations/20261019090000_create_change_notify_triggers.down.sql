@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS notify_change ON movie;
+DROP TRIGGER IF EXISTS notify_change ON quote;
+DROP TRIGGER IF EXISTS notify_change ON interview;
+DROP FUNCTION IF EXISTS trigger_notify_change;
@@ -0,0 +1,38 @@
+-- Add up migration script here
+-- Tell every listening server instance which movie, quote or interview changed
+CREATE OR REPLACE FUNCTION trigger_notify_change()
+    RETURNS TRIGGER AS $$
+DECLARE
+    record_id INTEGER;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        record_id = OLD.id;
+    ELSE
+        record_id = NEW.id;
+    END IF;
+
+    PERFORM pg_notify(
+        'catalogue_changes',
+        json_build_object('table', TG_TABLE_NAME, 'action', lower(TG_OP), 'id', record_id)::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS notify_change ON movie;
+CREATE TRIGGER notify_change
+    AFTER INSERT OR UPDATE OR DELETE ON movie
+    FOR EACH ROW
+EXECUTE PROCEDURE trigger_notify_change();
+
+DROP TRIGGER IF EXISTS notify_change ON quote;
+CREATE TRIGGER notify_change
+    AFTER INSERT OR UPDATE OR DELETE ON quote
+    FOR EACH ROW
+EXECUTE PROCEDURE trigger_notify_change();
+
+DROP TRIGGER IF EXISTS notify_change ON interview;
+CREATE TRIGGER notify_change
+    AFTER INSERT OR UPDATE OR DELETE ON interview
+    FOR EACH ROW
+EXECUTE PROCEDURE trigger_notify_change();
@@ -0,0 +1,9 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS movie_still (
+    id SERIAL PRIMARY KEY,
+    movie_id INT NOT NULL REFERENCES movie(id) ON DELETE CASCADE,
+    image_url TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_movie_still_movie_id ON movie_still (movie_id);
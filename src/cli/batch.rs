@@ -0,0 +1,534 @@
+//! `tarantinors-cli batch`: runs a JSONL file of operations, one per line.
+//!
+//! ```text
+//! {"name": "rd", "op": "create", "resource": "movies", "body": {"title": "Reservoir Dogs", ...}}
+//! {"op": "get", "resource": "movies", "id": "${rd.id}"}
+//! {"op": "list", "resource": "movies", "params": {"genre": "crime", "limit": 5}}
+//! {"op": "delete", "resource": "movies", "id": "${rd.id}"}
+//! ```
+//!
+//! `${name.path}` is replaced by a field of the output of the earlier line
+//! with that `name`; a string that is only a reference takes the field's JSON
+//! type, so ids stay numbers.
+
+use crate::output::Columns;
+use anyhow::Context;
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use tarantino_rs::client::Client;
+use tarantino_rs::interviews::api::models::{CreateInterviewRequest, InterviewListParams};
+use tarantino_rs::movies::api::models::{CreateMovieRequest, MovieListParams};
+use tarantino_rs::quotes::api::models::{CreateQuoteRequest, QuoteListParams};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Op {
+    List,
+    Get,
+    Create,
+    Update,
+    Delete,
+}
+
+impl Op {
+    fn as_str(self) -> &'static str {
+        match self {
+            Op::List => "list",
+            Op::Get => "get",
+            Op::Create => "create",
+            Op::Update => "update",
+            Op::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resource {
+    #[serde(alias = "movie")]
+    Movies,
+    #[serde(alias = "quote")]
+    Quotes,
+    #[serde(alias = "interview")]
+    Interviews,
+}
+
+/// One line of a batch file, with references still unresolved.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    /// Lets later lines refer to this line's output
+    name: Option<String>,
+    op: Op,
+    resource: Resource,
+    id: Option<Value>,
+    body: Option<Value>,
+    /// Query parameters of a `list`
+    params: Option<Value>,
+    #[serde(skip)]
+    line: usize,
+    /// Indexes of the earlier steps this one refers to
+    #[serde(skip)]
+    needs: BTreeSet<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineStatus {
+    Ok,
+    Failed,
+    /// Not run because an earlier line failed with `--stop-on-error`
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LineResult {
+    pub line: usize,
+    pub name: Option<String>,
+    pub op: Op,
+    pub resource: Resource,
+    pub status: LineStatus,
+    /// The record the line read, changed or created
+    pub id: Option<i32>,
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+}
+
+impl Columns for LineResult {
+    const COLUMNS: &'static [&'static str] =
+        &["line", "name", "op", "resource", "status", "id", "error", "output"];
+    const DEFAULT_COLUMNS: &'static [&'static str] =
+        &["line", "name", "op", "resource", "status", "id", "error"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchReport {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub results: Vec<LineResult>,
+}
+
+/// Parses and checks every line before anything runs, so a typo on the last
+/// line does not leave the first ones applied.
+pub fn read_steps(text: &str) -> anyhow::Result<Vec<Step>> {
+    let mut steps: Vec<Step> = Vec::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let number = number + 1;
+        let mut step: Step =
+            serde_json::from_str(line).with_context(|| format!("invalid line {}", number))?;
+        step.line = number;
+        check_fields(&step).with_context(|| format!("invalid line {}", number))?;
+
+        for value in [&step.id, &step.body, &step.params].into_iter().flatten() {
+            for reference in references(value) {
+                let name = reference.split('.').next().unwrap_or_default();
+                let index = names.get(name).with_context(|| {
+                    format!(
+                        "line {}: '${{{}}}' refers to no earlier line named '{}'",
+                        number, reference, name
+                    )
+                })?;
+                step.needs.insert(*index);
+            }
+        }
+
+        if let Some(name) = &step.name {
+            if let Some(index) = names.get(name) {
+                anyhow::bail!(
+                    "line {}: the name '{}' is already used on line {}",
+                    number,
+                    name,
+                    steps[*index].line
+                );
+            }
+            names.insert(name.clone(), steps.len());
+        }
+        steps.push(step);
+    }
+
+    Ok(steps)
+}
+
+fn check_fields(step: &Step) -> anyhow::Result<()> {
+    let needs_id = matches!(step.op, Op::Get | Op::Update | Op::Delete);
+    let needs_body = matches!(step.op, Op::Create | Op::Update);
+    if step.op == Op::Update && step.resource == Resource::Quotes {
+        anyhow::bail!("quotes cannot be updated");
+    }
+    if needs_id != step.id.is_some() {
+        anyhow::bail!("`id` is {} for {}", required(needs_id), step.op.as_str());
+    }
+    if needs_body != step.body.is_some() {
+        anyhow::bail!(
+            "`body` is {} for {}",
+            required(needs_body),
+            step.op.as_str()
+        );
+    }
+    if step.params.is_some() && step.op != Op::List {
+        anyhow::bail!("`params` only apply to list");
+    }
+    Ok(())
+}
+
+fn required(needed: bool) -> &'static str {
+    if needed { "required" } else { "not allowed" }
+}
+
+/// The `name.path` of every `${name.path}` in the strings of `value`.
+fn references(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(text) => {
+            let mut found = Vec::new();
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("${") {
+                let Some(end) = rest[start..].find('}') else {
+                    break;
+                };
+                found.push(rest[start + 2..start + end].to_string());
+                rest = &rest[start + end + 1..];
+            }
+            found
+        }
+        Value::Array(values) => values.iter().flat_map(references).collect(),
+        Value::Object(fields) => fields.values().flat_map(references).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Replaces the references in `value` with the outputs of earlier lines.
+fn resolve(value: &Value, outputs: &HashMap<String, Value>) -> anyhow::Result<Value> {
+    Ok(match value {
+        Value::String(text) => {
+            let found = references(value);
+            match found.as_slice() {
+                [] => value.clone(),
+                [reference] if *text == format!("${{{}}}", reference) => {
+                    lookup(reference, outputs)?.clone()
+                }
+                _ => {
+                    let mut text = text.clone();
+                    for reference in found {
+                        let replacement = match lookup(&reference, outputs)? {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        text = text.replace(&format!("${{{}}}", reference), &replacement);
+                    }
+                    Value::String(text)
+                }
+            }
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| resolve(value, outputs))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), resolve(value, outputs)?)))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        _ => value.clone(),
+    })
+}
+
+/// Follows `name.field.0.field` into the output of the line called `name`.
+fn lookup<'a>(reference: &str, outputs: &'a HashMap<String, Value>) -> anyhow::Result<&'a Value> {
+    let mut parts = reference.split('.');
+    let name = parts.next().unwrap_or_default();
+    let mut value = outputs.get(name).with_context(|| {
+        format!(
+            "'{}' did not succeed, so '${{{}}}' has no value",
+            name, reference
+        )
+    })?;
+    for part in parts {
+        value = match value {
+            Value::Array(values) => part.parse::<usize>().ok().and_then(|i| values.get(i)),
+            _ => value.get(part),
+        }
+        .with_context(|| format!("'${{{}}}': '{}' has no '{}'", reference, name, part))?;
+    }
+    Ok(value)
+}
+
+/// A step with its references replaced, ready to send.
+struct Resolved {
+    op: Op,
+    resource: Resource,
+    id: Option<i32>,
+    body: Option<Value>,
+    params: Value,
+}
+
+impl Step {
+    fn resolve(&self, outputs: &HashMap<String, Value>) -> anyhow::Result<Resolved> {
+        let id = match &self.id {
+            Some(id) => Some(
+                serde_json::from_value(resolve(id, outputs)?).context("`id` must be a number")?,
+            ),
+            None => None,
+        };
+        let body = match &self.body {
+            Some(body) => Some(resolve(body, outputs)?),
+            None => None,
+        };
+        let params = match &self.params {
+            Some(params) => resolve(params, outputs)?,
+            None => Value::Object(Default::default()),
+        };
+        Ok(Resolved {
+            op: self.op,
+            resource: self.resource,
+            id,
+            body,
+            params,
+        })
+    }
+
+    fn result(&self, status: LineStatus) -> LineResult {
+        LineResult {
+            line: self.line,
+            name: self.name.clone(),
+            op: self.op,
+            resource: self.resource,
+            status,
+            id: None,
+            error: None,
+            output: None,
+        }
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: Option<Value>, what: &str) -> anyhow::Result<T> {
+    serde_json::from_value(value.unwrap_or_default()).with_context(|| format!("invalid {}", what))
+}
+
+/// Sends one step, returning the response as JSON.
+async fn execute(client: &Client, step: Resolved) -> anyhow::Result<Value> {
+    let id = step.id.unwrap_or_default();
+    let params = Some(step.params);
+    let output = match (step.resource, step.op) {
+        (Resource::Movies, Op::List) => {
+            let params: MovieListParams = parse(params, "params")?;
+            serde_json::to_value(client.movies().list(&params).await?)?
+        }
+        (Resource::Movies, Op::Get) => serde_json::to_value(client.movies().get_movie(id).await?)?,
+        (Resource::Movies, Op::Create) => {
+            let body: CreateMovieRequest = parse(step.body, "body")?;
+            serde_json::to_value(client.movies().create_movie(&body).await?)?
+        }
+        (Resource::Movies, Op::Update) => {
+            let body: CreateMovieRequest = parse(step.body, "body")?;
+            serde_json::to_value(client.movies().update_movie(id, &body).await?)?
+        }
+        (Resource::Movies, Op::Delete) => {
+            serde_json::to_value(client.movies().delete_movie(id).await?)?
+        }
+        (Resource::Quotes, Op::List) => {
+            let params: QuoteListParams = parse(params, "params")?;
+            serde_json::to_value(client.quotes().list(&params).await?)?
+        }
+        (Resource::Quotes, Op::Get) => serde_json::to_value(client.quotes().get_quote(id).await?)?,
+        (Resource::Quotes, Op::Create) => {
+            let body: CreateQuoteRequest = parse(step.body, "body")?;
+            serde_json::to_value(client.quotes().create_quote(&body).await?)?
+        }
+        (Resource::Quotes, Op::Update) => anyhow::bail!("quotes cannot be updated"),
+        (Resource::Quotes, Op::Delete) => {
+            serde_json::to_value(client.quotes().delete_quote(id).await?)?
+        }
+        (Resource::Interviews, Op::List) => {
+            let params: InterviewListParams = parse(params, "params")?;
+            serde_json::to_value(client.interviews().list(&params).await?)?
+        }
+        (Resource::Interviews, Op::Get) => {
+            serde_json::to_value(client.interviews().get_interview(id).await?)?
+        }
+        (Resource::Interviews, Op::Create) => {
+            let body: CreateInterviewRequest = parse(step.body, "body")?;
+            serde_json::to_value(client.interviews().create_interview(&body).await?)?
+        }
+        (Resource::Interviews, Op::Update) => {
+            let body: CreateInterviewRequest = parse(step.body, "body")?;
+            serde_json::to_value(client.interviews().update_interview(id, &body).await?)?
+        }
+        (Resource::Interviews, Op::Delete) => {
+            serde_json::to_value(client.interviews().delete_interview(id).await?)?
+        }
+    };
+    Ok(output)
+}
+
+/// Results so far of a running batch.
+struct Progress<'a> {
+    steps: &'a [Step],
+    stop_on_error: bool,
+    results: Vec<Option<LineResult>>,
+    /// Outputs of the named lines that succeeded
+    outputs: HashMap<String, Value>,
+    stopped: bool,
+}
+
+impl Progress<'_> {
+    fn finish(&mut self, index: usize, outcome: anyhow::Result<(Option<i32>, Value)>) {
+        let step = &self.steps[index];
+        let result = match outcome {
+            Ok((id, output)) => {
+                if let Some(name) = &step.name {
+                    self.outputs.insert(name.clone(), output.clone());
+                }
+                LineResult {
+                    id,
+                    output: Some(output),
+                    ..step.result(LineStatus::Ok)
+                }
+            }
+            Err(e) => {
+                eprintln!("Line {} failed: {:#}", step.line, e);
+                self.stopped |= self.stop_on_error;
+                LineResult {
+                    error: Some(format!("{:#}", e)),
+                    ..step.result(LineStatus::Failed)
+                }
+            }
+        };
+        self.results[index] = Some(result);
+    }
+
+    fn is_running(&self, index: usize) -> bool {
+        self.results[index].is_none()
+    }
+}
+
+/// Runs the steps in file order, up to `parallel` at a time. A step waits for
+/// the steps it refers to; with `stop_on_error` no step starts after a failure.
+pub async fn run(
+    client: &Client,
+    steps: &[Step],
+    parallel: usize,
+    stop_on_error: bool,
+) -> BatchReport {
+    let mut progress = Progress {
+        steps,
+        stop_on_error,
+        results: vec![None; steps.len()],
+        outputs: HashMap::new(),
+        stopped: false,
+    };
+    let mut running = FuturesUnordered::new();
+
+    for (index, step) in steps.iter().enumerate() {
+        while !running.is_empty()
+            && (running.len() >= parallel || step.needs.iter().any(|i| progress.is_running(*i)))
+        {
+            if let Some((done, outcome)) = running.next().await {
+                progress.finish(done, outcome);
+            }
+        }
+        if progress.stopped {
+            progress.results[index] = Some(step.result(LineStatus::Skipped));
+            continue;
+        }
+
+        // A reference to a line that failed has no output and fails here
+        match step.resolve(&progress.outputs) {
+            Ok(resolved) => {
+                let target = resolved.id;
+                running.push(async move {
+                    let outcome = execute(client, resolved).await.map(|output| {
+                        let created = output["id"].as_i64().map(|id| id as i32);
+                        (created.or(target), output)
+                    });
+                    (index, outcome)
+                });
+            }
+            Err(e) => progress.finish(index, Err(e)),
+        }
+    }
+    while let Some((done, outcome)) = running.next().await {
+        progress.finish(done, outcome);
+    }
+
+    let results: Vec<LineResult> = progress.results.into_iter().flatten().collect();
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    BatchReport {
+        succeeded: count(LineStatus::Ok),
+        failed: count(LineStatus::Failed),
+        skipped: count(LineStatus::Skipped),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn references_must_name_an_earlier_line() {
+        let steps = read_steps(
+            r#"{"name": "q", "op": "create", "resource": "quote", "body": {"text": "Hi"}}
+
+{"op": "delete", "resource": "quotes", "id": "${q.id}"}"#,
+        )
+        .unwrap();
+        assert_eq!(steps[1].line, 3);
+        assert_eq!(steps[1].needs, BTreeSet::from([0]));
+
+        let later = r#"{"op": "get", "resource": "quotes", "id": "${q.id}"}
+{"name": "q", "op": "get", "resource": "quotes", "id": 1}"#;
+        let error = read_steps(later).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "line 1: '${q.id}' refers to no earlier line named 'q'"
+        );
+
+        let missing_id = r#"{"op": "get", "resource": "movies"}"#;
+        let error = format!("{:#}", read_steps(missing_id).unwrap_err());
+        assert_eq!(error, "invalid line 1: `id` is required for get");
+    }
+
+    #[test]
+    fn resolve_keeps_types_of_whole_references() {
+        let outputs = HashMap::from([(
+            "list".to_string(),
+            json!({"movies": [{"id": 7, "title": "Jackie Brown"}], "total": 1}),
+        )]);
+
+        let body = json!({
+            "id": "${list.movies.0.id}",
+            "title": "${list.movies.0.title} (${list.total} of them)",
+            "plain": ["no references", 3],
+        });
+        assert_eq!(
+            resolve(&body, &outputs).unwrap(),
+            json!({
+                "id": 7,
+                "title": "Jackie Brown (1 of them)",
+                "plain": ["no references", 3],
+            })
+        );
+
+        let error = resolve(&json!("${list.movies.1.id}"), &outputs).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "'${list.movies.1.id}': 'list' has no '1'"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tarantino_rs::interviews::api::models::InterviewResponse;
+use tarantino_rs::movies::api::models::{
+    MovieAwardNominationResponse, MovieAwardResponse, MovieResponse,
+};
+use tarantino_rs::quotes::api::models::QuoteResponse;
+
+/// Rows moved by Page Up and Page Down.
+const PAGE: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tab {
+    #[default]
+    Movies,
+    Quotes,
+    Interviews,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 3] = [Tab::Movies, Tab::Quotes, Tab::Interviews];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Movies => "Movies",
+            Tab::Quotes => "Quotes",
+            Tab::Interviews => "Interviews",
+        }
+    }
+
+    /// Singular name used in prompts and messages
+    pub fn noun(self) -> &'static str {
+        match self {
+            Tab::Movies => "movie",
+            Tab::Quotes => "quote",
+            Tab::Interviews => "interview",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|tab| *tab == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    fn previous(self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+}
+
+/// Cast, crew and awards of a movie, read from its sub-resource endpoints.
+#[derive(Debug, Clone)]
+pub struct MovieDetail {
+    pub movie: MovieResponse,
+    pub directors: Vec<String>,
+    pub writers: Vec<String>,
+    pub producers: Vec<String>,
+    pub actors: Vec<String>,
+    pub awards: Vec<MovieAwardResponse>,
+    pub nominations: Vec<MovieAwardNominationResponse>,
+}
+
+/// A record picked for deletion, waiting for `y`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDelete {
+    pub tab: Tab,
+    pub id: i32,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Browse,
+    /// Keys go to the filter text
+    Filter,
+    Detail,
+    Confirm(PendingDelete),
+}
+
+/// Work for the event loop that needs the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Reload,
+    ShowMovie(i32),
+    Delete(PendingDelete),
+}
+
+#[derive(Debug, Default)]
+pub struct App {
+    pub tab: Tab,
+    pub mode: Mode,
+    pub movies: Vec<MovieResponse>,
+    pub quotes: Vec<QuoteResponse>,
+    pub interviews: Vec<InterviewResponse>,
+    /// Filter text per tab, in `Tab::ALL` order
+    pub filters: [String; 3],
+    /// Selected row of the filtered list, per tab
+    pub selected: [usize; 3],
+    pub detail: Option<MovieDetail>,
+    /// Line shown in the status bar, such as the last error
+    pub status: Option<String>,
+}
+
+impl App {
+    pub fn tab(&self) -> Tab {
+        self.tab
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filters[self.tab().index()]
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected[self.tab().index()]
+    }
+
+    /// `(id, label)` of the rows of the current tab that match its filter.
+    pub fn rows(&self) -> Vec<(i32, String)> {
+        let filter = self.filter().to_lowercase();
+        let matches = |text: &str| filter.is_empty() || text.to_lowercase().contains(&filter);
+        match self.tab() {
+            Tab::Movies => self
+                .movies
+                .iter()
+                .filter(|movie| {
+                    matches(&movie.title)
+                        || matches(&movie.genre)
+                        || matches(&movie.release_year.to_string())
+                        || movie.directors.iter().any(|name| matches(name))
+                })
+                .map(|movie| {
+                    (
+                        movie.id,
+                        format!("{} ({})", movie.title, movie.release_year),
+                    )
+                })
+                .collect(),
+            Tab::Quotes => self
+                .quotes
+                .iter()
+                .filter(|quote| matches(&quote.text))
+                .map(|quote| (quote.id, quote.text.clone()))
+                .collect(),
+            Tab::Interviews => self
+                .interviews
+                .iter()
+                .filter(|interview| matches(&interview.title) || matches(&interview.description))
+                .map(|interview| (interview.id, interview.title.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn selected_movie(&self) -> Option<&MovieResponse> {
+        let (id, _) = self.rows().into_iter().nth(self.selected())?;
+        self.movies.iter().find(|movie| movie.id == id)
+    }
+
+    pub fn selected_interview(&self) -> Option<&InterviewResponse> {
+        let (id, _) = self.rows().into_iter().nth(self.selected())?;
+        self.interviews.iter().find(|interview| interview.id == id)
+    }
+
+    /// Keeps every tab's selection inside its list, e.g. after a reload.
+    pub fn clamp_selection(&mut self) {
+        let current = self.tab;
+        for tab in Tab::ALL {
+            self.tab = tab;
+            let last = self.rows().len().saturating_sub(1);
+            self.selected[tab.index()] = self.selected[tab.index()].min(last);
+        }
+        self.tab = current;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.rows().len().saturating_sub(1);
+        let selected = &mut self.selected[self.tab().index()];
+        *selected = selected.saturating_add_signed(delta).min(last);
+    }
+
+    fn switch_tab(&mut self, tab: Tab) {
+        self.tab = tab;
+        self.status = None;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+
+        match self.mode.clone() {
+            Mode::Filter => {
+                let index = self.tab().index();
+                match key.code {
+                    KeyCode::Enter => self.mode = Mode::Browse,
+                    KeyCode::Esc => {
+                        self.filters[index].clear();
+                        self.mode = Mode::Browse;
+                    }
+                    KeyCode::Backspace => {
+                        self.filters[index].pop();
+                    }
+                    KeyCode::Char(c) => self.filters[index].push(c),
+                    _ => {}
+                }
+                self.selected[index] = 0;
+                None
+            }
+            Mode::Confirm(pending) => {
+                // Back to the movie on screen, if the delete started there
+                self.mode = if self.detail.is_some() {
+                    Mode::Detail
+                } else {
+                    Mode::Browse
+                };
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => Some(Action::Delete(pending)),
+                    _ => {
+                        self.status = Some("Delete cancelled".to_string());
+                        None
+                    }
+                }
+            }
+            Mode::Detail => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => {
+                        self.mode = Mode::Browse;
+                        self.detail = None;
+                    }
+                    KeyCode::Char('d') | KeyCode::Delete => self.confirm_delete(),
+                    _ => {}
+                }
+                None
+            }
+            Mode::Browse => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+                KeyCode::Tab | KeyCode::Right => {
+                    self.switch_tab(self.tab().next());
+                    None
+                }
+                KeyCode::BackTab | KeyCode::Left => {
+                    self.switch_tab(self.tab().previous());
+                    None
+                }
+                KeyCode::Char(c @ '1'..='3') => {
+                    self.switch_tab(Tab::ALL[c as usize - '1' as usize]);
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_selection(1);
+                    None
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_selection(-1);
+                    None
+                }
+                KeyCode::PageDown => {
+                    self.move_selection(PAGE as isize);
+                    None
+                }
+                KeyCode::PageUp => {
+                    self.move_selection(-(PAGE as isize));
+                    None
+                }
+                KeyCode::Home | KeyCode::Char('g') => {
+                    self.selected[self.tab().index()] = 0;
+                    None
+                }
+                KeyCode::End | KeyCode::Char('G') => {
+                    self.move_selection(isize::MAX);
+                    None
+                }
+                KeyCode::Char('/') => {
+                    self.mode = Mode::Filter;
+                    None
+                }
+                KeyCode::Char('r') => Some(Action::Reload),
+                KeyCode::Enter if self.tab() == Tab::Movies => self
+                    .selected_movie()
+                    .map(|movie| Action::ShowMovie(movie.id)),
+                KeyCode::Char('d') | KeyCode::Delete => {
+                    self.confirm_delete();
+                    None
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Asks before deleting the selected record, or the movie on screen.
+    fn confirm_delete(&mut self) {
+        let pending = match (&self.detail, self.mode == Mode::Detail) {
+            (Some(detail), true) => Some((detail.movie.id, detail.movie.title.clone())),
+            _ => self.rows().into_iter().nth(self.selected()),
+        };
+        if let Some((id, label)) = pending {
+            self.mode = Mode::Confirm(PendingDelete {
+                tab: self.tab(),
+                id,
+                label,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn quote(id: i32, text: &str) -> QuoteResponse {
+        QuoteResponse {
+            id,
+            text: text.to_string(),
+        }
+    }
+
+    fn app_with_quotes() -> App {
+        let mut app = App {
+            quotes: vec![
+                quote(1, "Say what again"),
+                quote(2, "Royale with cheese"),
+                quote(3, "What's in the case?"),
+            ],
+            ..App::default()
+        };
+        app.handle_key(key(KeyCode::Char('2')));
+        app
+    }
+
+    #[test]
+    fn filter_narrows_rows_as_you_type() {
+        let mut app = app_with_quotes();
+        app.handle_key(key(KeyCode::Down));
+
+        app.handle_key(key(KeyCode::Char('/')));
+        for c in "WHAT".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        let ids: Vec<_> = app.rows().into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(app.selected(), 0);
+
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.rows().len(), 3);
+        assert_eq!(app.mode, Mode::Browse);
+    }
+
+    #[test]
+    fn delete_needs_confirmation() {
+        let mut app = app_with_quotes();
+        app.handle_key(key(KeyCode::End));
+
+        assert_eq!(app.handle_key(key(KeyCode::Char('d'))), None);
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), None);
+        assert_eq!(app.mode, Mode::Browse);
+
+        app.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('y'))),
+            Some(Action::Delete(PendingDelete {
+                tab: Tab::Quotes,
+                id: 3,
+                label: "What's in the case?".to_string(),
+            }))
+        );
+    }
+}
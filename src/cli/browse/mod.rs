@@ -0,0 +1,127 @@
+//! `tarantinors-cli browse`: a full-screen browser for the catalogue.
+//!
+//! `app` holds the state and turns key presses into [`app::Action`]s, `ui`
+//! draws it, and this module runs the actions against the API.
+
+mod app;
+mod ui;
+
+use app::{Action, App, Mode, MovieDetail, PendingDelete, Tab};
+use crossterm::event::{Event, EventStream, KeyEventKind};
+use futures::StreamExt;
+use ratatui::DefaultTerminal;
+use std::io::IsTerminal;
+use tarantino_rs::client::{Client, ClientResult};
+use tarantino_rs::movies::api::models::MovieListParams;
+
+/// Runs the browser until the user quits, restoring the terminal afterwards.
+pub async fn browse(client: &Client) -> anyhow::Result<()> {
+    if !std::io::stdout().is_terminal() {
+        anyhow::bail!("browse needs an interactive terminal");
+    }
+
+    let mut app = App::default();
+    // Fail before taking over the screen when the server can't be reached
+    reload(client, &mut app).await?;
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, client, &mut app).await;
+    ratatui::try_restore()?;
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    client: &Client,
+    app: &mut App,
+) -> anyhow::Result<()> {
+    let mut events = EventStream::new();
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        let Some(event) = events.next().await else {
+            return Ok(());
+        };
+        let Event::Key(key) = event? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        app.status = None;
+        let Some(action) = app.handle_key(key) else {
+            continue;
+        };
+        let result = match action {
+            Action::Quit => return Ok(()),
+            Action::Reload => reload(client, app).await,
+            Action::ShowMovie(id) => show_movie(client, app, id).await,
+            Action::Delete(pending) => delete(client, app, pending).await,
+        };
+        // Errors stay on screen; the user can retry or quit
+        if let Err(e) = result {
+            app.status = Some(format!("Error: {:#}", anyhow::Error::from(e)));
+        }
+    }
+}
+
+async fn reload(client: &Client, app: &mut App) -> ClientResult<()> {
+    let movies = client.movies();
+    let quotes = client.quotes();
+    let interviews = client.interviews();
+    let (movies, quotes, interviews) = futures::try_join!(
+        movies.all(MovieListParams::default()),
+        quotes.all(),
+        interviews.all()
+    )?;
+    app.movies = movies;
+    app.quotes = quotes;
+    app.interviews = interviews;
+    app.clamp_selection();
+    Ok(())
+}
+
+/// Reads the movie and its cast, crew and awards from their endpoints.
+async fn show_movie(client: &Client, app: &mut App, id: i32) -> ClientResult<()> {
+    let movies = client.movies();
+    let (movie, directors, writers, producers, actors, awards, nominations) = futures::try_join!(
+        movies.get_movie(id),
+        movies.get_directors(id),
+        movies.get_writers(id),
+        movies.get_producers(id),
+        movies.get_actors(id),
+        movies.get_awards(id),
+        movies.get_nominations(id),
+    )?;
+    app.detail = Some(MovieDetail {
+        movie,
+        directors: directors.directors,
+        writers: writers.writers,
+        producers: producers.producers,
+        actors: actors.actors,
+        awards: awards.awards,
+        nominations: nominations.nominations,
+    });
+    app.mode = Mode::Detail;
+    Ok(())
+}
+
+async fn delete(client: &Client, app: &mut App, pending: PendingDelete) -> ClientResult<()> {
+    let message = match pending.tab {
+        Tab::Movies => client.movies().delete_movie(pending.id).await?.message,
+        Tab::Quotes => client.quotes().delete_quote(pending.id).await?.message,
+        Tab::Interviews => {
+            client
+                .interviews()
+                .delete_interview(pending.id)
+                .await?
+                .message
+        }
+    };
+    app.detail = None;
+    app.mode = Mode::Browse;
+    reload(client, app).await?;
+    app.status = Some(message);
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use super::app::{App, Mode, MovieDetail, Tab};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [tabs, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let titles: Vec<_> = Tab::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+        .collect();
+    let selected = Tab::ALL
+        .iter()
+        .position(|tab| *tab == app.tab())
+        .unwrap_or(0);
+    frame.render_widget(
+        Tabs::new(titles)
+            .select(selected)
+            .highlight_style(Style::new().bold().reversed())
+            .block(Block::bordered().title(" tarantinors ")),
+        tabs,
+    );
+
+    match (&app.mode, &app.detail) {
+        (Mode::Detail, Some(detail)) => draw_movie(frame, body, detail),
+        _ => draw_list(frame, body, app),
+    }
+
+    frame.render_widget(Paragraph::new(footer_line(app)), footer);
+
+    if let Mode::Confirm(pending) = &app.mode {
+        let area = centered(body, 60, 5);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(format!(
+                    "Delete {} '{}'?",
+                    pending.tab.noun(),
+                    pending.label
+                )),
+                Line::from(""),
+                Line::from("y to delete, any other key to cancel").italic(),
+            ])
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(" Confirm ").red()),
+            area,
+        );
+    }
+}
+
+fn draw_list(frame: &mut Frame, area: Rect, app: &App) {
+    let [list, preview] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(area);
+
+    let rows = app.rows();
+    let title = if app.filter().is_empty() {
+        format!(" {} ({}) ", app.tab().title(), rows.len())
+    } else {
+        format!(
+            " {} ({}) matching '{}' ",
+            app.tab().title(),
+            rows.len(),
+            app.filter()
+        )
+    };
+    let items: Vec<_> = rows
+        .into_iter()
+        .map(|(_, label)| ListItem::new(label))
+        .collect();
+    let mut state = ListState::default().with_selected(Some(app.selected()));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> "),
+        list,
+        &mut state,
+    );
+
+    frame.render_widget(
+        Paragraph::new(preview_lines(app))
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title(" Preview ")),
+        preview,
+    );
+}
+
+fn preview_lines(app: &App) -> Vec<Line<'static>> {
+    match app.tab() {
+        Tab::Movies => match app.selected_movie() {
+            Some(movie) => vec![
+                Line::from(movie.title.clone()).bold(),
+                field("Year", movie.release_year.to_string()),
+                field("Genre", movie.genre.clone()),
+                field("Rating", format!("{:.1}", movie.rating)),
+                field("Runtime", format!("{} min", movie.runtime)),
+                field("Directed by", movie.directors.join(", ")),
+                Line::from(""),
+                Line::from(movie.plot.clone()),
+                Line::from(""),
+                Line::from("Enter for cast, crew and awards").italic(),
+            ],
+            None => Vec::new(),
+        },
+        Tab::Quotes => app
+            .rows()
+            .into_iter()
+            .nth(app.selected())
+            .map(|(_, text)| vec![Line::from(text)])
+            .unwrap_or_default(),
+        Tab::Interviews => match app.selected_interview() {
+            Some(interview) => vec![
+                Line::from(interview.title.clone()).bold(),
+                field(
+                    "Video",
+                    format!("https://www.youtube.com/watch?v={}", interview.youtube_id),
+                ),
+                Line::from(""),
+                Line::from(interview.description.clone()),
+            ],
+            None => Vec::new(),
+        },
+    }
+}
+
+fn draw_movie(frame: &mut Frame, area: Rect, detail: &MovieDetail) {
+    let movie = &detail.movie;
+    let mut lines = vec![
+        field("Released", movie.release_date.to_string()),
+        field("Genre", movie.genre.clone()),
+        field("Rating", format!("{:.1}", movie.rating)),
+        field("Runtime", format!("{} min", movie.runtime)),
+        field("Budget", movie.budget.clone()),
+        field("Production", movie.production_details.clone()),
+        Line::from(""),
+        Line::from(movie.plot.clone()),
+    ];
+
+    let people = [
+        ("Directors", &detail.directors),
+        ("Writers", &detail.writers),
+        ("Producers", &detail.producers),
+        ("Cast", &detail.actors),
+    ];
+    for (heading, names) in people {
+        lines.push(Line::from(""));
+        lines.push(Line::from(heading).bold().underlined());
+        lines.extend(names.iter().map(|name| Line::from(format!("  {}", name))));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from("Awards").bold().underlined());
+    lines.extend(detail.awards.iter().map(|award| {
+        award_line(
+            award.year,
+            &award.name,
+            &award.category,
+            award.recipient.as_deref(),
+        )
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::from("Nominations").bold().underlined());
+    lines.extend(detail.nominations.iter().map(|nomination| {
+        award_line(
+            nomination.year,
+            &nomination.name,
+            &nomination.category,
+            nomination.nominee.as_deref(),
+        )
+    }));
+
+    let title = format!(" {} ({}) ", movie.title, movie.release_year);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+fn award_line(year: i32, name: &str, category: &str, person: Option<&str>) -> Line<'static> {
+    let mut text = format!("  {} {}, {}", year, name, category);
+    if let Some(person) = person {
+        text.push_str(&format!(" ({})", person));
+    }
+    Line::from(text)
+}
+
+fn field(name: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::from(format!("{}: ", name)).bold(),
+        Span::from(value),
+    ])
+}
+
+fn footer_line(app: &App) -> Line<'static> {
+    if let Some(status) = &app.status {
+        return Line::from(status.clone()).yellow();
+    }
+    let keys = match app.mode {
+        Mode::Filter => format!("/{}  Enter keep filter  Esc clear", app.filter()),
+        Mode::Detail => "Esc back  d delete  Ctrl-C quit".to_string(),
+        Mode::Confirm(_) => "y confirm  any key cancel".to_string(),
+        Mode::Browse => {
+            "Tab/1-3 switch  j/k move  / filter  Enter details  d delete  r reload  q quit"
+                .to_string()
+        }
+    };
+    Line::from(keys).dim()
+}
+
+/// A `width` percent wide, `height` rows tall rectangle in the middle of `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [_, row, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, area, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Percentage(width),
+        Constraint::Fill(1),
+    ])
+    .areas(row);
+    area
+}
@@ -1,17 +1,67 @@
-mod http_client;
-mod services;
-use clap::{Parser, Subcommand};
-use serde::{Deserialize, Serialize};
-use services::{interviews::InterviewsService, movies::MoviesService, quotes::QuotesService};
+mod batch;
+mod browse;
+mod config;
+mod errors;
+mod input;
+mod output;
+mod sync;
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use config::{Config, Profile, Settings, mask_token};
+use errors::{BatchFailed, EXIT_CODES_HELP, ImportFailed};
+use input::{body_or_prompt, parse_body};
+use output::{Output, OutputFormat, Person};
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tarantino_rs::client::{Client, InterviewsService, MoviesService, QuotesService};
+use tarantino_rs::export::api::models::{ArchiveFormat, ExportParams};
+use tarantino_rs::import::api::models::{
+    ArchiveImportParams, ImportFormat, ImportMode, ImportParams, ImportResource,
+};
 use tarantino_rs::interviews::api::models::CreateInterviewRequest;
-use tarantino_rs::movies::api::models::CreateMovieRequest;
+use tarantino_rs::movies::api::models::{
+    CreateMovieRequest, MovieListParams, MovieSort, SortOrder,
+};
 use tarantino_rs::quotes::api::models::CreateQuoteRequest;
+use tarantino_rs::telemetry;
+use tracing::{Instrument, info_span};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{EnvFilter, filter::LevelFilter};
 #[derive(Parser)]
 #[command(name = "tarantinors-cli")]
 #[command(author, version, about = "Manage Tarantinors app data through api", long_about = None)]
+#[command(after_help = EXIT_CODES_HELP)]
 struct Cli {
-    #[arg(short, long, global = true, default_value = "http://localhost:3000")]
-    pub url: String,
+    /// The profile from the config file to use, see `config path`
+    #[arg(short, long, global = true, env = "TARANTINORS_PROFILE")]
+    pub profile: Option<String>,
+
+    /// The server to talk to [default: the profile url, or http://localhost:3000]
+    #[arg(short, long, global = true, env = "TARANTINORS_URL")]
+    pub url: Option<String>,
+
+    /// How to print results; tables and JSON are plain when stdout is not a terminal
+    /// [default: the profile output, or table]
+    #[arg(short, long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// Seconds to wait for each attempt of a request [default: the profile timeout, or 30]
+    #[arg(long, global = true)]
+    pub timeout: Option<u64>,
+
+    /// Seconds to wait for a connection [default: the profile connect timeout, or 10]
+    #[arg(long, global = true)]
+    pub connect_timeout: Option<u64>,
+
+    /// Retries of reads and deletes after connection errors, 429 and 503
+    /// [default: the profile retries, or 3]
+    #[arg(long, global = true)]
+    pub retries: Option<u32>,
+
+    /// Comma separated fields to show in table and CSV output
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub columns: Option<Vec<String>>,
 
     #[command(subcommand)]
     pub command: Commands,
@@ -22,7 +72,7 @@ pub enum Commands {
     /// Movies Operations
     Movies {
         #[command(subcommand)]
-        command: MoviesCommands,
+        command: Box<MoviesCommands>,
     },
     /// Interviews Operations
     Interviews {
@@ -34,82 +84,566 @@ pub enum Commands {
         #[command(subcommand)]
         command: QuotesCommands,
     },
+    /// Bulk import records from a JSON, JSONL or CSV file, or an export archive
+    Import {
+        /// The kind of records in the file, `archive` for an export archive
+        #[arg(value_enum)]
+        resource: ImportResourceArg,
+
+        /// The file to import
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// The file format, guessed from the file extension when omitted
+        #[arg(long, value_enum)]
+        format: Option<ImportFormatArg>,
+
+        /// Import the valid records even when others fail
+        #[arg(long)]
+        best_effort: bool,
+    },
+    /// Export every movie, quote and interview as an archive
+    Export {
+        /// The archive layout, guessed from the output file extension when omitted
+        #[arg(long, value_enum)]
+        format: Option<ArchiveFormatArg>,
+
+        /// Write the archive to this file instead of stdout
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+    /// Make the server match a directory of movies/, quotes/ and interviews/ JSON files
+    Sync {
+        /// The directory to sync from; resources without a folder are left alone
+        dir: PathBuf,
+
+        /// Print the planned changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run the operations of a JSONL file, one per line
+    Batch {
+        /// The JSONL file, `-` for stdin
+        file: PathBuf,
+
+        /// Start no more lines after one fails; the rest are reported as skipped
+        #[arg(long)]
+        stop_on_error: bool,
+
+        /// How many lines run at once; a line still waits for the lines it refers to
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..=32))]
+        parallel: u16,
+    },
+    /// Browse movies, quotes and interviews in a full-screen terminal UI
+    Browse {},
+    /// Store an API token in the active profile, read from a hidden prompt or stdin
+    Login {},
+    /// Manage the profiles in the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
 }
 
 #[derive(Subcommand, Debug)]
-pub enum MoviesCommands {
-    /// List all movies
+pub enum ConfigCommands {
+    /// Print where the config file lives
+    Path {},
+
+    /// List the profiles, marking the default one
     List {},
 
+    /// Show the settings a profile resolves to, with the token masked
+    Show {
+        /// The profile, the active one when omitted
+        name: Option<String>,
+    },
+
+    /// Create a profile or change some of its settings
+    Set {
+        name: String,
+
+        #[arg(long)]
+        url: Option<String>,
+
+        #[arg(long, value_enum)]
+        output: Option<OutputFormat>,
+
+        /// Seconds to wait for each attempt of a request
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Seconds to wait for a connection
+        #[arg(long)]
+        connect_timeout: Option<u64>,
+
+        /// Retries of reads and deletes after connection errors, 429 and 503
+        #[arg(long)]
+        retries: Option<u32>,
+    },
+
+    /// Make a profile the default
+    Use { name: String },
+
+    /// Forget the token stored in a profile
+    Logout {
+        /// The profile, the active one when omitted
+        name: Option<String>,
+    },
+
+    /// Delete a profile
+    Remove { name: String },
+}
+
+impl ConfigCommands {
+    pub fn execute(&self, config: &mut Config, active: &str) -> anyhow::Result<String> {
+        match self {
+            ConfigCommands::Path {} => Ok(Config::path()?.display().to_string()),
+            ConfigCommands::List {} => {
+                let default = config.active_name(None);
+                Ok(config
+                    .profiles
+                    .iter()
+                    .map(|(name, profile)| {
+                        let marker = if *name == default { "*" } else { " " };
+                        let url = profile.url.as_deref().unwrap_or(config::DEFAULT_URL);
+                        format!("{} {}\t{}", marker, name, url)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            ConfigCommands::Show { name } => {
+                let name = name.as_deref().unwrap_or(active);
+                let profile = config.profile(name, true)?;
+                let settings = Settings::resolve(&profile, Profile::default());
+                let token = settings
+                    .client
+                    .token
+                    .as_deref()
+                    .map(mask_token)
+                    .unwrap_or_else(|| "(none)".to_string());
+                Ok(format!(
+                    "profile\t{}\nurl\t{}\noutput\t{}\ntimeout\t{}s\nconnect_timeout\t{}s\nretries\t{}\ntoken\t{}",
+                    name,
+                    settings.client.url,
+                    settings
+                        .output
+                        .to_possible_value()
+                        .map(|value| value.get_name().to_string())
+                        .unwrap_or_default(),
+                    settings.client.timeout.as_secs(),
+                    settings.client.connect_timeout.as_secs(),
+                    settings.client.retry.max_retries,
+                    token
+                ))
+            }
+            ConfigCommands::Set {
+                name,
+                url,
+                output,
+                timeout,
+                connect_timeout,
+                retries,
+            } => {
+                config
+                    .profiles
+                    .entry(name.clone())
+                    .or_default()
+                    .merge(Profile {
+                        url: url.clone(),
+                        output: *output,
+                        timeout_secs: *timeout,
+                        connect_timeout_secs: *connect_timeout,
+                        retries: *retries,
+                        token: None,
+                    });
+                let path = config.save()?;
+                Ok(format!("Profile '{}' saved to {}", name, path.display()))
+            }
+            ConfigCommands::Use { name } => {
+                config.profile(name, true)?;
+                config.default_profile = Some(name.clone());
+                config.save()?;
+                Ok(format!("Using profile '{}' by default", name))
+            }
+            ConfigCommands::Logout { name } => {
+                let name = name.as_deref().unwrap_or(active);
+                if let Some(profile) = config.profiles.get_mut(name) {
+                    profile.token = None;
+                    config.save()?;
+                }
+                Ok(format!("Removed the token of profile '{}'", name))
+            }
+            ConfigCommands::Remove { name } => {
+                if config.profiles.remove(name).is_none() {
+                    anyhow::bail!("no profile named '{}'", name);
+                }
+                if config.default_profile.as_deref() == Some(name.as_str()) {
+                    config.default_profile = None;
+                }
+                config.save()?;
+                Ok(format!("Profile '{}' removed", name))
+            }
+        }
+    }
+}
+
+/// Reads a token without echoing it, or from stdin when it is piped in, so
+/// it never ends up in the shell history.
+fn read_token() -> anyhow::Result<String> {
+    let token = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password("API token: ")?
+    } else {
+        let mut token = String::new();
+        std::io::stdin().read_to_string(&mut token)?;
+        token
+    };
+
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("no token given");
+    }
+    Ok(token)
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ImportResourceArg {
+    Movies,
+    Quotes,
+    Interviews,
+    Archive,
+}
+
+impl ImportResourceArg {
+    /// The resource of a plain import, `None` for an export archive.
+    fn resource(self) -> Option<ImportResource> {
+        match self {
+            ImportResourceArg::Movies => Some(ImportResource::Movies),
+            ImportResourceArg::Quotes => Some(ImportResource::Quotes),
+            ImportResourceArg::Interviews => Some(ImportResource::Interviews),
+            ImportResourceArg::Archive => None,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ImportFormatArg {
+    Json,
+    Jsonl,
+    Csv,
+}
+
+impl From<ImportFormatArg> for ImportFormat {
+    fn from(format: ImportFormatArg) -> Self {
+        match format {
+            ImportFormatArg::Json => ImportFormat::Json,
+            ImportFormatArg::Jsonl => ImportFormat::Jsonl,
+            ImportFormatArg::Csv => ImportFormat::Csv,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ArchiveFormatArg {
+    Json,
+    Jsonl,
+}
+
+impl From<ArchiveFormatArg> for ArchiveFormat {
+    fn from(format: ArchiveFormatArg) -> Self {
+        match format {
+            ArchiveFormatArg::Json => ArchiveFormat::Json,
+            ArchiveFormatArg::Jsonl => ArchiveFormat::Jsonl,
+        }
+    }
+}
+
+/// Guesses the import format from the file extension, defaulting to a JSON array.
+fn import_format_from_path(path: &Path) -> ImportFormat {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("csv") => ImportFormat::Csv,
+        Some("jsonl") | Some("ndjson") => ImportFormat::Jsonl,
+        _ => ImportFormat::Json,
+    }
+}
+
+impl Commands {
+    fn name(&self) -> &'static str {
+        match self {
+            Commands::Movies { .. } => "movies",
+            Commands::Interviews { .. } => "interviews",
+            Commands::Quotes { .. } => "quotes",
+            Commands::Import { .. } => "import",
+            Commands::Export { .. } => "export",
+            Commands::Sync { .. } => "sync",
+            Commands::Browse { .. } => "browse",
+            Commands::Batch { .. } => "batch",
+            Commands::Login { .. } => "login",
+            Commands::Config { .. } => "config",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MoviesCommands {
+    /// List movies, optionally filtered and sorted
+    List {
+        #[command(flatten)]
+        filters: MovieFilterArgs,
+    },
+
+    /// Find movies whose title or plot contains some text
+    Search {
+        /// The text to look for, ignoring case
+        text: String,
+
+        #[command(flatten)]
+        filters: MovieFilterArgs,
+    },
+
     /// Create a new movie
     Create {
-        /// The Movie body
-        #[arg(short, long, value_parser = parse_json::<CreateMovieRequest>)]
-        body: CreateMovieRequest,
+        /// The movie as JSON: inline, `@path.json` to read a file or `-` to read stdin
+        #[arg(
+            short,
+            long,
+            value_parser = parse_body::<CreateMovieRequest>,
+            required_unless_present = "interactive",
+            conflicts_with = "interactive"
+        )]
+        body: Option<CreateMovieRequest>,
+
+        /// Prompt for each field instead of passing a body
+        #[arg(long)]
+        interactive: bool,
     },
 
     /// Read a movie
     Read {
-        #[arg(short, long, default_value_t = 1)]
-        movie_id: i32,
+        #[command(flatten)]
+        movie: MovieRef,
     },
 
     /// Delete a movie
     Delete {
-        #[arg(short, long, default_value_t = 1)]
-        movie_id: i32,
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the actors of a movie
+    Actors {
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the directors of a movie
+    Directors {
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the producers of a movie
+    Producers {
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the writers of a movie
+    Writers {
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the awards a movie won
+    Awards {
+        #[command(flatten)]
+        movie: MovieRef,
+    },
+
+    /// List the award nominations of a movie
+    Nominations {
+        #[command(flatten)]
+        movie: MovieRef,
     },
 }
 
-impl MoviesCommands {
-    pub async fn execute(&self, movies_service: &MoviesService) -> String {
-        match self {
-            MoviesCommands::List {} => {
-                // Implement listing movies
-                let command_results = match movies_service.list().await {
-                    Ok(movies) => serde_json::to_string_pretty(&movies)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
-            }
-            MoviesCommands::Create { body } => {
-                // Implement creating a movie
+/// Picks a movie by id or by title.
+#[derive(Args, Debug)]
+pub struct MovieRef {
+    #[arg(short, long, default_value_t = 1)]
+    movie_id: i32,
 
-                let command_results = match movies_service.create_movie(body).await {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
+    /// The whole title, ignoring case, instead of the id
+    #[arg(short, long, conflicts_with = "movie_id")]
+    title: Option<String>,
+}
 
-                command_results
-            }
-            MoviesCommands::Read { movie_id } => {
-                // Implement reading a movie
+impl MovieRef {
+    async fn id(&self, movies_service: &MoviesService) -> anyhow::Result<i32> {
+        match &self.title {
+            Some(title) => Ok(movies_service.find_id_by_title(title).await?),
+            None => Ok(self.movie_id),
+        }
+    }
+}
 
-                let command_results = match movies_service.get_movie(movie_id.clone()).await {
-                    Ok(movies) => serde_json::to_string_pretty(&movies)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
+/// Mirrors the query parameters of `GET /movies`.
+#[derive(Args, Debug)]
+pub struct MovieFilterArgs {
+    /// Only movies of this genre, ignoring case
+    #[arg(long)]
+    genre: Option<String>,
 
-                command_results
-            }
-            MoviesCommands::Delete { movie_id } => {
-                // Implement deleting a movie
+    /// Only movies released this year
+    #[arg(long)]
+    year: Option<i32>,
+
+    /// Only movies directed by someone whose name contains this
+    #[arg(long)]
+    director: Option<String>,
+
+    /// Only movies with an actor whose name contains this
+    #[arg(long)]
+    actor: Option<String>,
+
+    /// Only movies rated at least this
+    #[arg(long)]
+    min_rating: Option<f32>,
+
+    /// Order by this field, ties by title
+    #[arg(long, value_enum, default_value_t = MovieSortArg::ReleaseYear)]
+    sort: MovieSortArg,
+
+    /// Show the highest values first
+    #[arg(long)]
+    desc: bool,
+
+    /// Show at most this many movies (1-100)
+    #[arg(long)]
+    limit: Option<i64>,
 
-                let command_results = match movies_service.delete_movie(movie_id.clone()).await {
-                    Ok(movies) => serde_json::to_string_pretty(&movies)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
+    /// Skip this many movies first
+    #[arg(long)]
+    offset: Option<i64>,
+}
+
+impl MovieFilterArgs {
+    fn params(&self, q: Option<String>) -> MovieListParams {
+        MovieListParams {
+            q,
+            title: None,
+            genre: self.genre.clone(),
+            year: self.year,
+            director: self.director.clone(),
+            actor: self.actor.clone(),
+            min_rating: self.min_rating,
+            sort: self.sort.into(),
+            order: if self.desc {
+                SortOrder::Desc
+            } else {
+                SortOrder::Asc
+            },
+            limit: self.limit,
+            offset: self.offset,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum MovieSortArg {
+    ReleaseYear,
+    Title,
+    Rating,
+    Runtime,
+}
+
+impl From<MovieSortArg> for MovieSort {
+    fn from(sort: MovieSortArg) -> Self {
+        match sort {
+            MovieSortArg::ReleaseYear => MovieSort::ReleaseYear,
+            MovieSortArg::Title => MovieSort::Title,
+            MovieSortArg::Rating => MovieSort::Rating,
+            MovieSortArg::Runtime => MovieSort::Runtime,
+        }
+    }
+}
 
-                command_results
+fn people(names: &[String]) -> Vec<Person> {
+    names
+        .iter()
+        .map(|name| Person { name: name.clone() })
+        .collect()
+}
+
+impl MoviesCommands {
+    pub async fn execute(
+        &self,
+        movies_service: &MoviesService,
+        output: &Output,
+    ) -> anyhow::Result<String> {
+        match self {
+            MoviesCommands::List { filters } => {
+                let movies = movies_service.list(&filters.params(None)).await?;
+                output.list(&movies.movies)
+            }
+            MoviesCommands::Search { text, filters } => {
+                let movies = movies_service
+                    .list(&filters.params(Some(text.clone())))
+                    .await?;
+                output.list(&movies.movies)
+            }
+            MoviesCommands::Create { body, .. } => {
+                let body = body_or_prompt(body)?;
+                let message = movies_service.create_movie(&body).await?;
+                output.message(&message, &message.message)
+            }
+            MoviesCommands::Read { movie } => {
+                let movie = movies_service
+                    .get_movie(movie.id(movies_service).await?)
+                    .await?;
+                output.item(&movie)
+            }
+            MoviesCommands::Delete { movie } => {
+                let message = movies_service
+                    .delete_movie(movie.id(movies_service).await?)
+                    .await?;
+                output.message(&message, &message.message)
+            }
+            MoviesCommands::Actors { movie } => {
+                let actors = movies_service
+                    .get_actors(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&actors, &people(&actors.actors))
+            }
+            MoviesCommands::Directors { movie } => {
+                let directors = movies_service
+                    .get_directors(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&directors, &people(&directors.directors))
+            }
+            MoviesCommands::Producers { movie } => {
+                let producers = movies_service
+                    .get_producers(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&producers, &people(&producers.producers))
+            }
+            MoviesCommands::Writers { movie } => {
+                let writers = movies_service
+                    .get_writers(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&writers, &people(&writers.writers))
+            }
+            MoviesCommands::Awards { movie } => {
+                let awards = movies_service
+                    .get_awards(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&awards, &awards.awards)
+            }
+            MoviesCommands::Nominations { movie } => {
+                let nominations = movies_service
+                    .get_nominations(movie.id(movies_service).await?)
+                    .await?;
+                output.report(&nominations, &nominations.nominations)
             }
         }
     }
@@ -122,9 +656,19 @@ pub enum InterviewsCommands {
 
     /// Create a new interview
     Create {
-        /// The Movie body
-        #[arg(short, long, value_parser = parse_json::<CreateInterviewRequest>)]
-        body: CreateInterviewRequest,
+        /// The interview as JSON: inline, `@path.json` to read a file or `-` to read stdin
+        #[arg(
+            short,
+            long,
+            value_parser = parse_body::<CreateInterviewRequest>,
+            required_unless_present = "interactive",
+            conflicts_with = "interactive"
+        )]
+        body: Option<CreateInterviewRequest>,
+
+        /// Prompt for each field instead of passing a body
+        #[arg(long)]
+        interactive: bool,
     },
 
     /// Read an interview
@@ -141,59 +685,28 @@ pub enum InterviewsCommands {
 }
 
 impl InterviewsCommands {
-    pub async fn execute(&self, interviews_service: &InterviewsService) -> String {
+    pub async fn execute(
+        &self,
+        interviews_service: &InterviewsService,
+        output: &Output,
+    ) -> anyhow::Result<String> {
         match self {
             InterviewsCommands::List {} => {
-                // Implement listing interviews
-
-                let command_results = match interviews_service.list().await {
-                    Ok(interviews) => serde_json::to_string_pretty(&interviews)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+                let interviews = interviews_service.all().await?;
+                output.list(&interviews)
             }
-            InterviewsCommands::Create { body } => {
-                // Implement creating an interview
-
-                let command_results = match interviews_service.create_interview(body).await {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+            InterviewsCommands::Create { body, .. } => {
+                let body = body_or_prompt(body)?;
+                let message = interviews_service.create_interview(&body).await?;
+                output.message(&message, &message.message)
             }
             InterviewsCommands::Read { interview_id } => {
-                // Implement reading an interview
-
-                let command_results =
-                    match interviews_service.get_interview(interview_id.clone()).await {
-                        Ok(quote) => serde_json::to_string_pretty(&quote)
-                            .map_err(|e| format!("Error: {}", e.to_string()))
-                            .unwrap(),
-                        Err(e) => format!("Error: {}", e.to_string()),
-                    };
-
-                command_results
+                let interview = interviews_service.get_interview(*interview_id).await?;
+                output.item(&interview)
             }
             InterviewsCommands::Delete { interview_id } => {
-                // Implement deleting an interview
-
-                let command_results = match interviews_service
-                    .delete_interview(interview_id.clone())
-                    .await
-                {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+                let message = interviews_service.delete_interview(*interview_id).await?;
+                output.message(&message, &message.message)
             }
         }
     }
@@ -206,9 +719,19 @@ pub enum QuotesCommands {
 
     /// Create a new quote
     Create {
-        /// The Quote body
-        #[arg(short, long, value_parser = parse_json::<CreateQuoteRequest>)]
-        body: CreateQuoteRequest,
+        /// The quote as JSON: inline, `@path.json` to read a file or `-` to read stdin
+        #[arg(
+            short,
+            long,
+            value_parser = parse_body::<CreateQuoteRequest>,
+            required_unless_present = "interactive",
+            conflicts_with = "interactive"
+        )]
+        body: Option<CreateQuoteRequest>,
+
+        /// Prompt for each field instead of passing a body
+        #[arg(long)]
+        interactive: bool,
     },
 
     /// Read a quote
@@ -227,89 +750,261 @@ pub enum QuotesCommands {
 }
 
 impl QuotesCommands {
-    pub async fn execute(&self, quotes_service: &QuotesService) -> String {
+    pub async fn execute(
+        &self,
+        quotes_service: &QuotesService,
+        output: &Output,
+    ) -> anyhow::Result<String> {
         match self {
             QuotesCommands::List {} => {
-                let command_results = match quotes_service.list_quotes().await {
-                    Ok(quotes) => serde_json::to_string_pretty(&quotes)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+                let quotes = quotes_service.all().await?;
+                output.list(&quotes)
             }
-            QuotesCommands::Create { body } => {
-                // Implement creating a quote
-                let command_results = match quotes_service.create_quote(body).await {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+            QuotesCommands::Create { body, .. } => {
+                let body = body_or_prompt(body)?;
+                let message = quotes_service.create_quote(&body).await?;
+                output.message(&message, &message.message)
             }
             QuotesCommands::Read { quote_id } => {
-                // Implement reading a quote
-
-                let command_results = match quotes_service.get_quote(quote_id.clone()).await {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+                let quote = quotes_service.get_quote(*quote_id).await?;
+                output.item(&quote)
             }
             QuotesCommands::Delete { quote_id } => {
-                // Implement deleting a quote
-                let command_results = match quotes_service.delete_quote(quote_id.clone()).await {
-                    Ok(quote) => serde_json::to_string_pretty(&quote)
-                        .map_err(|e| format!("Error: {}", e.to_string()))
-                        .unwrap(),
-                    Err(e) => format!("Error: {}", e.to_string()),
-                };
-
-                command_results
+                let message = quotes_service.delete_quote(*quote_id).await?;
+                output.message(&message, &message.message)
             }
         }
     }
 }
 
-fn parse_json<T: for<'de> Deserialize<'de>>(s: &str) -> Result<T, String> {
-    serde_json::from_str(s).map_err(|e| e.to_string())
-}
-
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> ExitCode {
+    tracing_subscriber::registry()
+        .with(telemetry::otel_layer("tarantinors-cli"))
+        .with(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .init();
+
     let cli = Cli::parse();
-    let url = cli.url;
+    let command_span = info_span!("cli_command", command = cli.command.name());
+
+    let result = start(cli).instrument(command_span).await;
 
-    let command = cli.command;
+    telemetry::shutdown();
 
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(errors::exit_code(&e))
+        }
+    }
+}
+
+/// Resolves the profile and settings, then runs the command.
+async fn start(cli: Cli) -> anyhow::Result<()> {
+    let mut config = Config::load()?;
+    let active = config.active_name(cli.profile.as_deref());
+
+    match cli.command {
+        Commands::Login {} => {
+            let token = read_token()?;
+            let profile = config.profiles.entry(active.clone()).or_default();
+            if cli.url.is_some() {
+                profile.url = cli.url;
+            }
+            profile.token = Some(token);
+            let path = config.save()?;
+            eprintln!("Token saved to profile '{}' in {}", active, path.display());
+            Ok(())
+        }
+        Commands::Config { command } => {
+            let results = command.execute(&mut config, &active)?;
+            println!("{}", results);
+            Ok(())
+        }
+        command => {
+            let profile = config.profile(&active, cli.profile.is_some())?;
+            let flags = Profile {
+                url: cli.url,
+                output: cli.output,
+                timeout_secs: cli.timeout,
+                connect_timeout_secs: cli.connect_timeout,
+                retries: cli.retries,
+                token: None,
+            };
+            let settings = Settings::resolve(&profile, flags);
+            let output = Output::new(settings.output, cli.columns);
+            let client = Client::from_config(&settings.client)?;
+            run(command, &client, &output).await
+        }
+    }
+}
+
+async fn run(command: Commands, client: &Client, output: &Output) -> anyhow::Result<()> {
     match command {
         Commands::Movies { command } => {
             // Execute movie commands
-            let movies_service = MoviesService::new(url.clone())?;
-            let output = command.execute(&movies_service).await;
-            println!("{}", output);
+            let movies_service = client.movies();
+            let results = command.execute(&movies_service, output).await?;
+            println!("{}", results);
         }
         Commands::Interviews { command } => {
             // Execute interview commands
             // let interviews_url = format!("{}/api/v1/interviews", url);
             // command.execute(interviews_url);
 
-            let interviews_service = InterviewsService::new(url.clone())?;
-            let output = command.execute(&interviews_service).await;
-            println!("{}", output);
+            let interviews_service = client.interviews();
+            let results = command.execute(&interviews_service, output).await?;
+            println!("{}", results);
         }
         Commands::Quotes { command } => {
             // Execute quote commands
             // let quotes_url = format!("{}/api/v1/quotes", url);
-            let quotes_service = QuotesService::new(url.clone())?;
-            let output = command.execute(&quotes_service).await;
-            println!("{}", output);
+            let quotes_service = client.quotes();
+            let results = command.execute(&quotes_service, output).await?;
+            println!("{}", results);
+        }
+        Commands::Import {
+            resource,
+            file,
+            format,
+            best_effort,
+        } => {
+            let body = std::fs::read_to_string(&file)
+                .with_context(|| format!("failed to read {}", file.display()))?;
+
+            let format = format
+                .map(ImportFormat::from)
+                .unwrap_or_else(|| import_format_from_path(&file));
+            let mode = if best_effort {
+                ImportMode::BestEffort
+            } else {
+                ImportMode::AllOrNothing
+            };
+
+            let import_service = client.import();
+            let (failed, results) = match resource.resource() {
+                Some(resource) => {
+                    let params = ImportParams {
+                        resource,
+                        format: Some(format),
+                        mode,
+                    };
+                    let report = import_service.import(&params, body).await?;
+                    (report.failed, output.report(&report, &report.records)?)
+                }
+                None => {
+                    let format = match format {
+                        ImportFormat::Json => ArchiveFormat::Json,
+                        ImportFormat::Jsonl => ArchiveFormat::Jsonl,
+                        ImportFormat::Csv => anyhow::bail!("archives are JSON or JSONL, not CSV"),
+                    };
+                    let params = ArchiveImportParams {
+                        format: Some(format),
+                        mode,
+                    };
+                    let report = import_service.import_archive(&params, body).await?;
+                    let failed =
+                        report.movies.failed + report.quotes.failed + report.interviews.failed;
+                    let results = output.sections(
+                        &report,
+                        &[
+                            ("movies", &report.movies.records),
+                            ("quotes", &report.quotes.records),
+                            ("interviews", &report.interviews.records),
+                        ],
+                    )?;
+                    (failed, results)
+                }
+            };
+            println!("{}", results);
+
+            if failed > 0 {
+                return Err(ImportFailed { failed }.into());
+            }
+        }
+        Commands::Browse {} => browse::browse(client).await?,
+        Commands::Batch {
+            file,
+            stop_on_error,
+            parallel,
+        } => {
+            let text = if file.as_os_str() == "-" {
+                let mut text = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut text)
+                    .context("failed to read stdin")?;
+                text
+            } else {
+                std::fs::read_to_string(&file)
+                    .with_context(|| format!("failed to read {}", file.display()))?
+            };
+            let steps = batch::read_steps(&text)?;
+
+            let report = batch::run(client, &steps, parallel.into(), stop_on_error).await;
+            println!("{}", output.report(&report, &report.results)?);
+            eprintln!(
+                "{} succeeded, {} failed, {} skipped",
+                report.succeeded, report.failed, report.skipped
+            );
+
+            if report.failed > 0 {
+                return Err(BatchFailed {
+                    failed: report.failed,
+                }
+                .into());
+            }
+        }
+        Commands::Sync { dir, dry_run } => {
+            let movies = client.movies();
+            let quotes = client.quotes();
+            let interviews = client.interviews();
+            let syncer = sync::Syncer {
+                movies: &movies,
+                quotes: &quotes,
+                interviews: &interviews,
+            };
+
+            let changes = syncer.plan(&dir).await?;
+            if changes.is_empty() {
+                eprintln!("Already in sync");
+                return Ok(());
+            }
+            if !dry_run {
+                syncer.apply(&changes).await?;
+            }
+
+            let plan = sync::SyncPlan { dry_run, changes };
+            println!("{}", output.report(&plan, &plan.changes)?);
+        }
+        Commands::Login { .. } | Commands::Config { .. } => {
+            unreachable!("handled before connecting")
+        }
+        Commands::Export { format, file } => {
+            let format = match (format, &file) {
+                (Some(format), _) => format.into(),
+                (None, Some(path)) => match import_format_from_path(path) {
+                    ImportFormat::Jsonl => ArchiveFormat::Jsonl,
+                    _ => ArchiveFormat::Json,
+                },
+                (None, None) => ArchiveFormat::Json,
+            };
+
+            let export_service = client.export();
+            let archive = export_service.export(&ExportParams { format }).await?;
+
+            match file {
+                Some(path) => {
+                    std::fs::write(&path, archive)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    eprintln!("Archive written to {}", path.display());
+                }
+                None => print!("{}", archive),
+            }
         }
     }
 
@@ -0,0 +1,333 @@
+use crate::output::OutputFormat;
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tarantino_rs::client::ClientConfig;
+
+pub const DEFAULT_URL: &str = "http://localhost:3000";
+
+/// Profile used when neither `--profile` nor `default_profile` names one.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named server to talk to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// Retries of idempotent requests after connection errors, 429 and 503
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Sent as a bearer token with every request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl Profile {
+    /// Replaces the settings that `other` has.
+    pub fn merge(&mut self, other: Profile) {
+        self.url = other.url.or(self.url.take());
+        self.output = other.output.or(self.output);
+        self.timeout_secs = other.timeout_secs.or(self.timeout_secs);
+        self.connect_timeout_secs = other.connect_timeout_secs.or(self.connect_timeout_secs);
+        self.retries = other.retries.or(self.retries);
+        self.token = other.token.or(self.token.take());
+    }
+}
+
+/// The CLI configuration file.
+///
+/// Lives at `$XDG_CONFIG_HOME/tarantinors/config.toml` (or the platform
+/// equivalent), unless `TARANTINORS_CONFIG` points somewhere else.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    pub fn path() -> anyhow::Result<PathBuf> {
+        if let Some(path) = std::env::var_os("TARANTINORS_CONFIG") {
+            return Ok(PathBuf::from(path));
+        }
+
+        let dir = dirs::config_dir().context("could not find the user config directory")?;
+        Ok(dir.join("tarantinors").join("config.toml"))
+    }
+
+    /// Reads the config file; a missing file is an empty config.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = Self::path()?;
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                toml::from_str(&text).with_context(|| format!("invalid config {}", path.display()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    /// Writes the config file, readable by the current user only since it
+    /// may hold tokens.
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        let text = toml::to_string_pretty(self)?;
+        write_private(&path, &text)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Name of the profile to use: `--profile`, then `default_profile`.
+    pub fn active_name(&self, requested: Option<&str>) -> String {
+        requested
+            .map(str::to_string)
+            .or_else(|| self.default_profile.clone())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// The named profile; asking for a missing one by name is an error, but
+    /// the implicit default profile may be absent.
+    pub fn profile(&self, name: &str, explicit: bool) -> anyhow::Result<Profile> {
+        match self.profiles.get(name) {
+            Some(profile) => Ok(profile.clone()),
+            None if explicit => bail!("no profile named '{}' in {}", name, Self::path()?.display()),
+            None => Ok(Profile::default()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(text.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    std::fs::write(path, text)
+}
+
+/// Settings after merging flags, environment and profile.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub output: OutputFormat,
+    pub client: ClientConfig,
+}
+
+impl Settings {
+    /// Flags win over the environment, which wins over the profile; the token
+    /// only comes from `TARANTINORS_TOKEN` or the profile, never a flag.
+    pub fn resolve(profile: &Profile, flags: Profile) -> Self {
+        let mut settings = profile.clone();
+        if let Some(token) = std::env::var("TARANTINORS_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty())
+        {
+            settings.token = Some(token);
+        }
+        settings.merge(Profile {
+            token: None,
+            ..flags
+        });
+
+        let mut client = ClientConfig::new(settings.url.unwrap_or_else(|| DEFAULT_URL.to_string()));
+        client.user_agent = "TarantinorsCli/1.0".to_string();
+        client.token = settings.token;
+        if let Some(secs) = settings.timeout_secs {
+            client.timeout = Duration::from_secs(secs);
+        }
+        if let Some(secs) = settings.connect_timeout_secs {
+            client.connect_timeout = Duration::from_secs(secs);
+        }
+        if let Some(retries) = settings.retries {
+            client.retry.max_retries = retries;
+        }
+
+        Self {
+            output: settings.output.unwrap_or_default(),
+            client,
+        }
+    }
+}
+
+/// Hides all but the last four characters of a token.
+pub fn mask_token(token: &str) -> String {
+    let count = token.chars().count();
+    if count <= 4 {
+        return "****".to_string();
+    }
+    let tail: String = token.chars().skip(count - 4).collect();
+    format!("****{}", tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    const CONFIG: &str = r#"
+default_profile = "staging"
+
+[profiles.staging]
+url = "https://staging.example"
+output = "json"
+token = "from-profile"
+
+[profiles.local]
+retries = 5
+"#;
+
+    /// Runs `f` with `name` set to `value`, or unset for `None`.
+    fn with_env<R>(name: &str, value: Option<&str>, f: impl FnOnce() -> R) -> R {
+        let previous = std::env::var_os(name);
+        // SAFETY: every test that touches the environment is `#[serial]`
+        unsafe {
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+        let result = f();
+        unsafe {
+            match previous {
+                Some(previous) => std::env::set_var(name, previous),
+                None => std::env::remove_var(name),
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn profiles_are_picked_by_flag_then_default_profile() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+
+        assert_eq!(config.active_name(Some("local")), "local");
+        assert_eq!(config.active_name(None), "staging");
+        assert_eq!(Config::default().active_name(None), DEFAULT_PROFILE);
+
+        let staging = config.profile("staging", false).unwrap();
+        assert_eq!(staging.url.as_deref(), Some("https://staging.example"));
+        assert_eq!(staging.output, Some(OutputFormat::Json));
+        assert_eq!(config.profile("local", true).unwrap().retries, Some(5));
+
+        // Only a profile asked for by name has to exist
+        assert!(config.profile("prod", true).is_err());
+        assert!(config.profile("prod", false).unwrap().url.is_none());
+    }
+
+    #[test]
+    fn merge_keeps_settings_the_other_profile_lacks() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let mut profile = config.profiles["staging"].clone();
+        profile.merge(config.profiles["local"].clone());
+
+        assert_eq!(profile.url.as_deref(), Some("https://staging.example"));
+        assert_eq!(profile.retries, Some(5));
+        assert_eq!(profile.token.as_deref(), Some("from-profile"));
+    }
+
+    #[test]
+    #[serial]
+    fn token_comes_from_the_environment_before_the_profile() {
+        let config: Config = toml::from_str(CONFIG).unwrap();
+        let profile = &config.profiles["staging"];
+        let flags = || Profile {
+            url: Some("http://localhost:4000".to_string()),
+            token: Some("from-flag".to_string()),
+            ..Profile::default()
+        };
+
+        let settings = with_env("TARANTINORS_TOKEN", None, || {
+            Settings::resolve(profile, flags())
+        });
+        assert_eq!(settings.client.token.as_deref(), Some("from-profile"));
+        assert_eq!(settings.client.url, "http://localhost:4000");
+        assert_eq!(settings.output, OutputFormat::Json);
+
+        let settings = with_env("TARANTINORS_TOKEN", Some("from-env"), || {
+            Settings::resolve(profile, flags())
+        });
+        assert_eq!(settings.client.token.as_deref(), Some("from-env"));
+
+        // An empty variable does not hide the profile's token
+        let settings = with_env("TARANTINORS_TOKEN", Some(""), || {
+            Settings::resolve(profile, flags())
+        });
+        assert_eq!(settings.client.token.as_deref(), Some("from-profile"));
+
+        let settings = with_env("TARANTINORS_TOKEN", None, || {
+            Settings::resolve(&Profile::default(), Profile::default())
+        });
+        assert_eq!(settings.client.token, None);
+        assert_eq!(settings.client.url, DEFAULT_URL);
+    }
+
+    #[test]
+    fn mask_token_shows_only_the_last_four_characters() {
+        assert_eq!(mask_token(""), "****");
+        assert_eq!(mask_token("abcd"), "****");
+        assert_eq!(mask_token("abcde"), "****bcde");
+        assert_eq!(mask_token("tok-1234567890"), "****7890");
+        assert_eq!(mask_token("clé-ünïcødé"), "****cødé");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn save_writes_a_file_only_the_user_can_read() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "tarantinors-config-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let path = dir.join("nested").join("config.toml");
+        let config: Config = toml::from_str(CONFIG).unwrap();
+
+        let saved = with_env("TARANTINORS_CONFIG", path.to_str(), || {
+            let saved = config.save().unwrap();
+            let mode = std::fs::metadata(&saved).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            // A file made readable by others is tightened on the next save
+            std::fs::set_permissions(&saved, std::fs::Permissions::from_mode(0o644)).unwrap();
+            config.save().unwrap();
+            let mode = std::fs::metadata(&saved).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            let loaded = Config::load().unwrap();
+            assert_eq!(loaded.default_profile.as_deref(), Some("staging"));
+            assert_eq!(
+                loaded.profiles["staging"].token.as_deref(),
+                Some("from-profile")
+            );
+            saved
+        });
+
+        assert_eq!(saved, path);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
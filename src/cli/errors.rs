@@ -0,0 +1,171 @@
+//! Exit codes of `tarantinors-cli`, so scripts can tell failures apart.
+
+use tarantino_rs::client::ClientError;
+use thiserror::Error;
+
+/// Anything not covered below, such as unreadable files
+pub const GENERAL_ERROR: u8 = 1;
+/// Invalid command line usage; clap exits with it too
+pub const USAGE: u8 = 2;
+/// The resource does not exist (HTTP 404)
+pub const NOT_FOUND: u8 = 3;
+/// The request was rejected as invalid (HTTP 400/409/422, or records failed to import)
+pub const VALIDATION: u8 = 4;
+/// Missing or refused credentials (HTTP 401/403)
+pub const AUTH: u8 = 5;
+/// The server could not be reached or did not answer in time
+pub const NETWORK: u8 = 6;
+/// The server failed or answered with something unexpected (HTTP 5xx, 429)
+pub const SERVER: u8 = 7;
+
+/// Shown at the end of `--help`.
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  success
+  1  general error
+  2  invalid command line usage
+  3  not found
+  4  validation failed
+  5  authentication or authorization failed
+  6  network error
+  7  server error";
+
+/// Some records of an import were rejected; the report says which.
+#[derive(Debug, Error)]
+#[error("{failed} record(s) failed to import")]
+pub struct ImportFailed {
+    pub failed: usize,
+}
+
+/// A command line value clap cannot check, such as an unknown `--columns`
+/// name.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct UsageError(pub String);
+
+/// Some lines of a batch file failed; the report says which.
+#[derive(Debug, Error)]
+#[error("{failed} line(s) of the batch failed")]
+pub struct BatchFailed {
+    pub failed: usize,
+}
+
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    if error.downcast_ref::<ImportFailed>().is_some() {
+        return VALIDATION;
+    }
+    if error.downcast_ref::<UsageError>().is_some() {
+        return USAGE;
+    }
+
+    match error.downcast_ref::<ClientError>() {
+        Some(ClientError::NotFound(_)) => NOT_FOUND,
+        Some(ClientError::Validation(_)) | Some(ClientError::Conflict(_)) => VALIDATION,
+        Some(ClientError::Unauthorized(_)) | Some(ClientError::InvalidToken) => AUTH,
+        Some(ClientError::RequestFailed(e)) if e.is_connect() || e.is_timeout() => NETWORK,
+        Some(ClientError::RequestFailed(_))
+        | Some(ClientError::SerializationError(_))
+        | Some(ClientError::RateLimited(_))
+        | Some(ClientError::HttpError(_)) => SERVER,
+        None => GENERAL_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use clap::Parser;
+    use reqwest::StatusCode;
+    use tarantino_rs::client::ApiError;
+
+    fn api_error(status: StatusCode) -> Box<ApiError> {
+        Box::new(ApiError {
+            status,
+            message: status.to_string(),
+            ..ApiError::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn each_error_kind_has_its_exit_code() {
+        // Nothing listens on port 1, and the second URL cannot be built
+        let refused = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        let unbuildable = reqwest::get("not a url").await.unwrap_err();
+        let unparsable = serde_json::from_str::<i32>("x").unwrap_err();
+
+        let cases: Vec<(&str, anyhow::Error, u8)> = vec![
+            ("plain error", anyhow::anyhow!("cannot read file"), 1),
+            ("failed batch", BatchFailed { failed: 2 }.into(), 1),
+            (
+                "unknown column",
+                UsageError("unknown column 'budget'".to_string()).into(),
+                2,
+            ),
+            (
+                "not found",
+                ClientError::NotFound(api_error(StatusCode::NOT_FOUND)).into(),
+                3,
+            ),
+            (
+                "not found with context",
+                Err::<(), _>(ClientError::NotFound(api_error(StatusCode::NOT_FOUND)))
+                    .context("loading movie 7")
+                    .unwrap_err(),
+                3,
+            ),
+            (
+                "validation",
+                ClientError::Validation(api_error(StatusCode::UNPROCESSABLE_ENTITY)).into(),
+                4,
+            ),
+            (
+                "conflict",
+                ClientError::Conflict(api_error(StatusCode::CONFLICT)).into(),
+                4,
+            ),
+            ("failed import", ImportFailed { failed: 1 }.into(), 4),
+            (
+                "unauthorized",
+                ClientError::Unauthorized(api_error(StatusCode::UNAUTHORIZED)).into(),
+                5,
+            ),
+            ("invalid token", ClientError::InvalidToken.into(), 5),
+            (
+                "connection refused",
+                ClientError::RequestFailed(refused).into(),
+                6,
+            ),
+            (
+                "request not sent",
+                ClientError::RequestFailed(unbuildable).into(),
+                7,
+            ),
+            (
+                "unreadable response",
+                ClientError::SerializationError(unparsable).into(),
+                7,
+            ),
+            (
+                "rate limited",
+                ClientError::RateLimited(api_error(StatusCode::TOO_MANY_REQUESTS)).into(),
+                7,
+            ),
+            (
+                "server error",
+                ClientError::HttpError(api_error(StatusCode::INTERNAL_SERVER_ERROR)).into(),
+                7,
+            ),
+        ];
+
+        for (kind, error, code) in cases {
+            assert_eq!(exit_code(&error), code, "{kind}");
+        }
+
+        // Usage errors never reach `exit_code`: clap exits with 2 itself
+        let Err(usage) = crate::Cli::try_parse_from(["tarantinors-cli", "--no-such-flag"]) else {
+            panic!("an unknown flag was accepted");
+        };
+        assert_eq!(usage.exit_code(), 2);
+    }
+}
@@ -0,0 +1,336 @@
+use anyhow::{Context, anyhow, bail};
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use std::io::{BufRead, Read, Write};
+use std::str::FromStr;
+use tarantino_rs::interviews::api::models::CreateInterviewRequest;
+use tarantino_rs::movies::api::models::{
+    CreateMovieAwardNominationRequest, CreateMovieAwardRequest, CreateMovieRequest,
+};
+use tarantino_rs::quotes::api::models::CreateQuoteRequest;
+use validator::{Validate, ValidationErrors};
+
+/// Reads a `--body` argument: inline JSON, `@path` for a file or `-` for stdin.
+///
+/// The body is validated with the same rules the API applies, so mistakes are
+/// reported before anything is sent.
+pub fn parse_body<T: DeserializeOwned + Validate>(arg: &str) -> Result<T, String> {
+    let (source, json) = if arg == "-" {
+        let mut json = String::new();
+        std::io::stdin()
+            .read_to_string(&mut json)
+            .map_err(|e| format!("failed to read stdin: {}", e))?;
+        ("stdin".to_string(), json)
+    } else if let Some(path) = arg.strip_prefix('@') {
+        let json =
+            std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+        (path.to_string(), json)
+    } else {
+        ("--body".to_string(), arg.to_string())
+    };
+
+    let body: T = serde_json::from_str(&json).map_err(|e| json_error(&source, &json, &e))?;
+    body.validate()
+        .map_err(|errors| format!("invalid body in {}:\n{}", source, field_errors(&errors)))?;
+    Ok(body)
+}
+
+/// Points at the line and column serde stopped at.
+pub fn json_error(source: &str, json: &str, error: &serde_json::Error) -> String {
+    let location = format!(" at line {} column {}", error.line(), error.column());
+    let reason = error.to_string();
+    let mut message = format!(
+        "invalid JSON in {} at line {}, column {}: {}",
+        source,
+        error.line(),
+        error.column(),
+        reason.strip_suffix(&location).unwrap_or(&reason)
+    );
+
+    if let Some(line) = json.lines().nth(error.line().saturating_sub(1)) {
+        let gutter = error.line().to_string();
+        message.push_str(&format!(
+            "\n {} | {}\n {} | {}^",
+            gutter,
+            line,
+            " ".repeat(gutter.len()),
+            " ".repeat(error.column().saturating_sub(1))
+        ));
+    }
+    message
+}
+
+pub fn field_errors(errors: &ValidationErrors) -> String {
+    let mut lines = errors
+        .field_errors()
+        .iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |error| {
+                let message = error
+                    .message
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| error.code.to_string());
+                format!("  {}: {}", field, message)
+            })
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines.join("\n")
+}
+
+/// A create request that can be filled in field by field.
+pub trait Interactive: Validate + Sized {
+    /// Fields in the order they are asked for
+    const FIELDS: &'static [&'static str];
+
+    fn empty() -> Self;
+
+    fn ask(&mut self, field: &str, prompter: &mut Prompter) -> anyhow::Result<()>;
+}
+
+/// Asks for every field, then asks again for the ones that fail validation.
+pub fn prompt<T: Interactive>() -> anyhow::Result<T> {
+    let mut prompter = Prompter::stdin();
+    let mut request = T::empty();
+
+    for field in T::FIELDS {
+        request.ask(field, &mut prompter)?;
+    }
+
+    while let Err(errors) = request.validate() {
+        prompter.say(&format!("Please fix:\n{}", field_errors(&errors)))?;
+        let mut invalid = errors
+            .field_errors()
+            .keys()
+            .map(|field| field.to_string())
+            .collect::<Vec<_>>();
+        invalid.sort_by_key(|field| T::FIELDS.iter().position(|f| f == field));
+        for field in invalid {
+            request.ask(&field, &mut prompter)?;
+        }
+    }
+
+    Ok(request)
+}
+
+/// Reads answers from stdin and writes questions to stderr, keeping stdout
+/// for the command output.
+pub struct Prompter {
+    input: std::io::StdinLock<'static>,
+}
+
+impl Prompter {
+    fn stdin() -> Self {
+        Self {
+            input: std::io::stdin().lock(),
+        }
+    }
+
+    fn say(&mut self, message: &str) -> anyhow::Result<()> {
+        writeln!(std::io::stderr(), "{}", message)?;
+        Ok(())
+    }
+
+    pub fn text(&mut self, label: &str) -> anyhow::Result<String> {
+        let mut stderr = std::io::stderr();
+        write!(stderr, "{}: ", label)?;
+        stderr.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            bail!("input ended before {} was entered", label);
+        }
+        Ok(line.trim().to_string())
+    }
+
+    /// Asks until the answer parses as `T`.
+    pub fn parsed<T: FromStr>(&mut self, label: &str) -> anyhow::Result<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        loop {
+            match self.text(label)?.parse() {
+                Ok(value) => return Ok(value),
+                Err(e) => self.say(&format!("  {}", e))?,
+            }
+        }
+    }
+
+    pub fn date(&mut self, label: &str) -> anyhow::Result<NaiveDate> {
+        loop {
+            let answer = self.text(&format!("{} (YYYY-MM-DD)", label))?;
+            match NaiveDate::parse_from_str(&answer, "%Y-%m-%d") {
+                Ok(date) => return Ok(date),
+                Err(e) => self.say(&format!("  {}", e))?,
+            }
+        }
+    }
+
+    /// A comma separated list of names.
+    pub fn list(&mut self, label: &str) -> anyhow::Result<Vec<String>> {
+        let answer = self.text(&format!("{} (comma separated)", label))?;
+        Ok(answer
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Repeats `entry` until an empty first answer.
+    pub fn entries<T>(
+        &mut self,
+        label: &str,
+        mut entry: impl FnMut(&mut Self, String) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        let mut entries = Vec::new();
+        loop {
+            let first = self.text(&format!(
+                "{} #{} name (empty to finish)",
+                label,
+                entries.len() + 1
+            ))?;
+            if first.is_empty() {
+                return Ok(entries);
+            }
+            entries.push(entry(self, first)?);
+        }
+    }
+}
+
+impl Interactive for CreateMovieRequest {
+    const FIELDS: &'static [&'static str] = &[
+        "title",
+        "release_year",
+        "genre",
+        "plot",
+        "runtime",
+        "rating",
+        "release_date",
+        "image_url",
+        "youtube_id",
+        "production_details",
+        "budget",
+        "directors",
+        "producers",
+        "actors",
+        "writers",
+        "awards",
+        "nominations",
+    ];
+
+    fn empty() -> Self {
+        Self {
+            title: String::new(),
+            release_year: 0,
+            genre: String::new(),
+            plot: String::new(),
+            runtime: 0,
+            rating: 0.0,
+            release_date: NaiveDate::default(),
+            image_url: String::new(),
+            youtube_id: String::new(),
+            production_details: String::new(),
+            budget: String::new(),
+            directors: Vec::new(),
+            producers: Vec::new(),
+            actors: Vec::new(),
+            writers: Vec::new(),
+            awards: Vec::new(),
+            nominations: Vec::new(),
+        }
+    }
+
+    fn ask(&mut self, field: &str, prompter: &mut Prompter) -> anyhow::Result<()> {
+        match field {
+            "title" => self.title = prompter.text("Title")?,
+            "release_year" => self.release_year = prompter.parsed("Release year")?,
+            "genre" => self.genre = prompter.text("Genre")?,
+            "plot" => self.plot = prompter.text("Plot")?,
+            "runtime" => self.runtime = prompter.parsed("Runtime (minutes)")?,
+            "rating" => self.rating = prompter.parsed("Rating (0-10)")?,
+            "release_date" => self.release_date = prompter.date("Release date")?,
+            "image_url" => self.image_url = prompter.text("Image URL")?,
+            "youtube_id" => self.youtube_id = prompter.text("YouTube trailer id")?,
+            "production_details" => {
+                self.production_details = prompter.text("Production details")?
+            }
+            "budget" => self.budget = prompter.text("Budget")?,
+            "directors" => self.directors = prompter.list("Directors")?,
+            "producers" => self.producers = prompter.list("Producers")?,
+            "actors" => self.actors = prompter.list("Actors")?,
+            "writers" => self.writers = prompter.list("Writers")?,
+            "awards" => {
+                self.awards = prompter.entries("Award", |prompter, name| {
+                    Ok(CreateMovieAwardRequest {
+                        name,
+                        category: prompter.text("  Category")?,
+                        year: prompter.parsed("  Year")?,
+                        recipient: prompter.text("  Recipient")?,
+                    })
+                })?
+            }
+            "nominations" => {
+                self.nominations = prompter.entries("Nomination", |prompter, name| {
+                    Ok(CreateMovieAwardNominationRequest {
+                        name,
+                        category: prompter.text("  Category")?,
+                        year: prompter.parsed("  Year")?,
+                        nominee: prompter.text("  Nominee")?,
+                    })
+                })?
+            }
+            _ => return Err(anyhow!("unknown movie field {}", field)),
+        }
+        Ok(())
+    }
+}
+
+impl Interactive for CreateQuoteRequest {
+    const FIELDS: &'static [&'static str] = &["text"];
+
+    fn empty() -> Self {
+        Self {
+            text: String::new(),
+        }
+    }
+
+    fn ask(&mut self, field: &str, prompter: &mut Prompter) -> anyhow::Result<()> {
+        match field {
+            "text" => self.text = prompter.text("Quote")?,
+            _ => return Err(anyhow!("unknown quote field {}", field)),
+        }
+        Ok(())
+    }
+}
+
+impl Interactive for CreateInterviewRequest {
+    const FIELDS: &'static [&'static str] = &["title", "description", "youtube_id"];
+
+    fn empty() -> Self {
+        Self {
+            title: String::new(),
+            description: String::new(),
+            youtube_id: String::new(),
+        }
+    }
+
+    fn ask(&mut self, field: &str, prompter: &mut Prompter) -> anyhow::Result<()> {
+        match field {
+            "title" => self.title = prompter.text("Title")?,
+            "description" => self.description = prompter.text("Description")?,
+            "youtube_id" => self.youtube_id = prompter.text("YouTube video id")?,
+            _ => return Err(anyhow!("unknown interview field {}", field)),
+        }
+        Ok(())
+    }
+}
+
+/// Takes the `--body` value, or prompts for one when `--interactive` was given.
+pub fn body_or_prompt<T: Interactive + Clone>(body: &Option<T>) -> anyhow::Result<T> {
+    match body {
+        Some(body) => Ok(body.clone()),
+        None => prompt().context("interactive input failed"),
+    }
+}
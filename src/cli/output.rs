@@ -0,0 +1,559 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::IsTerminal;
+use tarantino_rs::import::api::models::ImportRecordReport;
+use tarantino_rs::interviews::api::models::InterviewResponse;
+use tarantino_rs::movies::api::models::{
+    MovieAwardNominationResponse, MovieAwardResponse, MovieResponse,
+};
+use tarantino_rs::quotes::api::models::QuoteResponse;
+
+use crate::errors::UsageError;
+
+/// Widest a table cell gets on a terminal before it is cut with `…`.
+const MAX_CELL_WIDTH: usize = 48;
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Aligned columns
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+    /// One id per line
+    Ids,
+}
+
+/// A response that can be shown as a table row.
+pub trait Columns: Serialize {
+    /// Every field `--columns` can pick
+    const COLUMNS: &'static [&'static str];
+    /// Columns shown when `--columns` is not given
+    const DEFAULT_COLUMNS: &'static [&'static str];
+}
+
+impl Columns for MovieResponse {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "title",
+        "release_year",
+        "genre",
+        "plot",
+        "runtime",
+        "rating",
+        "release_date",
+        "image_url",
+        "youtube_id",
+        "production_details",
+        "budget",
+        "directors",
+        "producers",
+        "actors",
+        "writers",
+        "awards",
+        "nominations",
+    ];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "id",
+        "title",
+        "release_year",
+        "genre",
+        "rating",
+        "directors",
+    ];
+}
+
+impl Columns for MovieAwardResponse {
+    const COLUMNS: &'static [&'static str] = &["name", "category", "year", "recipient"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["year", "name", "category", "recipient"];
+}
+
+impl Columns for MovieAwardNominationResponse {
+    const COLUMNS: &'static [&'static str] = &["name", "category", "year", "nominee"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["year", "name", "category", "nominee"];
+}
+
+/// A cast or crew member, as a table row.
+#[derive(Serialize)]
+pub struct Person {
+    pub name: String,
+}
+
+impl Columns for Person {
+    const COLUMNS: &'static [&'static str] = &["name"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["name"];
+}
+
+impl Columns for QuoteResponse {
+    const COLUMNS: &'static [&'static str] = &["id", "text"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "text"];
+}
+
+impl Columns for InterviewResponse {
+    const COLUMNS: &'static [&'static str] = &["id", "title", "description", "youtube_id"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "title", "youtube_id"];
+}
+
+impl Columns for ImportRecordReport {
+    const COLUMNS: &'static [&'static str] =
+        &["record", "key", "status", "id", "reason", "field_errors"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["record", "key", "status", "id", "reason"];
+}
+
+/// Renders command results in the format picked with `--output`.
+///
+/// When stdout is not a terminal, tables lose their padding and truncation and
+/// JSON is printed on one line, so the output can be piped into other tools.
+pub struct Output {
+    format: OutputFormat,
+    columns: Option<Vec<String>>,
+    terminal: bool,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat, columns: Option<Vec<String>>) -> Self {
+        Self {
+            format,
+            columns,
+            terminal: std::io::stdout().is_terminal(),
+        }
+    }
+
+    pub fn list<T: Columns>(&self, items: &[T]) -> anyhow::Result<String> {
+        match self.format {
+            OutputFormat::Json => self.json(&items),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(&items)?),
+            OutputFormat::Ids => Ok(ids(&to_rows(items)?)),
+            OutputFormat::Csv => csv(&self.columns::<T>()?, &to_rows(items)?),
+            OutputFormat::Table => Ok(self.table(&self.columns::<T>()?, &to_rows(items)?)),
+        }
+    }
+
+    /// A single record; tables show it as one `field value` line per column.
+    pub fn item<T: Columns>(&self, item: &T) -> anyhow::Result<String> {
+        let row = to_row(item)?;
+        match self.format {
+            OutputFormat::Json => self.json(item),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(item)?),
+            OutputFormat::Ids => Ok(ids(&[row])),
+            OutputFormat::Csv => csv(&self.columns::<T>()?, &[row]),
+            OutputFormat::Table => {
+                let fields = match &self.columns {
+                    Some(_) => self.columns::<T>()?,
+                    None => row
+                        .as_object()
+                        .map(|object| object.keys().cloned().collect())
+                        .unwrap_or_default(),
+                };
+                let rows = fields
+                    .iter()
+                    .map(|field| vec![field.clone(), cell(&row[field.as_str()])])
+                    .collect::<Vec<_>>();
+                Ok(self.align(&rows, false))
+            }
+        }
+    }
+
+    /// A response that only carries a human readable message.
+    pub fn message<T: Serialize>(&self, response: &T, message: &str) -> anyhow::Result<String> {
+        match self.format {
+            OutputFormat::Json => self.json(response),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(response)?),
+            OutputFormat::Ids => Ok(String::new()),
+            OutputFormat::Csv | OutputFormat::Table => Ok(message.to_string()),
+        }
+    }
+
+    /// Whole documents such as import reports; tables and CSV show `rows`.
+    pub fn report<T: Serialize, R: Columns>(
+        &self,
+        report: &T,
+        rows: &[R],
+    ) -> anyhow::Result<String> {
+        match self.format {
+            OutputFormat::Json => self.json(report),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(report)?),
+            _ => self.list(rows),
+        }
+    }
+
+    /// Like `report`, for documents with one list of rows per resource;
+    /// tables and CSV merge them under a leading `resource` column.
+    pub fn sections<T: Serialize, R: Columns>(
+        &self,
+        report: &T,
+        sections: &[(&str, &[R])],
+    ) -> anyhow::Result<String> {
+        let mut rows = Vec::new();
+        for (resource, items) in sections {
+            for row in to_rows(items)? {
+                let mut tagged = serde_json::Map::new();
+                tagged.insert("resource".to_string(), Value::from(*resource));
+                if let Value::Object(fields) = row {
+                    tagged.extend(fields);
+                }
+                rows.push(Value::Object(tagged));
+            }
+        }
+
+        let columns = match &self.columns {
+            Some(columns) => {
+                check_columns(columns, &[&["resource"], R::COLUMNS].concat())?;
+                columns.clone()
+            }
+            None => std::iter::once("resource".to_string())
+                .chain(self.columns::<R>()?)
+                .collect(),
+        };
+
+        match self.format {
+            OutputFormat::Json => self.json(report),
+            OutputFormat::Yaml => Ok(serde_yaml::to_string(report)?),
+            OutputFormat::Ids => Ok(ids(&rows)),
+            OutputFormat::Csv => csv(&columns, &rows),
+            OutputFormat::Table => Ok(self.table(&columns, &rows)),
+        }
+    }
+
+    fn json<T: Serialize + ?Sized>(&self, value: &T) -> anyhow::Result<String> {
+        let json = if self.terminal {
+            serde_json::to_string_pretty(value)?
+        } else {
+            serde_json::to_string(value)?
+        };
+        Ok(json)
+    }
+
+    fn columns<T: Columns>(&self) -> Result<Vec<String>, UsageError> {
+        match &self.columns {
+            Some(columns) => {
+                check_columns(columns, T::COLUMNS)?;
+                Ok(columns.clone())
+            }
+            None => Ok(T::DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect()),
+        }
+    }
+
+    fn table(&self, columns: &[String], rows: &[Value]) -> String {
+        let header = if self.terminal {
+            columns.iter().map(|c| c.to_uppercase()).collect()
+        } else {
+            columns.to_vec()
+        };
+
+        let mut lines = vec![header];
+        lines.extend(rows.iter().map(|row| {
+            columns
+                .iter()
+                .map(|column| cell(&row[column.as_str()]))
+                .collect()
+        }));
+        self.align(&lines, true)
+    }
+
+    /// Pads cells into columns on a terminal, joins them with tabs otherwise.
+    fn align(&self, lines: &[Vec<String>], truncate_cells: bool) -> String {
+        if !self.terminal {
+            return lines
+                .iter()
+                .map(|line| {
+                    line.iter()
+                        .map(|c| one_line(c))
+                        .collect::<Vec<_>>()
+                        .join("\t")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
+        let lines = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|c| {
+                        if truncate_cells {
+                            truncate(c)
+                        } else {
+                            c.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let count = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let widths = (0..count)
+            .map(|i| {
+                lines
+                    .iter()
+                    .filter_map(|line| line.get(i))
+                    .map(|c| c.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+
+        lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .zip(&widths)
+                    .map(|(c, width)| format!("{:<width$}", c, width = width))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Refuses `--columns` names the rows do not have.
+fn check_columns(columns: &[String], known: &[&str]) -> Result<(), UsageError> {
+    match columns.iter().find(|c| !known.contains(&c.as_str())) {
+        Some(unknown) => Err(UsageError(format!(
+            "unknown column '{}' for --columns, expected one of: {}",
+            unknown,
+            known.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn to_rows<T: Serialize>(items: &[T]) -> anyhow::Result<Vec<Value>> {
+    items.iter().map(to_row).collect()
+}
+
+/// Goes through JSON text rather than `to_value` so `f32` fields such as
+/// ratings keep their short form instead of widening to `f64` digits.
+fn to_row<T: Serialize>(item: &T) -> anyhow::Result<Value> {
+    Ok(serde_json::from_str(&serde_json::to_string(item)?)?)
+}
+
+fn ids(rows: &[Value]) -> String {
+    rows.iter()
+        .filter_map(|row| row.get("id"))
+        .filter(|id| !id.is_null())
+        .map(cell)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// CSV with a header row.
+fn csv(columns: &[String], rows: &[Value]) -> anyhow::Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(columns)?;
+    for row in rows {
+        writer.write_record(columns.iter().map(|column| csv_cell(&row[column.as_str()])))?;
+    }
+    let csv = String::from_utf8(writer.into_inner()?)?;
+    Ok(csv.trim_end().to_string())
+}
+
+/// Flattens a JSON value into one table cell, lists comma separated.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(values) => values.iter().map(cell).collect::<Vec<_>>().join(", "),
+        Value::Object(fields) => fields.values().map(cell).collect::<Vec<_>>().join(" "),
+        _ => value.to_string(),
+    }
+}
+
+/// Flattens a JSON value into one CSV cell, the way the import reads it back:
+/// lists of names `;` separated, lists of objects as JSON.
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Array(values) if values.iter().any(Value::is_object) => value.to_string(),
+        Value::Array(values) => values.iter().map(cell).collect::<Vec<_>>().join(";"),
+        _ => cell(value),
+    }
+}
+
+/// Turns the tabs and line breaks of a cell into spaces, so piped tables keep
+/// one row per line and one column per tab.
+fn one_line(cell: &str) -> String {
+    cell.replace("\r\n", " ").replace(['\t', '\n', '\r'], " ")
+}
+
+fn truncate(cell: &str) -> String {
+    let cell = cell.replace('\n', " ");
+    if cell.chars().count() <= MAX_CELL_WIDTH {
+        return cell;
+    }
+    let cut: String = cell.chars().take(MAX_CELL_WIDTH - 1).collect();
+    format!("{}…", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Film {
+        id: i32,
+        title: String,
+        directors: Vec<String>,
+        rating: f32,
+    }
+
+    impl Columns for Film {
+        const COLUMNS: &'static [&'static str] = &["id", "title", "directors", "rating"];
+        const DEFAULT_COLUMNS: &'static [&'static str] = &["id", "title"];
+    }
+
+    fn films() -> Vec<Film> {
+        vec![
+            Film {
+                id: 1,
+                title: "Jackie Brown".to_string(),
+                directors: vec!["Quentin Tarantino".to_string()],
+                rating: 7.5,
+            },
+            Film {
+                id: 12,
+                title: "From Dusk till Dawn, the film".to_string(),
+                directors: vec!["Robert Rodriguez".to_string(), "QT".to_string()],
+                rating: 7.2,
+            },
+        ]
+    }
+
+    fn output(format: OutputFormat, columns: Option<&[&str]>, terminal: bool) -> Output {
+        Output {
+            format,
+            columns: columns.map(|columns| columns.iter().map(|c| c.to_string()).collect()),
+            terminal,
+        }
+    }
+
+    #[test]
+    fn table_pads_columns_on_a_terminal_and_uses_tabs_otherwise() {
+        let table = output(OutputFormat::Table, None, true)
+            .list(&films())
+            .unwrap();
+        assert_eq!(
+            table,
+            "ID  TITLE\n1   Jackie Brown\n12  From Dusk till Dawn, the film"
+        );
+
+        let piped = output(OutputFormat::Table, None, false)
+            .list(&films())
+            .unwrap();
+        assert_eq!(
+            piped,
+            "id\ttitle\n1\tJackie Brown\n12\tFrom Dusk till Dawn, the film"
+        );
+    }
+
+    #[test]
+    fn table_shows_a_single_item_as_field_value_lines() {
+        let item = output(OutputFormat::Table, Some(&["title", "directors"]), false)
+            .item(&films()[1])
+            .unwrap();
+        assert_eq!(
+            item,
+            "title\tFrom Dusk till Dawn, the film\ndirectors\tRobert Rodriguez, QT"
+        );
+    }
+
+    #[test]
+    fn json_is_pretty_only_on_a_terminal() {
+        let films = &films()[..1];
+        let piped = output(OutputFormat::Json, None, false).list(films).unwrap();
+        assert_eq!(
+            piped,
+            r#"[{"id":1,"title":"Jackie Brown","directors":["Quentin Tarantino"],"rating":7.5}]"#
+        );
+
+        let pretty = output(OutputFormat::Json, None, true).list(films).unwrap();
+        assert!(pretty.starts_with("[\n  {\n    \"id\": 1,"));
+    }
+
+    #[test]
+    fn yaml_keeps_every_field() {
+        let yaml = output(OutputFormat::Yaml, Some(&["id"]), false)
+            .item(&films()[0])
+            .unwrap();
+        assert_eq!(
+            yaml,
+            "id: 1\ntitle: Jackie Brown\ndirectors:\n- Quentin Tarantino\nrating: 7.5\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_cells_and_separates_names_with_semicolons() {
+        let csv = output(
+            OutputFormat::Csv,
+            Some(&["title", "directors", "rating"]),
+            false,
+        )
+        .list(&films())
+        .unwrap();
+        assert_eq!(
+            csv,
+            "title,directors,rating\n\
+             Jackie Brown,Quentin Tarantino,7.5\n\
+             \"From Dusk till Dawn, the film\",Robert Rodriguez;QT,7.2"
+        );
+    }
+
+    #[test]
+    fn ids_prints_one_id_per_line() {
+        let ids = output(OutputFormat::Ids, None, true)
+            .list(&films())
+            .unwrap();
+        assert_eq!(ids, "1\n12");
+    }
+
+    #[test]
+    fn columns_pick_and_order_the_fields() {
+        let table = output(OutputFormat::Table, Some(&["rating", "id"]), false)
+            .list(&films())
+            .unwrap();
+        assert_eq!(table, "rating\tid\n7.5\t1\n7.2\t12");
+    }
+
+    #[test]
+    fn unknown_columns_are_refused() {
+        for format in [OutputFormat::Table, OutputFormat::Csv] {
+            let error = output(format, Some(&["id", "budget"]), false)
+                .list(&films())
+                .unwrap_err();
+            assert!(error.to_string().contains("unknown column 'budget'"));
+            assert_eq!(crate::errors::exit_code(&error), crate::errors::USAGE);
+        }
+
+        let error = output(OutputFormat::Table, Some(&["budget"]), false)
+            .item(&films()[0])
+            .unwrap_err();
+        assert_eq!(crate::errors::exit_code(&error), crate::errors::USAGE);
+    }
+
+    #[test]
+    fn piped_cells_keep_one_row_per_line() {
+        let film = Film {
+            id: 3,
+            title: "Kill Bill\tVol. 1\nVol. 2".to_string(),
+            directors: vec!["Quentin Tarantino".to_string()],
+            rating: 8.1,
+        };
+        let piped = output(OutputFormat::Table, None, false)
+            .list(&[film])
+            .unwrap();
+        assert_eq!(piped, "id\ttitle\n3\tKill Bill Vol. 1 Vol. 2");
+    }
+
+    #[test]
+    fn long_cells_are_cut_on_a_terminal() {
+        let cell = "x".repeat(MAX_CELL_WIDTH + 10);
+        let cut = truncate(&cell);
+        assert_eq!(cut.chars().count(), MAX_CELL_WIDTH);
+        assert!(cut.ends_with('…'));
+        assert_eq!(truncate("line\nbreak"), "line break");
+    }
+}
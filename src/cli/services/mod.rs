@@ -1,3 +0,0 @@
-pub mod interviews;
-pub mod movies;
-pub mod quotes;
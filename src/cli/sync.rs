@@ -0,0 +1,412 @@
+use crate::input::{field_errors, json_error};
+use crate::output::Columns;
+use anyhow::{Context, bail};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tarantino_rs::client::{InterviewsService, MoviesService, QuotesService};
+use tarantino_rs::interviews::api::models::CreateInterviewRequest;
+use tarantino_rs::movies::api::models::{CreateMovieRequest, MovieListParams};
+use tarantino_rs::quotes::api::models::CreateQuoteRequest;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl SyncAction {
+    fn verb(self) -> &'static str {
+        match self {
+            SyncAction::Create => "create",
+            SyncAction::Update => "update",
+            SyncAction::Delete => "delete",
+        }
+    }
+
+    fn past_tense(self) -> &'static str {
+        match self {
+            SyncAction::Create => "Created",
+            SyncAction::Update => "Updated",
+            SyncAction::Delete => "Deleted",
+        }
+    }
+}
+
+/// A record as written in the catalogue directory.
+#[derive(Debug, Clone)]
+enum Record {
+    Movie(Box<CreateMovieRequest>),
+    Quote(CreateQuoteRequest),
+    Interview(CreateInterviewRequest),
+}
+
+/// One step of a sync plan.
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub resource: &'static str,
+    pub action: SyncAction,
+    /// Natural key: the title, or the text of a quote
+    pub key: String,
+    /// Server id of the record being updated or deleted
+    pub id: Option<i32>,
+    /// Fields that differ from the server, for updates
+    pub fields: Vec<String>,
+    #[serde(skip)]
+    record: Option<Record>,
+}
+
+impl Columns for Change {
+    const COLUMNS: &'static [&'static str] = &["resource", "action", "key", "id", "fields"];
+    const DEFAULT_COLUMNS: &'static [&'static str] = &["action", "resource", "key", "id", "fields"];
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncPlan {
+    pub dry_run: bool,
+    pub changes: Vec<Change>,
+}
+
+/// A file holds either one record or an array of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    Many(Vec<T>),
+    One(T),
+}
+
+/// Reads `<dir>/<resource>/*.json`, or `None` when that directory does not
+/// exist, so resources left out of the catalogue are never touched.
+fn read_records<T: DeserializeOwned + Validate>(
+    dir: &Path,
+    resource: &str,
+    key: impl Fn(&T) -> &str,
+) -> anyhow::Result<Option<BTreeMap<String, T>>> {
+    let dir = dir.join(resource);
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+
+    let mut paths = std::fs::read_dir(&dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+    paths.sort();
+
+    let mut records = BTreeMap::new();
+    let mut sources: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for path in paths {
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let source = path.display().to_string();
+        let parsed = serde_json::from_str::<OneOrMany<T>>(&json)
+            .map_err(|e| anyhow::anyhow!(json_error(&source, &json, &e)))?;
+        let parsed = match parsed {
+            OneOrMany::Many(records) => records,
+            OneOrMany::One(record) => vec![record],
+        };
+
+        for record in parsed {
+            let record_key = key(&record).to_string();
+            if let Err(errors) = record.validate() {
+                bail!(
+                    "invalid record '{}' in {}:\n{}",
+                    record_key,
+                    source,
+                    field_errors(&errors)
+                );
+            }
+            if let Some(first) = sources.get(&record_key) {
+                bail!(
+                    "'{}' is in both {} and {}",
+                    record_key,
+                    first.display(),
+                    source
+                );
+            }
+            sources.insert(record_key.clone(), path.clone());
+            records.insert(record_key, record);
+        }
+    }
+    Ok(Some(records))
+}
+
+/// A record as a JSON object with list fields sorted, so that the order the
+/// server returns people and awards in does not count as a difference.
+fn normalized<T: Serialize>(record: &T) -> anyhow::Result<Map<String, Value>> {
+    let Value::Object(mut fields) = serde_json::from_str(&serde_json::to_string(record)?)? else {
+        bail!("records are JSON objects");
+    };
+    for value in fields.values_mut() {
+        if let Value::Array(items) = value {
+            items.sort_by_key(|item| item.to_string());
+        }
+    }
+    Ok(fields)
+}
+
+fn changed_fields<T: Serialize>(server: &T, local: &T) -> anyhow::Result<Vec<String>> {
+    let server = normalized(server)?;
+    let local = normalized(local)?;
+    Ok(local
+        .iter()
+        .filter(|(field, value)| server.get(*field) != Some(value))
+        .map(|(field, _)| field.clone())
+        .collect())
+}
+
+/// Compares local records with the server ones, both keyed by natural key.
+fn diff<T: Serialize>(
+    resource: &'static str,
+    local: BTreeMap<String, T>,
+    mut server: BTreeMap<String, (i32, T)>,
+    wrap: impl Fn(T) -> Record,
+) -> anyhow::Result<Vec<Change>> {
+    let mut changes = Vec::new();
+    for (key, record) in local {
+        match server.remove(&key) {
+            None => changes.push(Change {
+                resource,
+                action: SyncAction::Create,
+                key,
+                id: None,
+                fields: Vec::new(),
+                record: Some(wrap(record)),
+            }),
+            Some((id, current)) => {
+                let fields = changed_fields(&current, &record)?;
+                if !fields.is_empty() {
+                    changes.push(Change {
+                        resource,
+                        action: SyncAction::Update,
+                        key,
+                        id: Some(id),
+                        fields,
+                        record: Some(wrap(record)),
+                    });
+                }
+            }
+        }
+    }
+    changes.extend(server.into_iter().map(|(key, (id, _))| Change {
+        resource,
+        action: SyncAction::Delete,
+        key,
+        id: Some(id),
+        fields: Vec::new(),
+        record: None,
+    }));
+    Ok(changes)
+}
+
+/// Makes the server catalogue match a directory of JSON files.
+///
+/// The directory holds `movies/`, `quotes/` and `interviews/` folders of
+/// `*.json` files, each with one record or an array of records, in the same
+/// shape `create` takes. Records are matched by title (text for quotes).
+pub struct Syncer<'a> {
+    pub movies: &'a MoviesService,
+    pub quotes: &'a QuotesService,
+    pub interviews: &'a InterviewsService,
+}
+
+impl Syncer<'_> {
+    pub async fn plan(&self, dir: &Path) -> anyhow::Result<Vec<Change>> {
+        if !dir.is_dir() {
+            bail!("{} is not a directory", dir.display());
+        }
+
+        let mut changes = Vec::new();
+        let mut found = false;
+
+        if let Some(local) = read_records(dir, "movies", |m: &CreateMovieRequest| &m.title)? {
+            found = true;
+            let server = self
+                .movies
+                .all(MovieListParams::default())
+                .await?
+                .into_iter()
+                .map(|movie| {
+                    (
+                        movie.title.clone(),
+                        (movie.id, CreateMovieRequest::from(movie)),
+                    )
+                })
+                .collect();
+            changes.extend(diff("movie", local, server, |movie| {
+                Record::Movie(Box::new(movie))
+            })?);
+        }
+
+        if let Some(local) = read_records(dir, "quotes", |q: &CreateQuoteRequest| &q.text)? {
+            found = true;
+            let server = self
+                .quotes
+                .all()
+                .await?
+                .into_iter()
+                .map(|quote| {
+                    let text = quote.text.clone();
+                    (text.clone(), (quote.id, CreateQuoteRequest { text }))
+                })
+                .collect();
+            changes.extend(diff("quote", local, server, Record::Quote)?);
+        }
+
+        if let Some(local) = read_records(dir, "interviews", |i: &CreateInterviewRequest| &i.title)?
+        {
+            found = true;
+            let server = self
+                .interviews
+                .all()
+                .await?
+                .into_iter()
+                .map(|interview| {
+                    (
+                        interview.title.clone(),
+                        (
+                            interview.id,
+                            CreateInterviewRequest {
+                                title: interview.title,
+                                description: interview.description,
+                                youtube_id: interview.youtube_id,
+                            },
+                        ),
+                    )
+                })
+                .collect();
+            changes.extend(diff("interview", local, server, Record::Interview)?);
+        }
+
+        if !found {
+            bail!(
+                "{} has no movies, quotes or interviews directory",
+                dir.display()
+            );
+        }
+
+        // Creates first, deletes last, so a failure part way leaves nothing missing
+        changes.sort_by_key(|change| match change.action {
+            SyncAction::Create => 0,
+            SyncAction::Update => 1,
+            SyncAction::Delete => 2,
+        });
+        Ok(changes)
+    }
+
+    /// Applies the plan in order, stopping at the first failure.
+    pub async fn apply(&self, changes: &[Change]) -> anyhow::Result<()> {
+        for (done, change) in changes.iter().enumerate() {
+            self.apply_change(change).await.with_context(|| {
+                format!(
+                    "failed to {} {} '{}' ({} of {} changes applied)",
+                    change.action.verb(),
+                    change.resource,
+                    change.key,
+                    done,
+                    changes.len()
+                )
+            })?;
+            eprintln!(
+                "{} {} '{}'",
+                change.action.past_tense(),
+                change.resource,
+                change.key
+            );
+        }
+        Ok(())
+    }
+
+    async fn apply_change(&self, change: &Change) -> anyhow::Result<()> {
+        let id = change.id.unwrap_or_default();
+        match (&change.record, change.action) {
+            (Some(Record::Movie(movie)), SyncAction::Create) => {
+                self.movies.create_movie(movie).await?;
+            }
+            (Some(Record::Movie(movie)), SyncAction::Update) => {
+                self.movies.update_movie(id, movie).await?;
+            }
+            (Some(Record::Quote(quote)), SyncAction::Create) => {
+                self.quotes.create_quote(quote).await?;
+            }
+            (Some(Record::Interview(interview)), SyncAction::Create) => {
+                self.interviews.create_interview(interview).await?;
+            }
+            (Some(Record::Interview(interview)), SyncAction::Update) => {
+                self.interviews.update_interview(id, interview).await?;
+            }
+            (_, SyncAction::Delete) => match change.resource {
+                "movie" => {
+                    self.movies.delete_movie(id).await?;
+                }
+                "quote" => {
+                    self.quotes.delete_quote(id).await?;
+                }
+                _ => {
+                    self.interviews.delete_interview(id).await?;
+                }
+            },
+            _ => bail!("quotes have no fields to update"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interview(title: &str, description: &str) -> CreateInterviewRequest {
+        CreateInterviewRequest {
+            title: title.to_string(),
+            description: description.to_string(),
+            youtube_id: "abcdefghijk".to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_creates_updates_and_deletes_by_title() {
+        let local = BTreeMap::from([
+            ("Kept".to_string(), interview("Kept", "same")),
+            ("Changed".to_string(), interview("Changed", "new")),
+            ("Added".to_string(), interview("Added", "d")),
+        ]);
+        let server = BTreeMap::from([
+            ("Kept".to_string(), (1, interview("Kept", "same"))),
+            ("Changed".to_string(), (2, interview("Changed", "old"))),
+            ("Gone".to_string(), (3, interview("Gone", "d"))),
+        ]);
+
+        let changes = diff("interview", local, server, Record::Interview).unwrap();
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.action, c.key.as_str(), c.id, c.fields.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (SyncAction::Create, "Added", None, vec![]),
+                (
+                    SyncAction::Update,
+                    "Changed",
+                    Some(2),
+                    vec!["description".to_string()]
+                ),
+                (SyncAction::Delete, "Gone", Some(3), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn list_order_is_not_a_change() {
+        let server = serde_json::json!({"genres": ["Crime", "Drama"], "runtime": 99});
+        let local = serde_json::json!({"genres": ["Drama", "Crime"], "runtime": 100});
+        assert_eq!(changed_fields(&server, &local).unwrap(), vec!["runtime"]);
+    }
+}
@@ -0,0 +1,278 @@
+use super::{Client, ClientError};
+use crate::interviews::api::models::{CreateInterviewRequest, InterviewListParams};
+use crate::movies::api::models::{CreateMovieRequest, MovieListParams, MovieSort, SortOrder};
+use crate::quotes::api::models::{CreateQuoteRequest, QuoteListParams};
+use crate::store::Store;
+use crate::test_support::{create_database, drop_database};
+use axum::Router;
+use futures::TryStreamExt;
+use reqwest::StatusCode;
+use sqlx::postgres::PgPool;
+
+/// Serves the REST API on a free local port; the client talks real HTTP to it.
+async fn start_server(pool: &PgPool) -> Client {
+    let store = Store {
+        connection: pool.clone(),
+    };
+    let app = Router::new()
+        .nest("/api/v1", crate::movies::rest_api_router(&store))
+        .nest("/api/v1", crate::quotes::rest_api_router(&store))
+        .nest("/api/v1", crate::interviews::rest_api_router(&store));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    Client::new(url).unwrap()
+}
+
+fn movie(title: &str, release_year: i32, rating: f32) -> CreateMovieRequest {
+    serde_json::from_value(serde_json::json!({
+        "title": title,
+        "release_year": release_year,
+        "genre": "Crime",
+        "plot": "A plot.",
+        "runtime": 120,
+        "rating": rating,
+        "release_date": format!("{}-01-01", release_year),
+        "image_url": "https://example.com/poster.jpg",
+        "youtube_id": "abcdefghijk",
+        "production_details": "A Band Apart",
+        "budget": "$1 million",
+        "directors": ["Quentin Tarantino"],
+        "producers": ["Lawrence Bender"],
+        "actors": ["Harvey Keitel"],
+        "writers": ["Quentin Tarantino"],
+        "awards": [],
+        "nominations": []
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_movie_round_trip() {
+    let name = "tarantinors_client_movies";
+    let pool = create_database(name).await;
+    let movies = start_server(&pool).await.movies();
+
+    let created = movies
+        .create_movie(&movie("Reservoir Dogs", 1992, 8.3))
+        .await
+        .unwrap();
+    let id = movies.find_id_by_title("reservoir dogs").await.unwrap();
+    assert_eq!(created.id, Some(id));
+
+    let created = movies.get_movie(id).await.unwrap();
+    assert_eq!(created.title, "Reservoir Dogs");
+    assert_eq!(created.actors, vec!["Harvey Keitel"]);
+
+    let mut changed = movie("Reservoir Dogs", 1992, 9.0);
+    changed.actors.push("Tim Roth".to_string());
+    movies.update_movie(id, &changed).await.unwrap();
+    let mut actors = movies.get_actors(id).await.unwrap().actors;
+    actors.sort();
+    assert_eq!(actors, vec!["Harvey Keitel", "Tim Roth"]);
+
+    movies.delete_movie(id).await.unwrap();
+    let error = movies.get_movie(id).await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)));
+    assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+    drop_database(pool, name).await;
+}
+
+#[tokio::test]
+async fn test_movie_filters_match_text_literally() {
+    let name = "tarantinors_client_filters";
+    let pool = create_database(name).await;
+    let movies = start_server(&pool).await.movies();
+
+    let mut percent = movie("100% Tarantino", 2010, 7.0);
+    percent.plot = "Every film, 100% uncut.".to_string();
+    percent.actors = vec!["Zoë Bell".to_string()];
+    for request in [movie("Reservoir Dogs", 1992, 8.3), percent] {
+        movies.create_movie(&request).await.unwrap();
+    }
+
+    let titles = |params: MovieListParams| {
+        let movies = movies.clone();
+        async move {
+            let mut titles: Vec<_> = movies
+                .all(params)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|movie| movie.title)
+                .collect();
+            titles.sort();
+            titles
+        }
+    };
+
+    // `%`, `_` and `\` are plain characters, not patterns
+    for text in ["_", "\\", "R_servoir"] {
+        let found = titles(MovieListParams {
+            q: Some(text.to_string()),
+            ..MovieListParams::default()
+        })
+        .await;
+        assert!(found.is_empty(), "{text:?} matched {found:?}");
+    }
+
+    let found = titles(MovieListParams {
+        q: Some("%".to_string()),
+        ..MovieListParams::default()
+    })
+    .await;
+    assert_eq!(found, vec!["100% Tarantino"]);
+
+    let found = titles(MovieListParams {
+        q: Some("0% u".to_string()),
+        ..MovieListParams::default()
+    })
+    .await;
+    assert_eq!(found, vec!["100% Tarantino"]);
+
+    let found = titles(MovieListParams {
+        director: Some("tarantino".to_string()),
+        ..MovieListParams::default()
+    })
+    .await;
+    assert_eq!(found, vec!["100% Tarantino", "Reservoir Dogs"]);
+
+    let found = titles(MovieListParams {
+        actor: Some("_".to_string()),
+        ..MovieListParams::default()
+    })
+    .await;
+    assert!(found.is_empty());
+
+    let found = titles(MovieListParams {
+        actor: Some("BELL".to_string()),
+        ..MovieListParams::default()
+    })
+    .await;
+    assert_eq!(found, vec!["100% Tarantino"]);
+
+    drop_database(pool, name).await;
+}
+
+#[tokio::test]
+async fn test_errors_carry_server_details() {
+    let name = "tarantinors_client_errors";
+    let pool = create_database(name).await;
+    let client = start_server(&pool).await;
+
+    let error = client
+        .quotes()
+        .create_quote(&CreateQuoteRequest {
+            text: String::new(),
+        })
+        .await
+        .unwrap_err();
+    let ClientError::Validation(details) = &error else {
+        panic!("expected a validation error, got {:?}", error);
+    };
+    assert_eq!(details.status, StatusCode::BAD_REQUEST);
+    assert_eq!(details.field_errors["text"], vec!["Text cannot be empty"]);
+    assert_eq!(details.details.as_ref().unwrap()["error_count"], 1);
+
+    let interview = |title: &str| CreateInterviewRequest {
+        title: title.to_string(),
+        description: format!("{} talk.", title),
+        youtube_id: format!("{}-video", title),
+    };
+    let interviews = client.interviews();
+    interviews
+        .create_interview(&interview("First"))
+        .await
+        .unwrap();
+    interviews
+        .create_interview(&interview("Second"))
+        .await
+        .unwrap();
+    let second = interviews.all().await.unwrap()[1].id;
+
+    let renamed = CreateInterviewRequest {
+        title: "First".to_string(),
+        ..interview("Second")
+    };
+    let error = interviews
+        .update_interview(second, &renamed)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Conflict(_)));
+    assert_eq!(error.to_string(), "Another interview is titled 'First'");
+
+    drop_database(pool, name).await;
+}
+
+#[tokio::test]
+async fn test_pagination() {
+    let name = "tarantinors_client_pages";
+    let pool = create_database(name).await;
+    let client = start_server(&pool).await;
+
+    for text in ["a", "b", "c", "d", "e"] {
+        client
+            .quotes()
+            .create_quote(&CreateQuoteRequest {
+                text: text.to_string(),
+            })
+            .await
+            .unwrap();
+    }
+    let quotes = client.quotes();
+
+    let page = quotes
+        .list(&QuoteListParams {
+            limit: Some(2),
+            offset: Some(2),
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 5);
+    let texts: Vec<_> = page
+        .quotes
+        .iter()
+        .map(|quote| quote.text.as_str())
+        .collect();
+    assert_eq!(texts, vec!["c", "d"]);
+
+    let streamed: Vec<_> = quotes.stream(2).try_collect().await.unwrap();
+    let texts: Vec<_> = streamed.iter().map(|quote| quote.text.as_str()).collect();
+    assert_eq!(texts, vec!["a", "b", "c", "d", "e"]);
+
+    for (title, year, rating) in [("One", 1992, 8.3), ("Two", 1994, 8.9), ("Three", 1997, 7.5)] {
+        client
+            .movies()
+            .create_movie(&movie(title, year, rating))
+            .await
+            .unwrap();
+    }
+    let by_rating = MovieListParams {
+        sort: MovieSort::Rating,
+        order: SortOrder::Desc,
+        ..MovieListParams::default()
+    };
+    let titles: Vec<_> = client
+        .movies()
+        .stream(by_rating, 1)
+        .map_ok(|movie| movie.title)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(titles, vec!["Two", "One", "Three"]);
+
+    let error = client
+        .interviews()
+        .list(&InterviewListParams {
+            limit: Some(0),
+            offset: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ClientError::Validation(_)));
+
+    drop_database(pool, name).await;
+}
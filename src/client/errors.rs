@@ -0,0 +1,109 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// The error body the API sends with failed requests.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+    /// Messages per request field, for validation failures
+    pub field_errors: BTreeMap<String, Vec<String>>,
+    /// The `details` object exactly as the server sent it
+    pub details: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    message: String,
+    #[serde(default)]
+    details: Option<Value>,
+}
+
+impl ApiError {
+    /// Parses an error response body; bodies that are not API errors (such as
+    /// axum extractor rejections) become the message as they are.
+    pub(crate) fn from_body(status: StatusCode, text: &str) -> Self {
+        match serde_json::from_str::<ApiErrorBody>(text) {
+            Ok(body) => {
+                let field_errors = body
+                    .details
+                    .as_ref()
+                    .and_then(|details| details.get("field_errors"))
+                    .and_then(|errors| serde_json::from_value(errors.clone()).ok())
+                    .unwrap_or_default();
+                Self {
+                    status,
+                    message: body.message,
+                    field_errors,
+                    details: body.details,
+                }
+            }
+            Err(_) => Self {
+                status,
+                message: text.trim().to_string(),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+fn format_field_errors(field_errors: &BTreeMap<String, Vec<String>>) -> String {
+    field_errors
+        .iter()
+        .flat_map(|(field, messages)| {
+            messages
+                .iter()
+                .map(move |message| format!("\n  {}: {}", field, message))
+        })
+        .collect()
+}
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("HTTP error {}: {}", .0.status, .0.message)]
+    HttpError(Box<ApiError>),
+    #[error("Request failed")]
+    RequestFailed(#[from] reqwest::Error),
+    #[error("Serialization error")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Not found: {}", .0.message)]
+    NotFound(Box<ApiError>),
+    #[error("{}{}", .0.message, format_field_errors(&.0.field_errors))]
+    Validation(Box<ApiError>),
+    #[error("{}", .0.message)]
+    Conflict(Box<ApiError>),
+    #[error("Unauthorized")]
+    Unauthorized(Box<ApiError>),
+    #[error("Rate limited")]
+    RateLimited(Box<ApiError>),
+    #[error("The API token contains characters that cannot be sent in a header")]
+    InvalidToken,
+}
+
+impl ClientError {
+    /// The HTTP status the server answered with, if it answered.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::RequestFailed(e) => e.status(),
+            _ => self.api_error().map(|error| error.status),
+        }
+    }
+
+    /// The error body the server sent, if any.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Self::HttpError(error)
+            | Self::NotFound(error)
+            | Self::Validation(error)
+            | Self::Conflict(error)
+            | Self::Unauthorized(error)
+            | Self::RateLimited(error) => Some(error),
+            Self::RequestFailed(_) | Self::SerializationError(_) | Self::InvalidToken => None,
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
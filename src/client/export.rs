@@ -0,0 +1,23 @@
+use crate::client::errors::ClientResult;
+use crate::client::http::HttpClient;
+use crate::export::api::models::ExportParams;
+
+/// The `/api/v1/export` endpoints.
+#[derive(Debug, Clone)]
+pub struct ExportService {
+    http_client: HttpClient,
+}
+
+impl ExportService {
+    pub(crate) fn new(http_client: &HttpClient) -> Self {
+        Self {
+            http_client: http_client.join("/api/v1/export"),
+        }
+    }
+
+    /// Downloads the archive as text, exactly as the server rendered it.
+    pub async fn export(&self, params: &ExportParams) -> ClientResult<String> {
+        let url_path = "";
+        self.http_client.get_text(url_path, params).await
+    }
+}
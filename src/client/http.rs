@@ -0,0 +1,552 @@
+use crate::client::errors::{ApiError, ClientError, ClientResult};
+use crate::telemetry;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, header};
+
+use std::time::{Duration, SystemTime};
+use tracing::{Span, debug, error, info, warn};
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where and how to reach the API.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Server root, e.g. `http://localhost:3000`
+    pub url: String,
+    /// Limit for a whole request, per attempt
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    /// Sent as a bearer token with every request
+    pub token: Option<String>,
+    pub retry: RetryPolicy,
+    pub user_agent: String,
+}
+
+impl ClientConfig {
+    /// Default settings for the server at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            token: None,
+            retry: RetryPolicy::default(),
+            user_agent: concat!("tarantino-rs/", env!("CARGO_PKG_VERSION")).to_string(),
+        }
+    }
+}
+
+/// How idempotent requests are retried after connection errors, `429 Too Many
+/// Requests` and `503 Service Unavailable`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts after the first one; `0` disables retries
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Longest wait between attempts; a longer `Retry-After` is not waited out
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff with jitter: a random delay between half and all of
+    /// `base_delay * 2^attempt`, capped at `max_delay`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        ceiling / 2 + ceiling.mul_f64(fastrand::f64() / 2.0)
+    }
+
+    /// The wait before retrying, `None` when the server asked for more than
+    /// `max_delay`.
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Reads `Retry-After` as either delay seconds or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    match value.trim().parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            )
+        }
+    }
+}
+
+/// Sends requests under one base URL; cheap to clone, clones share connections.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+impl HttpClient {
+    pub fn new(config: &ClientConfig) -> ClientResult<Self> {
+        let mut headers = header::HeaderMap::new();
+        if let Some(token) = &config.token {
+            let mut value = header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|_| ClientError::InvalidToken)?;
+            value.set_sensitive(true);
+            headers.insert(header::AUTHORIZATION, value);
+        }
+
+        // HTTP/2 is negotiated through ALPN over TLS; plain HTTP uses HTTP/1.1
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout)
+            .user_agent(config.user_agent.as_str())
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            retry: config.retry.clone(),
+        })
+    }
+
+    /// A client for `path` under this one, e.g. `/api/v1/quotes`.
+    pub fn join(&self, path: &str) -> Self {
+        Self {
+            base_url: format!("{}{}", self.base_url, path),
+            ..self.clone()
+        }
+    }
+
+    /// Sends the request, retrying idempotent ones as the retry policy allows.
+    async fn send(&self, request: RequestBuilder) -> ClientResult<Response> {
+        let request = request.build()?;
+        let idempotent = request.method().is_idempotent();
+
+        let mut attempt = 0;
+        loop {
+            let retry = match request.try_clone() {
+                Some(retry) if idempotent && attempt < self.retry.max_retries => retry,
+                _ => break,
+            };
+
+            let delay = match self.client.execute(retry).await {
+                Ok(response)
+                    if matches!(
+                        response.status(),
+                        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+                    ) =>
+                {
+                    match self.retry.delay(attempt, retry_after(&response)) {
+                        Some(delay) => {
+                            warn!(
+                                "{} {}: {}, retrying",
+                                request.method(),
+                                request.url(),
+                                response.status()
+                            );
+                            delay
+                        }
+                        None => return Ok(response),
+                    }
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if e.is_connect() => {
+                    warn!("{} {}: {}, retrying", request.method(), request.url(), e);
+                    self.retry.backoff(attempt)
+                }
+                Err(e) => {
+                    error!("Request failed: {}", e);
+                    return Err(ClientError::RequestFailed(e));
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+
+        self.client.execute(request).await.map_err(|e| {
+            error!("Request failed: {}", e);
+            ClientError::RequestFailed(e)
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_str()
+    }
+
+    pub async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {}", url);
+
+        let request = self.client.get(&url).headers(trace_headers());
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn get_query<Q: serde::ser::Serialize, T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {}", url);
+
+        let request = self.client.get(&url).headers(trace_headers()).query(query);
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn get_text<Q: serde::ser::Serialize>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> ClientResult<String> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("GET {}", url);
+
+        let request = self.client.get(&url).headers(trace_headers()).query(query);
+        let response = self.send(request).await?;
+
+        let body = self.error_for_status(response).await?.text().await?;
+        Ok(body)
+    }
+
+    pub async fn post<
+        B: serde::ser::Serialize + std::fmt::Debug,
+        T: serde::de::DeserializeOwned,
+    >(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("POST {} with body", url);
+
+        let request = self
+            .client
+            .post(&url)
+            .headers(trace_headers())
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&body);
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn post_body<Q: serde::ser::Serialize, T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &Q,
+        body: String,
+        content_type: &str,
+    ) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("POST {} with {} body", url, content_type);
+
+        let request = self
+            .client
+            .post(&url)
+            .headers(trace_headers())
+            .query(query)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(body);
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn put<B: serde::ser::Serialize, T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("PUT {}", url);
+
+        let request = self.client.put(&url).headers(trace_headers()).json(body);
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub async fn delete<T: serde::de::DeserializeOwned>(&self, path: &str) -> ClientResult<T> {
+        let url = format!("{}{}", self.base_url, path);
+        debug!("DELETE {}", url);
+
+        let request = self.client.delete(&url).headers(trace_headers());
+        let response = self.send(request).await?;
+
+        self.handle_response(response).await
+    }
+
+    async fn handle_response<T: serde::de::DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> ClientResult<T> {
+        let body = self.error_for_status(response).await?.json::<T>().await?;
+        Ok(body)
+    }
+
+    async fn error_for_status(
+        &self,
+        response: reqwest::Response,
+    ) -> ClientResult<reqwest::Response> {
+        let status = response.status();
+
+        if status.is_success() {
+            info!("Response: OK");
+            return Ok(response);
+        }
+
+        let error = Box::new(ApiError::from_body(
+            status,
+            &response.text().await.unwrap_or_default(),
+        ));
+        error!("HTTP {}: {}", status, error.message);
+        Err(match status {
+            StatusCode::NOT_FOUND => ClientError::NotFound(error),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                ClientError::Validation(error)
+            }
+            StatusCode::CONFLICT => ClientError::Conflict(error),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ClientError::Unauthorized(error),
+            StatusCode::TOO_MANY_REQUESTS => ClientError::RateLimited(error),
+            _ => ClientError::HttpError(error),
+        })
+    }
+}
+
+/// `traceparent` header continuing the current span's trace on the server.
+fn trace_headers() -> header::HeaderMap {
+    let mut headers = header::HeaderMap::new();
+    telemetry::inject_context(&Span::current(), &mut headers);
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    type Failure = (StatusCode, Option<&'static str>);
+
+    fn config(url: String, max_retries: u32) -> ClientConfig {
+        ClientConfig {
+            timeout: Duration::from_secs(5),
+            connect_timeout: Duration::from_secs(1),
+            retry: RetryPolicy {
+                max_retries,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_secs(2),
+            },
+            ..ClientConfig::new(url)
+        }
+    }
+
+    /// Serves `failures` in turn, with an optional `Retry-After`, then `200`.
+    async fn mock_server(failures: &'static [Failure]) -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let handler = move || {
+            let hit = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match failures.get(hit) {
+                    Some((status, Some(after))) => {
+                        (*status, [(header::RETRY_AFTER, *after)], "{}").into_response()
+                    }
+                    Some((status, None)) => (*status, "{}").into_response(),
+                    None => axum::Json(json!({ "ok": true })).into_response(),
+                }
+            }
+        };
+        let router: Router = Router::new().route("/", get(handler.clone()).post(handler));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (url, hits)
+    }
+
+    fn ok(result: ClientResult<Value>) -> Value {
+        result.unwrap_or_else(|e| panic!("request failed: {}", e))
+    }
+
+    #[tokio::test]
+    async fn test_retries_service_unavailable() {
+        const FAILURES: &[Failure] = &[
+            (StatusCode::SERVICE_UNAVAILABLE, None),
+            (StatusCode::TOO_MANY_REQUESTS, None),
+        ];
+        let (url, hits) = mock_server(FAILURES).await;
+        let client = HttpClient::new(&config(url, 3)).unwrap();
+
+        let body = ok(client.get("/").await);
+
+        assert_eq!(body, json!({ "ok": true }));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        const FAILURES: &[Failure] = &[(StatusCode::SERVICE_UNAVAILABLE, None); 5];
+        let (url, hits) = mock_server(FAILURES).await;
+        let client = HttpClient::new(&config(url, 2)).unwrap();
+
+        let result = client.get::<Value>("/").await;
+
+        assert_eq!(
+            result.unwrap_err().status(),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_honours_retry_after() {
+        const FAILURES: &[Failure] = &[(StatusCode::TOO_MANY_REQUESTS, Some("1"))];
+        let (url, hits) = mock_server(FAILURES).await;
+        let client = HttpClient::new(&config(url, 3)).unwrap();
+
+        let started = Instant::now();
+        ok(client.get("/").await);
+
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_wait_out_long_retry_after() {
+        const FAILURES: &[Failure] = &[(StatusCode::TOO_MANY_REQUESTS, Some("120"))];
+        let (url, hits) = mock_server(FAILURES).await;
+        let client = HttpClient::new(&config(url, 3)).unwrap();
+
+        let result = client.get::<Value>("/").await;
+
+        assert!(matches!(result, Err(ClientError::RateLimited(_))));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_post() {
+        const FAILURES: &[Failure] = &[(StatusCode::SERVICE_UNAVAILABLE, None)];
+        let (url, hits) = mock_server(FAILURES).await;
+        let client = HttpClient::new(&config(url, 3)).unwrap();
+
+        let result = client.post::<_, Value>("/", &json!({})).await;
+
+        assert!(result.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_no_content() {
+        let router: Router = Router::new().route("/", get(|| async { StatusCode::NO_CONTENT }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        let client = HttpClient::new(&config(url, 0)).unwrap();
+
+        let body = client.get_text("/", &()).await;
+
+        assert_eq!(body.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_errors() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let client = HttpClient::new(&config(url, 2)).unwrap();
+
+        let started = Instant::now();
+        let result = client.get::<Value>("/").await;
+
+        assert!(matches!(result, Err(ClientError::RequestFailed(e)) if e.is_connect()));
+        // two backoffs of at least 5ms and 10ms
+        assert!(started.elapsed() >= Duration::from_millis(15));
+    }
+
+    #[tokio::test]
+    async fn test_talks_to_http1_only_servers() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let read = socket.read(&mut request).await.unwrap();
+            assert!(request[..read].starts_with(b"GET / HTTP/1.1\r\n"));
+
+            let body = r#"{"message":"Validation failed","details":{"field_errors":{"text":["must not be empty"]}}}"#;
+            let response = format!(
+                "HTTP/1.1 422 Unprocessable Entity\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        let client = HttpClient::new(&config(url, 0)).unwrap();
+
+        let error = client.get::<Value>("/").await.unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Validation failed\n  text: must not be empty"
+        );
+    }
+
+    #[test]
+    fn test_backoff_is_jittered_and_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+
+        for _ in 0..100 {
+            let first = policy.backoff(0);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let third = policy.backoff(2);
+            assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+            assert!(policy.backoff(20) <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_beyond_max_delay_is_not_waited() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(
+            policy.delay(0, Some(Duration::from_secs(2))),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(policy.delay(0, Some(policy.max_delay * 2)), None);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::client::errors::ClientResult;
+use crate::client::http::HttpClient;
+use crate::export::api::models::ArchiveFormat;
+use crate::import::api::models::{
+    ArchiveImportParams, ArchiveImportReport, ImportFormat, ImportParams, ImportReport,
+};
+
+/// The `/api/v1/import` endpoints.
+#[derive(Debug, Clone)]
+pub struct ImportService {
+    http_client: HttpClient,
+}
+
+impl ImportService {
+    pub(crate) fn new(http_client: &HttpClient) -> Self {
+        Self {
+            http_client: http_client.join("/api/v1/import"),
+        }
+    }
+
+    pub async fn import(&self, params: &ImportParams, body: String) -> ClientResult<ImportReport> {
+        let url_path = "";
+        let content_type = match params.format {
+            Some(ImportFormat::Csv) => "text/csv",
+            Some(ImportFormat::Jsonl) => "application/x-ndjson",
+            Some(ImportFormat::Json) | None => "application/json",
+        };
+
+        self.http_client
+            .post_body(url_path, params, body, content_type)
+            .await
+    }
+
+    pub async fn import_archive(
+        &self,
+        params: &ArchiveImportParams,
+        body: String,
+    ) -> ClientResult<ArchiveImportReport> {
+        let url_path = "/archive";
+        let content_type = match params.format {
+            Some(ArchiveFormat::Jsonl) => "application/x-ndjson",
+            Some(ArchiveFormat::Json) | None => "application/json",
+        };
+
+        self.http_client
+            .post_body(url_path, params, body, content_type)
+            .await
+    }
+}
@@ -0,0 +1,74 @@
+use crate::client::errors::ClientResult;
+use crate::client::http::HttpClient;
+use crate::client::pagination::{MAX_PAGE_SIZE, paginate};
+use crate::interviews::api::models::{
+    CreateInterviewRequest, InterviewListParams, InterviewListResponse, InterviewMessage,
+    InterviewResponse,
+};
+use futures::{Stream, TryStreamExt};
+
+/// The `/api/v1/interviews` endpoints.
+#[derive(Debug, Clone)]
+pub struct InterviewsService {
+    http_client: HttpClient,
+}
+
+impl InterviewsService {
+    pub(crate) fn new(http_client: &HttpClient) -> Self {
+        Self {
+            http_client: http_client.join("/api/v1/interviews"),
+        }
+    }
+
+    pub async fn get_interview(&self, id: i32) -> ClientResult<InterviewResponse> {
+        let url_path = format!("/{}", id);
+        self.http_client.get(&url_path).await
+    }
+
+    /// One page of interviews, or all of them when `params.limit` is `None`.
+    pub async fn list(&self, params: &InterviewListParams) -> ClientResult<InterviewListResponse> {
+        let url_path = "";
+        self.http_client.get_query(url_path, params).await
+    }
+
+    /// Every interview, fetched `page_size` at a time.
+    pub fn stream(
+        &self,
+        page_size: i64,
+    ) -> impl Stream<Item = ClientResult<InterviewResponse>> + '_ {
+        paginate(move |offset| {
+            let params = InterviewListParams {
+                limit: Some(page_size),
+                offset: Some(offset),
+            };
+            async move { self.list(&params).await }
+        })
+    }
+
+    /// Every interview, in pages of the largest size.
+    pub async fn all(&self) -> ClientResult<Vec<InterviewResponse>> {
+        self.stream(MAX_PAGE_SIZE).try_collect().await
+    }
+
+    pub async fn create_interview(
+        &self,
+        req: &CreateInterviewRequest,
+    ) -> ClientResult<InterviewMessage> {
+        let url_path = "";
+        self.http_client.post(url_path, req).await
+    }
+
+    pub async fn update_interview(
+        &self,
+        id: i32,
+        req: &CreateInterviewRequest,
+    ) -> ClientResult<InterviewMessage> {
+        let url_path = format!("/{}", id);
+        self.http_client.put(&url_path, req).await
+    }
+    //
+    pub async fn delete_interview(&self, id: i32) -> ClientResult<InterviewMessage> {
+        let url_path = format!("/{}", id);
+        self.http_client.delete(&url_path).await
+    }
+}
@@ -0,0 +1,127 @@
+//! Typed client for the tarantinors REST API.
+//!
+//! ```no_run
+//! use futures::TryStreamExt;
+//! use tarantino_rs::client::Client;
+//! use tarantino_rs::movies::api::models::MovieListParams;
+//!
+//! # async fn run() -> Result<(), tarantino_rs::client::ClientError> {
+//! let client = Client::builder("http://localhost:3000")
+//!     .token("secret")
+//!     .build()?;
+//!
+//! let movie = client.movies().get_movie(1).await?;
+//! let movies = client.movies().all(MovieListParams::default()).await?;
+//! let quotes: Vec<_> = client.quotes().stream(20).try_collect().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod errors;
+mod export;
+mod http;
+mod import;
+mod interviews;
+mod movies;
+mod pagination;
+mod quotes;
+
+pub use errors::{ApiError, ClientError, ClientResult};
+pub use export::ExportService;
+pub use http::{ClientConfig, DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, HttpClient, RetryPolicy};
+pub use import::ImportService;
+pub use interviews::InterviewsService;
+pub use movies::MoviesService;
+pub use pagination::{MAX_PAGE_SIZE, Page};
+pub use quotes::QuotesService;
+
+use std::time::Duration;
+
+/// Entry point to the API; cheap to clone, clones share connections.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http_client: HttpClient,
+}
+
+impl Client {
+    /// A client for the server at `url` with default settings.
+    pub fn new(url: impl Into<String>) -> ClientResult<Self> {
+        Self::builder(url).build()
+    }
+
+    pub fn builder(url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            config: ClientConfig::new(url),
+        }
+    }
+
+    pub fn from_config(config: &ClientConfig) -> ClientResult<Self> {
+        Ok(Self {
+            http_client: HttpClient::new(config)?,
+        })
+    }
+
+    pub fn movies(&self) -> MoviesService {
+        MoviesService::new(&self.http_client)
+    }
+
+    pub fn quotes(&self) -> QuotesService {
+        QuotesService::new(&self.http_client)
+    }
+
+    pub fn interviews(&self) -> InterviewsService {
+        InterviewsService::new(&self.http_client)
+    }
+
+    pub fn import(&self) -> ImportService {
+        ImportService::new(&self.http_client)
+    }
+
+    pub fn export(&self) -> ExportService {
+        ExportService::new(&self.http_client)
+    }
+}
+
+/// Settings for a [`Client`], starting from [`ClientConfig::new`].
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    config: ClientConfig,
+}
+
+impl ClientBuilder {
+    /// Limit for a whole request, per attempt; 30 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// 10 seconds by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+
+    /// Sent as a bearer token with every request.
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.config.token = Some(token.into());
+        self
+    }
+
+    /// How idempotent requests are retried; 3 retries by default.
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.config.retry = retry;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn build(self) -> ClientResult<Client> {
+        Client::from_config(&self.config)
+    }
+}
+
+#[cfg(test)]
+mod __tests;
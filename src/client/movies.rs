@@ -0,0 +1,125 @@
+use crate::client::errors::{ApiError, ClientError, ClientResult};
+use crate::client::http::HttpClient;
+use crate::client::pagination::{MAX_PAGE_SIZE, paginate};
+use crate::movies::api::models::{
+    Actors, CreateMovieRequest, Directors, MovieAwardsResponse, MovieListParams, MovieListResponse,
+    MovieNominationsResponse, MovieResponse, MoviesMessage, Producers, Writers,
+};
+use futures::{Stream, TryStreamExt};
+use reqwest::StatusCode;
+
+/// The `/api/v1/movies` endpoints.
+#[derive(Debug, Clone)]
+pub struct MoviesService {
+    http_client: HttpClient,
+}
+
+impl MoviesService {
+    pub(crate) fn new(http_client: &HttpClient) -> Self {
+        Self {
+            http_client: http_client.join("/api/v1/movies"),
+        }
+    }
+
+    pub async fn get_movie(&self, id: i32) -> ClientResult<MovieResponse> {
+        let url_path = format!("/{}", id);
+        self.http_client.get(&url_path).await
+    }
+
+    /// One page of movies, or all of them when `params.limit` is `None`.
+    pub async fn list(&self, params: &MovieListParams) -> ClientResult<MovieListResponse> {
+        let url_path = "";
+        self.http_client.get_query(url_path, params).await
+    }
+
+    /// Every movie matching `params`, fetched `page_size` at a time; the
+    /// `limit` and `offset` of `params` are ignored.
+    pub fn stream(
+        &self,
+        params: MovieListParams,
+        page_size: i64,
+    ) -> impl Stream<Item = ClientResult<MovieResponse>> + '_ {
+        paginate(move |offset| {
+            let params = MovieListParams {
+                limit: Some(page_size),
+                offset: Some(offset),
+                ..params.clone()
+            };
+            async move { self.list(&params).await }
+        })
+    }
+
+    /// Every movie matching `params`, in pages of the largest size.
+    pub async fn all(&self, params: MovieListParams) -> ClientResult<Vec<MovieResponse>> {
+        self.stream(params, MAX_PAGE_SIZE).try_collect().await
+    }
+
+    /// Looks up a movie id by its whole title, ignoring case.
+    pub async fn find_id_by_title(&self, title: &str) -> ClientResult<i32> {
+        let params = MovieListParams {
+            title: Some(title.to_string()),
+            ..MovieListParams::default()
+        };
+        self.list(&params)
+            .await?
+            .movies
+            .first()
+            .map(|movie| movie.id)
+            .ok_or_else(|| {
+                ClientError::NotFound(Box::new(ApiError {
+                    status: StatusCode::NOT_FOUND,
+                    message: format!("no movie titled '{}'", title),
+                    ..ApiError::default()
+                }))
+            })
+    }
+
+    pub async fn get_actors(&self, id: i32) -> ClientResult<Actors> {
+        let url_path = format!("/{}/actors", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn get_directors(&self, id: i32) -> ClientResult<Directors> {
+        let url_path = format!("/{}/directors", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn get_producers(&self, id: i32) -> ClientResult<Producers> {
+        let url_path = format!("/{}/producers", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn get_writers(&self, id: i32) -> ClientResult<Writers> {
+        let url_path = format!("/{}/writers", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn get_awards(&self, id: i32) -> ClientResult<MovieAwardsResponse> {
+        let url_path = format!("/{}/awards", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn get_nominations(&self, id: i32) -> ClientResult<MovieNominationsResponse> {
+        let url_path = format!("/{}/nominations", id);
+        self.http_client.get(&url_path).await
+    }
+
+    pub async fn create_movie(&self, req: &CreateMovieRequest) -> ClientResult<MoviesMessage> {
+        let url_path = "";
+        self.http_client.post(url_path, req).await
+    }
+
+    pub async fn update_movie(
+        &self,
+        id: i32,
+        req: &CreateMovieRequest,
+    ) -> ClientResult<MoviesMessage> {
+        let url_path = format!("/{}", id);
+        self.http_client.put(&url_path, req).await
+    }
+    //
+    pub async fn delete_movie(&self, id: i32) -> ClientResult<MoviesMessage> {
+        let url_path = format!("/{}", id);
+        self.http_client.delete(&url_path).await
+    }
+}
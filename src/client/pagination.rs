@@ -0,0 +1,83 @@
+use crate::client::errors::ClientResult;
+use crate::interviews::api::models::{InterviewListResponse, InterviewResponse};
+use crate::movies::api::models::{MovieListResponse, MovieResponse};
+use crate::quotes::api::models::{QuoteListResponse, QuoteResponse};
+use futures::{Stream, TryStreamExt, stream};
+
+/// Largest page the API serves.
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// A list response holding one page of a longer list.
+pub trait Page {
+    type Item;
+
+    /// Items across all pages
+    fn total(&self) -> i64;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl Page for MovieListResponse {
+    type Item = MovieResponse;
+
+    fn total(&self) -> i64 {
+        self.total
+    }
+
+    fn into_items(self) -> Vec<MovieResponse> {
+        self.movies
+    }
+}
+
+impl Page for QuoteListResponse {
+    type Item = QuoteResponse;
+
+    fn total(&self) -> i64 {
+        self.total
+    }
+
+    fn into_items(self) -> Vec<QuoteResponse> {
+        self.quotes
+    }
+}
+
+impl Page for InterviewListResponse {
+    type Item = InterviewResponse;
+
+    fn total(&self) -> i64 {
+        self.total
+    }
+
+    fn into_items(self) -> Vec<InterviewResponse> {
+        self.interviews
+    }
+}
+
+/// Streams every item of a list, calling `fetch` with the offset of each page
+/// until the server's total is reached or a page comes back empty.
+pub(crate) fn paginate<'a, P, F, Fut>(
+    mut fetch: F,
+) -> impl Stream<Item = ClientResult<P::Item>> + 'a
+where
+    P: Page + 'a,
+    P::Item: 'a,
+    F: FnMut(i64) -> Fut + 'a,
+    Fut: Future<Output = ClientResult<P>> + 'a,
+{
+    stream::try_unfold(Some(0), move |offset: Option<i64>| {
+        let page = offset.map(|offset| (offset, fetch(offset)));
+        async move {
+            let Some((offset, page)) = page else {
+                return ClientResult::Ok(None);
+            };
+            let page = page.await?;
+            let total = page.total();
+            let items = page.into_items();
+            let next = offset + items.len() as i64;
+            let more = !items.is_empty() && next < total;
+            Ok(Some((items, more.then_some(next))))
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
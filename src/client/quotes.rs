@@ -0,0 +1,58 @@
+use crate::client::errors::ClientResult;
+use crate::client::http::HttpClient;
+use crate::client::pagination::{MAX_PAGE_SIZE, paginate};
+use crate::quotes::api::models::{
+    CreateQuoteRequest, QuoteListParams, QuoteListResponse, QuoteMessage, QuoteResponse,
+};
+use futures::{Stream, TryStreamExt};
+
+/// The `/api/v1/quotes` endpoints.
+#[derive(Debug, Clone)]
+pub struct QuotesService {
+    http_client: HttpClient,
+}
+
+impl QuotesService {
+    pub(crate) fn new(http_client: &HttpClient) -> Self {
+        Self {
+            http_client: http_client.join("/api/v1/quotes"),
+        }
+    }
+
+    pub async fn get_quote(&self, id: i32) -> ClientResult<QuoteResponse> {
+        let url_path = format!("/{}", id);
+        self.http_client.get(&url_path).await
+    }
+
+    /// One page of quotes, or all of them when `params.limit` is `None`.
+    pub async fn list(&self, params: &QuoteListParams) -> ClientResult<QuoteListResponse> {
+        let url_path = "";
+        self.http_client.get_query(url_path, params).await
+    }
+
+    /// Every quote, fetched `page_size` at a time.
+    pub fn stream(&self, page_size: i64) -> impl Stream<Item = ClientResult<QuoteResponse>> + '_ {
+        paginate(move |offset| {
+            let params = QuoteListParams {
+                limit: Some(page_size),
+                offset: Some(offset),
+            };
+            async move { self.list(&params).await }
+        })
+    }
+
+    /// Every quote, in pages of the largest size.
+    pub async fn all(&self) -> ClientResult<Vec<QuoteResponse>> {
+        self.stream(MAX_PAGE_SIZE).try_collect().await
+    }
+
+    pub async fn create_quote(&self, req: &CreateQuoteRequest) -> ClientResult<QuoteMessage> {
+        let url_path = "";
+        self.http_client.post(url_path, req).await
+    }
+
+    pub async fn delete_quote(&self, id: i32) -> ClientResult<QuoteMessage> {
+        let url_path = format!("/{}", id);
+        self.http_client.delete(&url_path).await
+    }
+}
@@ -1,4 +1,7 @@
+use crate::export::api::handlers::ExportApiDoc;
+use crate::import::api::handlers::ImportApiDoc;
 use crate::interviews::api::handlers::InterviewsApiDoc;
+use crate::media::api::handlers::MediaApiDoc;
 use crate::movies::api::handlers::MoviesApiDoc;
 use crate::quotes::api::handlers::QuotesApiDoc;
 use utoipa::OpenApi;
@@ -26,6 +29,9 @@ use utoipa::OpenApi;
         (path = "/api/v1", api = MoviesApiDoc),
         (path = "/api/v1", api = QuotesApiDoc),
         (path = "/api/v1", api = InterviewsApiDoc),
+        (path = "/api/v1", api = ImportApiDoc),
+        (path = "/api/v1", api = ExportApiDoc),
+        (path = "/api/v1", api = MediaApiDoc),
     ),
 )]
 pub struct ApiDoc;
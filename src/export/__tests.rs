@@ -0,0 +1,155 @@
+use crate::store::Store;
+use crate::test_support::{create_database, drop_database};
+use axum::Router;
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use serde_json::{Value, json};
+use sqlx::postgres::PgPool;
+
+fn test_server(pool: &PgPool) -> TestServer {
+    let store = Store {
+        connection: pool.clone(),
+    };
+    let app = Router::new()
+        .nest("/api/v1", crate::import::rest_api_router(&store))
+        .nest("/api/v1", super::rest_api_router(&store));
+    TestServer::new(app).unwrap()
+}
+
+async fn seed(server: &TestServer) {
+    let movies = json!([
+        {
+            "title": "Jackie Brown",
+            "release_year": 1997,
+            "genre": "Crime",
+            "plot": "A flight attendant gets caught smuggling money.",
+            "runtime": 154,
+            "rating": 7.5,
+            "release_date": "1997-12-25",
+            "image_url": "https://example.com/jb.jpg",
+            "youtube_id": "B5Ib3ToYmPA",
+            "production_details": "A Band Apart",
+            "budget": "$12 million",
+            "directors": ["Quentin Tarantino"],
+            "producers": ["Lawrence Bender"],
+            "actors": ["Pam Grier", "Samuel L. Jackson", "Robert Forster"],
+            "writers": ["Quentin Tarantino", "Elmore Leonard"],
+            "awards": [],
+            "nominations": [
+                { "name": "Academy Awards", "category": "Best Supporting Actor", "year": 1998, "nominee": "Robert Forster" }
+            ]
+        },
+        {
+            "title": "Pulp Fiction",
+            "release_year": 1994,
+            "genre": "Crime",
+            "plot": "The lives of two mob hitmen intertwine.",
+            "runtime": 154,
+            "rating": 8.9,
+            "release_date": "1994-10-14",
+            "image_url": "https://example.com/pf.jpg",
+            "youtube_id": "s7EdQ4FqbhY",
+            "production_details": "A Band Apart",
+            "budget": "$8 million",
+            "directors": ["Quentin Tarantino"],
+            "producers": ["Lawrence Bender"],
+            "actors": ["John Travolta", "Uma Thurman", "Samuel L. Jackson"],
+            "writers": ["Quentin Tarantino", "Roger Avary"],
+            "awards": [
+                { "name": "Academy Awards", "category": "Best Original Screenplay", "year": 1995, "recipient": "Quentin Tarantino" }
+            ],
+            "nominations": [
+                { "name": "Academy Awards", "category": "Best Picture", "year": 1995, "nominee": "Lawrence Bender" }
+            ]
+        }
+    ]);
+    let quotes = json!([{ "text": "I steal from every single movie ever made." }]);
+    let interviews = json!([
+        { "title": "Charlie Rose 1994", "description": "On Pulp Fiction", "youtube_id": "abc123" }
+    ]);
+
+    for (resource, records) in [
+        ("movies", movies),
+        ("quotes", quotes),
+        ("interviews", interviews),
+    ] {
+        let response = server
+            .post("/api/v1/import")
+            .add_query_param("resource", resource)
+            .json(&records)
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["failed"], 0);
+    }
+}
+
+/// The archive minus the parts that legitimately differ between exports.
+fn without_timestamp(mut archive: Value) -> Value {
+    archive.as_object_mut().unwrap().remove("exported_at");
+    archive
+}
+
+#[tokio::test]
+async fn test_export_round_trips_through_import() {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let source_name = format!("tarantinors_export_source_{}", suffix);
+    let target_name = format!("tarantinors_export_target_{}", suffix);
+    let source_pool = create_database(&source_name).await;
+    let target_pool = create_database(&target_name).await;
+
+    let source = test_server(&source_pool);
+    let target = test_server(&target_pool);
+    seed(&source).await;
+
+    let exported = source.get("/api/v1/export").await;
+    exported.assert_status_ok();
+    let exported_archive = exported.json::<Value>();
+    assert_eq!(exported_archive["format"], "tarantinors-archive");
+    assert_eq!(exported_archive["version"], 1);
+    assert_eq!(
+        exported_archive["counts"],
+        json!({ "movies": 2, "quotes": 1, "interviews": 1 })
+    );
+
+    let exported_lines = source
+        .get("/api/v1/export")
+        .add_query_param("format", "jsonl")
+        .await
+        .text();
+    let imported = target
+        .post("/api/v1/import/archive")
+        .add_query_param("format", "jsonl")
+        .text(exported_lines.clone())
+        .await;
+    imported.assert_status_ok();
+    let report = imported.json::<Value>();
+    assert_eq!(report["committed"], true);
+    assert_eq!(report["movies"]["created"], 2);
+    assert_eq!(report["quotes"]["created"], 1);
+    assert_eq!(report["interviews"]["created"], 1);
+
+    let reexported = target.get("/api/v1/export").await.json::<Value>();
+    assert_eq!(
+        without_timestamp(reexported),
+        without_timestamp(exported_archive)
+    );
+
+    // Importing the same archive again changes nothing
+    let again = target
+        .post("/api/v1/import/archive")
+        .add_query_param("format", "jsonl")
+        .text(exported_lines)
+        .await
+        .json::<Value>();
+    assert_eq!(again["movies"]["skipped"], 2);
+    assert_eq!(again["movies"]["created"], 0);
+
+    let unknown = target
+        .post("/api/v1/import/archive")
+        .json(&json!({ "format": "something-else", "version": 1 }))
+        .await;
+    unknown.assert_status(StatusCode::BAD_REQUEST);
+
+    drop_database(source_pool, &source_name).await;
+    drop_database(target_pool, &target_name).await;
+}
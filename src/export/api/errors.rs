@@ -0,0 +1,58 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use thiserror::Error;
+use tracing::error;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct ExportApiErrorResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ExportApiError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("Internal error: {0}")]
+    InternalError(#[from] anyhow::Error),
+}
+
+impl ExportApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn user_message(&self) -> String {
+        match self {
+            Self::DatabaseError(_) => "A database error occurred".to_string(),
+            Self::InternalError(_) => "An internal error occurred".to_string(),
+        }
+    }
+
+    pub fn log_message(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl IntoResponse for ExportApiError {
+    fn into_response(self) -> Response {
+        let status_code = self.status_code();
+        let user_message = self.user_message();
+
+        error!(error = %self.log_message(), "{}", user_message);
+
+        let response = ExportApiErrorResponse {
+            message: user_message,
+        };
+
+        (status_code, Json(response)).into_response()
+    }
+}
@@ -0,0 +1,75 @@
+use crate::export::api::errors::{ExportApiError, ExportApiErrorResponse};
+use crate::export::api::models::{
+    Archive, ArchiveCounts, ArchiveFormat, ArchiveHeader, ExportParams,
+};
+use crate::export::api::service::ApiService;
+use crate::store::Store;
+
+use axum::Extension;
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use tracing::{info, instrument};
+use utoipa::OpenApi;
+
+/// Export the whole catalogue
+///
+/// Returns every movie, quote and interview as a versioned archive that can be
+/// posted back to `/import/archive`.
+#[utoipa::path(
+    get,
+    path = "/export",
+    params(ExportParams),
+    responses(
+        (status = 200, description = "Catalogue archive", body = Archive, content_type = "application/json"),
+        (status = 500, description = "Database server error", body = ExportApiErrorResponse)
+    ),
+    tag = "Export API"
+)]
+#[instrument(skip_all, fields(format = ?params.format))]
+pub async fn export_archive(
+    Extension(store): Extension<Store>,
+    Query(params): Query<ExportParams>,
+) -> Result<impl IntoResponse, ExportApiError> {
+    let service = ApiService::new(&store.connection);
+    let archive = service.export().await?;
+    let body = ApiService::render(&archive, params.format)?;
+
+    let counts = &archive.header.counts;
+    info!(
+        "exported {} movies, {} quotes, {} interviews",
+        counts.movies, counts.quotes, counts.interviews
+    );
+
+    let (content_type, extension) = match params.format {
+        ArchiveFormat::Json => ("application/json", "json"),
+        ArchiveFormat::Jsonl => ("application/x-ndjson", "jsonl"),
+    };
+    let disposition = format!(
+        "attachment; filename=\"tarantinors-{}.{}\"",
+        archive.header.exported_at.format("%Y%m%d%H%M%S"),
+        extension
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(export_archive),
+    components(schemas(
+        Archive,
+        ArchiveHeader,
+        ArchiveCounts,
+        ArchiveFormat,
+        ExportApiErrorResponse,
+    )),
+    modifiers()
+)]
+pub struct ExportApiDoc;
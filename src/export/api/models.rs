@@ -0,0 +1,168 @@
+use crate::interviews::api::models::CreateInterviewRequest;
+use crate::movies::api::models::{
+    CreateMovieAwardNominationRequest, CreateMovieAwardRequest, CreateMovieRequest, MovieResponse,
+};
+use crate::quotes::api::models::CreateQuoteRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Identifies a document as a catalogue archive.
+pub const ARCHIVE_FORMAT: &str = "tarantinors-archive";
+
+/// Bumped whenever the archive layout changes in a way older readers can't handle.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// A single JSON document
+    #[default]
+    Json,
+    /// A header line followed by one record per line
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    /// Archive layout, defaults to a single JSON document
+    #[serde(default)]
+    pub format: ArchiveFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveCounts {
+    pub movies: usize,
+    pub quotes: usize,
+    pub interviews: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveHeader {
+    /// Always `tarantinors-archive`
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub counts: ArchiveCounts,
+}
+
+impl ArchiveHeader {
+    /// Checks that a header read back from an archive is one this build understands.
+    pub fn check(&self) -> Result<(), String> {
+        if self.format != ARCHIVE_FORMAT {
+            return Err(format!(
+                "unknown archive format '{}', expected '{}'",
+                self.format, ARCHIVE_FORMAT
+            ));
+        }
+        if self.version > ARCHIVE_VERSION {
+            return Err(format!(
+                "archive version {} is newer than the supported version {}",
+                self.version, ARCHIVE_VERSION
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The whole catalogue, in the shape of the create requests so it can be imported back.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Archive {
+    #[serde(flatten)]
+    pub header: ArchiveHeader,
+    pub movies: Vec<CreateMovieRequest>,
+    pub quotes: Vec<CreateQuoteRequest>,
+    pub interviews: Vec<CreateInterviewRequest>,
+}
+
+impl Archive {
+    pub fn new(
+        movies: Vec<CreateMovieRequest>,
+        quotes: Vec<CreateQuoteRequest>,
+        interviews: Vec<CreateInterviewRequest>,
+    ) -> Self {
+        Self {
+            header: ArchiveHeader {
+                format: ARCHIVE_FORMAT.to_string(),
+                version: ARCHIVE_VERSION,
+                exported_at: Utc::now(),
+                counts: ArchiveCounts {
+                    movies: movies.len(),
+                    quotes: quotes.len(),
+                    interviews: interviews.len(),
+                },
+            },
+            movies,
+            quotes,
+            interviews,
+        }
+    }
+
+    /// One line per record after the header line, in the JSONL layout.
+    pub fn entries(&self) -> impl Iterator<Item = ArchiveEntry<'_>> {
+        self.movies
+            .iter()
+            .map(ArchiveEntry::Movie)
+            .chain(self.quotes.iter().map(ArchiveEntry::Quote))
+            .chain(self.interviews.iter().map(ArchiveEntry::Interview))
+    }
+}
+
+/// A record line of a JSONL archive.
+#[derive(Debug, Serialize)]
+#[serde(tag = "resource", content = "record", rename_all = "lowercase")]
+pub enum ArchiveEntry<'a> {
+    Movie(&'a CreateMovieRequest),
+    Quote(&'a CreateQuoteRequest),
+    Interview(&'a CreateInterviewRequest),
+}
+
+/// The people of a role by name, so exports of the same catalogue compare
+/// equal whatever order they were linked in.
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+}
+
+impl From<MovieResponse> for CreateMovieRequest {
+    fn from(movie: MovieResponse) -> Self {
+        Self {
+            title: movie.title,
+            release_year: movie.release_year,
+            genre: movie.genre,
+            plot: movie.plot,
+            runtime: movie.runtime,
+            rating: movie.rating,
+            release_date: movie.release_date,
+            image_url: movie.image_url,
+            youtube_id: movie.youtube_id,
+            production_details: movie.production_details,
+            budget: movie.budget,
+            directors: sorted(movie.directors),
+            producers: sorted(movie.producers),
+            actors: sorted(movie.actors),
+            writers: sorted(movie.writers),
+            awards: movie
+                .awards
+                .into_iter()
+                .map(|award| CreateMovieAwardRequest {
+                    name: award.name,
+                    category: award.category,
+                    year: award.year,
+                    recipient: award.recipient.unwrap_or_default(),
+                })
+                .collect(),
+            nominations: movie
+                .nominations
+                .into_iter()
+                .map(|nomination| CreateMovieAwardNominationRequest {
+                    name: nomination.name,
+                    category: nomination.category,
+                    year: nomination.year,
+                    nominee: nomination.nominee.unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
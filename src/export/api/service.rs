@@ -0,0 +1,78 @@
+use crate::export::api::errors::ExportApiError;
+use crate::export::api::models::{Archive, ArchiveFormat};
+use crate::interviews::api::models::CreateInterviewRequest;
+use crate::interviews::data::repository::InterviewRepository;
+use crate::movies::api::service::ApiService as MoviesApiService;
+use crate::quotes::api::models::CreateQuoteRequest;
+use crate::quotes::data::repository::QuoteRepository;
+
+use anyhow::anyhow;
+use sqlx::PgPool;
+
+pub struct ApiService {
+    movies_service: MoviesApiService,
+    quotes_repo: QuoteRepository,
+    interviews_repo: InterviewRepository,
+}
+
+impl ApiService {
+    pub fn new(pool: &PgPool) -> Self {
+        Self {
+            movies_service: MoviesApiService::new(pool),
+            quotes_repo: QuoteRepository::new(pool),
+            interviews_repo: InterviewRepository::new(pool),
+        }
+    }
+
+    /// Reads the whole catalogue, in a stable order.
+    pub async fn export(&self) -> Result<Archive, ExportApiError> {
+        let movies = self
+            .movies_service
+            .get_movies()
+            .await
+            .map_err(|e| anyhow!("failed to read movies: {}", e))?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        let quotes = self
+            .quotes_repo
+            .get_quotes()
+            .await?
+            .into_iter()
+            .map(|quote| CreateQuoteRequest { text: quote.text })
+            .collect();
+
+        let interviews = self
+            .interviews_repo
+            .get_interviews()
+            .await?
+            .into_iter()
+            .map(|interview| CreateInterviewRequest {
+                title: interview.title,
+                description: interview.description,
+                youtube_id: interview.youtube_id,
+            })
+            .collect();
+
+        Ok(Archive::new(movies, quotes, interviews))
+    }
+
+    pub fn render(archive: &Archive, format: ArchiveFormat) -> Result<String, ExportApiError> {
+        let body = match format {
+            ArchiveFormat::Json => {
+                serde_json::to_string_pretty(archive).map_err(anyhow::Error::from)?
+            }
+            ArchiveFormat::Jsonl => {
+                let mut lines =
+                    vec![serde_json::to_string(&archive.header).map_err(anyhow::Error::from)?];
+                for entry in archive.entries() {
+                    lines.push(serde_json::to_string(&entry).map_err(anyhow::Error::from)?);
+                }
+                lines.join("\n") + "\n"
+            }
+        };
+
+        Ok(body)
+    }
+}
@@ -0,0 +1,14 @@
+pub mod api;
+
+use axum::{Extension, Router, routing::get};
+
+use crate::store::Store;
+
+pub fn rest_api_router(export_store: &Store) -> Router {
+    Router::new()
+        .route("/export", get(api::handlers::export_archive))
+        .layer(Extension(export_store.clone()))
+}
+
+#[cfg(test)]
+mod __tests;
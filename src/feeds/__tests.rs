@@ -0,0 +1,128 @@
+use super::*;
+use crate::test_support::{create_database, drop_database, send};
+use axum::body::Body;
+use axum::http::Request;
+use rss::validation::Validate;
+
+/// A feed request to the `films.example` host.
+fn request(uri: &str, headers: &[(header::HeaderName, &str)]) -> Request<Body> {
+    let mut request = Request::builder()
+        .uri(uri)
+        .header(header::HOST, "films.example");
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    request.body(Body::empty()).unwrap()
+}
+
+#[test]
+fn feed_urls_name_the_feed_and_its_format() {
+    assert_eq!(
+        parse_feed("movies"),
+        Some((FeedKind::Movies, FeedFormat::Atom))
+    );
+    assert_eq!(
+        parse_feed("quotes.rss"),
+        Some((FeedKind::Quotes, FeedFormat::Rss))
+    );
+    assert_eq!(
+        parse_feed("all.atom"),
+        Some((FeedKind::All, FeedFormat::Atom))
+    );
+    assert_eq!(parse_feed("all.json"), None);
+    assert_eq!(parse_feed("people"), None);
+}
+
+#[tokio::test]
+async fn feeds_list_the_latest_additions_and_answer_conditional_gets() {
+    let name = "tarantinors_feeds_latest_additions";
+    let pool = create_database(name).await;
+    sqlx::query(
+        "INSERT INTO quote (text, created_at, updated_at)
+         VALUES ('Bingo.', '2026-01-01 10:00:00', '2026-01-01 10:00:00')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO interview (title, description, youtube_id, created_at, updated_at)
+         VALUES ('On Pulp Fiction', 'Talking dialogue', 'abc123',
+                 '2026-02-01 10:00:00', '2026-02-01 10:00:00')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let router = web_router(&Store {
+        connection: pool.clone(),
+    });
+
+    let response = send(&router, request("/feeds/all.rss", &[])).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.header(header::CONTENT_TYPE),
+        "application/rss+xml; charset=utf-8"
+    );
+    let channel = rss::Channel::read_from(response.body.as_bytes()).unwrap();
+    channel.validate().unwrap();
+    let links: Vec<_> = channel
+        .items()
+        .iter()
+        .filter_map(|item| item.link())
+        .collect();
+    assert_eq!(
+        links,
+        [
+            "https://www.youtube.com/watch?v=abc123",
+            "http://films.example/quotes#quote-1"
+        ]
+    );
+
+    let response = send(&router, request("/feeds/interviews", &[])).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.header(header::CONTENT_TYPE),
+        "application/atom+xml; charset=utf-8"
+    );
+    let feed = atom::Feed::read_from(response.body.as_bytes()).unwrap();
+    assert_eq!(feed.id(), "http://films.example/feeds/interviews.atom");
+    assert_eq!(feed.entries().len(), 1);
+    let entry = &feed.entries()[0];
+    assert_eq!(
+        entry.links()[0].href(),
+        "https://www.youtube.com/watch?v=abc123"
+    );
+    assert!(
+        entry
+            .content()
+            .and_then(|content| content.value())
+            .unwrap()
+            .contains("https://www.youtube.com/embed/abc123")
+    );
+    assert_eq!(
+        entry.published().unwrap().to_rfc3339(),
+        "2026-02-01T10:00:00+00:00"
+    );
+
+    let etag = response.header(header::ETAG).to_string();
+    let last_modified = response.header(header::LAST_MODIFIED).to_string();
+    assert_eq!(last_modified, "Sun, 01 Feb 2026 10:00:00 GMT");
+    let cached = |headers: &[(header::HeaderName, &str)]| {
+        send(&router, request("/feeds/interviews", headers))
+    };
+    let response = cached(&[(header::IF_NONE_MATCH, &etag)]).await;
+    assert_eq!(response.status, StatusCode::NOT_MODIFIED);
+    assert!(response.body.is_empty());
+    let response = cached(&[(header::IF_MODIFIED_SINCE, &last_modified)]).await;
+    assert_eq!(response.status, StatusCode::NOT_MODIFIED);
+    let response = cached(&[(header::IF_MODIFIED_SINCE, "Sat, 31 Jan 2026 10:00:00 GMT")]).await;
+    assert_eq!(response.status, StatusCode::OK);
+    // The other feed has another validator
+    let response = send(
+        &router,
+        request("/feeds/all", &[(header::IF_NONE_MATCH, &etag)]),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    drop_database(pool, name).await;
+}
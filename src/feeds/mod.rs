@@ -0,0 +1,481 @@
+//! Atom and RSS feeds of what was added to the catalogue, newest first, for
+//! feed readers and chat integrations. Each feed is served at
+//! `/feeds/{name}.atom` and `/feeds/{name}.rss`, the bare `/feeds/{name}`
+//! being Atom, where the name is `movies`, `quotes`, `interviews` or `all`.
+//! Responses carry an `ETag` and a `Last-Modified` date, so readers polling
+//! with `If-None-Match` or `If-Modified-Since` get a `304` until something
+//! changes.
+
+#[cfg(test)]
+mod __tests;
+
+use crate::interviews::data::{entities::Interview, repository::InterviewRepository};
+use crate::movies::data::{entities::Movie, repository::MovieRepository};
+use crate::quotes::data::{entities::Quote, repository::QuoteRepository};
+use crate::seo::{absolute_url, site_url};
+use crate::store::Store;
+use crate::web_error::WebError;
+use askama::Template;
+use atom_syndication as atom;
+use axum::{
+    Extension, Router,
+    extract::Path,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use rss::extension::atom::AtomExtension;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::cmp::Reverse;
+use std::time::SystemTime;
+
+/// Entries kept in each feed.
+pub const FEED_LENGTH: i64 = 50;
+
+const SITE_TITLE: &str = "Quentin Tarantino Films";
+
+/// How long readers may reuse a feed before asking again.
+const CACHE_CONTROL: &str = "public, max-age=300";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedKind {
+    Movies,
+    Quotes,
+    Interviews,
+    /// Movies, quotes and interviews together
+    All,
+}
+
+impl FeedKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Movies => "movies",
+            Self::Quotes => "quotes",
+            Self::Interviews => "interviews",
+            Self::All => "all",
+        }
+    }
+
+    fn title(&self) -> String {
+        let what = match self {
+            Self::Movies => "New movies",
+            Self::Quotes => "New quotes",
+            Self::Interviews => "New interviews",
+            Self::All => "What's new",
+        };
+        format!("{} - {}", what, SITE_TITLE)
+    }
+
+    /// The page of the site the feed follows.
+    fn page(&self) -> &'static str {
+        match self {
+            Self::Movies => "/movies",
+            Self::Quotes => "/quotes",
+            Self::Interviews => "/interviews",
+            Self::All => "/",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+impl FeedFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Atom => "atom",
+            Self::Rss => "rss",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Rss => "application/rss+xml; charset=utf-8",
+        }
+    }
+}
+
+/// Reads the last segment of a feed URL, such as `movies`, `quotes.rss` or
+/// `all.atom`.
+pub fn parse_feed(segment: &str) -> Option<(FeedKind, FeedFormat)> {
+    let (name, format) = match segment.rsplit_once('.') {
+        Some((name, "atom")) => (name, FeedFormat::Atom),
+        Some((name, "rss")) => (name, FeedFormat::Rss),
+        Some(_) => return None,
+        None => (segment, FeedFormat::Atom),
+    };
+    let kind = match name {
+        "movies" => FeedKind::Movies,
+        "quotes" => FeedKind::Quotes,
+        "interviews" => FeedKind::Interviews,
+        "all" => FeedKind::All,
+        _ => return None,
+    };
+
+    Some((kind, format))
+}
+
+fn youtube_watch_url(youtube_id: &str) -> String {
+    format!("https://www.youtube.com/watch?v={}", youtube_id)
+}
+
+#[derive(Template)]
+#[template(path = "partials/feeds/movie.html")]
+struct MovieContentTemplate<'a> {
+    movie: &'a Movie,
+    image_url: String,
+}
+
+#[derive(Template)]
+#[template(path = "partials/feeds/quote.html")]
+struct QuoteContentTemplate<'a> {
+    quote: &'a Quote,
+}
+
+#[derive(Template)]
+#[template(path = "partials/feeds/interview.html")]
+struct InterviewContentTemplate<'a> {
+    interview: &'a Interview,
+    watch_url: String,
+}
+
+/// One addition to the catalogue, as both feed formats show it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    /// Where the record lives on the site, such as `/movies/12` or
+    /// `/quotes#quote-3`; also what identifies the entry
+    pub path: String,
+    pub title: String,
+    pub summary: String,
+    /// HTML for feed readers
+    pub content: String,
+    /// Where the entry links to instead of the site, the YouTube video of an
+    /// interview
+    pub external_link: Option<String>,
+    /// `created_at` of the record
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl FeedEntry {
+    pub fn from_movie(movie: &Movie, site: &str) -> Result<Self, askama::Error> {
+        let content = MovieContentTemplate {
+            movie,
+            image_url: absolute_url(site, &movie.image_url),
+        }
+        .render()?;
+
+        Ok(Self {
+            path: format!("/movies/{}", movie.id),
+            title: format!("{} ({})", movie.title, movie.release_year),
+            summary: movie.plot.clone(),
+            content: content.trim().to_string(),
+            external_link: None,
+            published: movie.created_at.and_utc(),
+            updated: movie.updated_at.and_utc(),
+        })
+    }
+
+    pub fn from_quote(quote: &Quote) -> Result<Self, askama::Error> {
+        let content = QuoteContentTemplate { quote }.render()?;
+
+        Ok(Self {
+            path: format!("/quotes#quote-{}", quote.id),
+            title: quote.text.clone(),
+            summary: quote.text.clone(),
+            content: content.trim().to_string(),
+            external_link: None,
+            published: quote.created_at.and_utc(),
+            updated: quote.updated_at.and_utc(),
+        })
+    }
+
+    pub fn from_interview(interview: &Interview) -> Result<Self, askama::Error> {
+        let watch_url = youtube_watch_url(&interview.youtube_id);
+        let content = InterviewContentTemplate {
+            interview,
+            watch_url: watch_url.clone(),
+        }
+        .render()?;
+
+        Ok(Self {
+            path: format!("/interviews#interview-{}", interview.id),
+            title: interview.title.clone(),
+            summary: interview.description.clone(),
+            content: content.trim().to_string(),
+            external_link: Some(watch_url),
+            published: interview.created_at.and_utc(),
+            updated: interview.updated_at.and_utc(),
+        })
+    }
+}
+
+/// A feed ready to be written in either format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub kind: FeedKind,
+    /// Newest first
+    pub entries: Vec<FeedEntry>,
+    pub site: String,
+}
+
+impl Feed {
+    /// Reads the [`FEED_LENGTH`] latest additions of the feed.
+    pub async fn load(pool: &PgPool, kind: FeedKind, site: &str) -> Result<Self, WebError> {
+        let mut entries = Vec::new();
+        if matches!(kind, FeedKind::Movies | FeedKind::All) {
+            for movie in MovieRepository::new(pool)
+                .get_latest_movies(FEED_LENGTH)
+                .await?
+            {
+                entries.push(FeedEntry::from_movie(&movie, site)?);
+            }
+        }
+        if matches!(kind, FeedKind::Quotes | FeedKind::All) {
+            for quote in QuoteRepository::new(pool)
+                .get_latest_quotes(FEED_LENGTH)
+                .await?
+            {
+                entries.push(FeedEntry::from_quote(&quote)?);
+            }
+        }
+        if matches!(kind, FeedKind::Interviews | FeedKind::All) {
+            let repository = InterviewRepository::new(pool);
+            for interview in repository.get_latest_interviews(FEED_LENGTH).await? {
+                entries.push(FeedEntry::from_interview(&interview)?);
+            }
+        }
+        entries.sort_by_key(|entry| Reverse(entry.published));
+        entries.truncate(FEED_LENGTH as usize);
+
+        Ok(Self {
+            kind,
+            entries,
+            site: site.to_string(),
+        })
+    }
+
+    /// When the newest entry was added or edited; the Unix epoch for an empty
+    /// feed.
+    pub fn updated(&self) -> DateTime<Utc> {
+        self.entries
+            .iter()
+            .map(|entry| entry.updated.max(entry.published))
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+
+    fn url(&self, format: FeedFormat) -> String {
+        format!(
+            "{}/feeds/{}.{}",
+            self.site,
+            self.kind.name(),
+            format.extension()
+        )
+    }
+
+    fn entry_url(&self, entry: &FeedEntry) -> String {
+        format!("{}{}", self.site, entry.path)
+    }
+
+    pub fn render(&self, format: FeedFormat) -> Result<String, WebError> {
+        match format {
+            FeedFormat::Atom => self.atom(),
+            FeedFormat::Rss => self.rss(),
+        }
+    }
+
+    /// An Atom 1.0 document (RFC 4287).
+    pub fn atom(&self) -> Result<String, WebError> {
+        let link = |href: String, rel: &str, mime_type: &str| atom::Link {
+            href,
+            rel: rel.to_string(),
+            mime_type: Some(mime_type.to_string()),
+            ..Default::default()
+        };
+
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let page = self.entry_url(entry);
+                let mut links = vec![link(
+                    entry.external_link.clone().unwrap_or_else(|| page.clone()),
+                    "alternate",
+                    "text/html",
+                )];
+                if entry.external_link.is_some() {
+                    links.push(link(page.clone(), "related", "text/html"));
+                }
+
+                atom::Entry {
+                    title: atom::Text::plain(entry.title.clone()),
+                    id: page,
+                    updated: entry.updated.max(entry.published).fixed_offset(),
+                    published: Some(entry.published.fixed_offset()),
+                    links,
+                    summary: Some(atom::Text::plain(entry.summary.clone())),
+                    content: Some(atom::Content {
+                        value: Some(entry.content.clone()),
+                        content_type: Some("html".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let feed = atom::Feed {
+            title: atom::Text::plain(self.kind.title()),
+            id: self.url(FeedFormat::Atom),
+            updated: self.updated().fixed_offset(),
+            authors: vec![atom::Person {
+                name: SITE_TITLE.to_string(),
+                uri: Some(self.site.clone()),
+                ..Default::default()
+            }],
+            links: vec![
+                link(self.url(FeedFormat::Atom), "self", "application/atom+xml"),
+                link(
+                    format!("{}{}", self.site, self.kind.page()),
+                    "alternate",
+                    "text/html",
+                ),
+            ],
+            generator: Some(atom::Generator {
+                value: env!("CARGO_PKG_NAME").to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                ..Default::default()
+            }),
+            lang: Some("en".to_string()),
+            entries,
+            ..Default::default()
+        };
+
+        let xml = feed
+            .write_to(Vec::new())
+            .map_err(|e| WebError::InternalError(format!("Could not write the feed: {}", e)))?;
+        String::from_utf8(xml)
+            .map_err(|e| WebError::InternalError(format!("Could not write the feed: {}", e)))
+    }
+
+    /// An RSS 2.0 document, with the `atom:link` to itself that validators
+    /// ask for.
+    pub fn rss(&self) -> Result<String, WebError> {
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let page = self.entry_url(entry);
+                rss::Item {
+                    title: Some(entry.title.clone()),
+                    link: Some(entry.external_link.clone().unwrap_or_else(|| page.clone())),
+                    description: Some(entry.content.clone()),
+                    guid: Some(rss::Guid {
+                        value: page,
+                        permalink: true,
+                    }),
+                    pub_date: Some(entry.published.to_rfc2822()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let channel = rss::Channel {
+            title: self.kind.title(),
+            link: format!("{}{}", self.site, self.kind.page()),
+            description: format!("The latest additions to {}", SITE_TITLE),
+            language: Some("en".to_string()),
+            last_build_date: Some(self.updated().to_rfc2822()),
+            generator: Some(env!("CARGO_PKG_NAME").to_string()),
+            ttl: Some("5".to_string()),
+            atom_ext: Some(AtomExtension {
+                links: vec![atom::Link {
+                    href: self.url(FeedFormat::Rss),
+                    rel: "self".to_string(),
+                    mime_type: Some("application/rss+xml".to_string()),
+                    ..Default::default()
+                }],
+            }),
+            items,
+            ..Default::default()
+        };
+
+        let xml = channel
+            .write_to(Vec::new())
+            .map_err(|e| WebError::InternalError(format!("Could not write the feed: {}", e)))?;
+        String::from_utf8(xml)
+            .map_err(|e| WebError::InternalError(format!("Could not write the feed: {}", e)))
+    }
+}
+
+/// A strong validator for a feed body. SHA-256 keeps it the same across
+/// builds and instances, so readers keep their `304`s over a deploy.
+pub fn etag(body: &str) -> String {
+    format!("\"{:x}\"", Sha256::digest(body.as_bytes()))
+}
+
+/// Whether the reader's copy is still current. `If-None-Match` wins over
+/// `If-Modified-Since` when both are sent (RFC 9110, section 13.2.2).
+pub fn is_fresh(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH) {
+        return if_none_match.to_str().is_ok_and(|tags| {
+            tags.split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+    }
+
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok())
+        .is_some_and(|since| SystemTime::from(last_modified) <= since)
+}
+
+/// `GET /feeds/{feed}`: one of the feeds, or `304 Not Modified`.
+pub async fn feed(
+    Extension(store): Extension<Store>,
+    Path(segment): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, WebError> {
+    let (kind, format) = parse_feed(&segment)
+        .ok_or_else(|| WebError::PageNotFound(format!("/feeds/{}", segment)))?;
+
+    let feed = Feed::load(&store.connection, kind, &site_url(&headers)).await?;
+    let body = feed.render(format)?;
+    let etag = etag(&body);
+    // HTTP dates have whole seconds
+    let last_modified = DateTime::from_timestamp(feed.updated().timestamp(), 0).unwrap_or_default();
+    let validators = [
+        (header::ETAG, etag.clone()),
+        (
+            header::LAST_MODIFIED,
+            httpdate::fmt_http_date(SystemTime::from(last_modified)),
+        ),
+        (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+    ];
+
+    if is_fresh(&headers, &etag, last_modified) {
+        return Ok((StatusCode::NOT_MODIFIED, validators).into_response());
+    }
+
+    Ok((
+        validators,
+        [(header::CONTENT_TYPE, format.content_type())],
+        body,
+    )
+        .into_response())
+}
+
+pub fn web_router(store: &Store) -> Router {
+    Router::new()
+        .route("/feeds/{feed}", get(feed))
+        .layer(Extension(store.clone()))
+}
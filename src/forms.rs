@@ -0,0 +1,241 @@
+//! Pieces shared by the create, edit and delete screens of movies, quotes and
+//! interviews.
+
+use crate::htmx::impl_page;
+use crate::i18n::Locale;
+use askama::Template;
+use axum::extract::rejection::FormRejection;
+use axum::extract::{Form, FromRequest, Request};
+use axum::http::StatusCode;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use validator::ValidationErrors;
+
+/// A submitted `application/x-www-form-urlencoded` body. Kept as pairs so
+/// repeated fields, such as one `actors` input per cast row, keep their order.
+#[derive(Debug, Default)]
+pub struct FormData(pub Vec<(String, String)>);
+
+impl FormData {
+    /// The first value of `name`, trimmed, or an empty string.
+    pub fn value(&self, name: &str) -> String {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Every value of `name`, trimmed, blank rows included.
+    pub fn values(&self, name: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .collect()
+    }
+
+    /// Whether the form was sent with the submit button `name`.
+    pub fn pressed(&self, name: &str) -> bool {
+        self.0.iter().any(|(key, _)| key == name)
+    }
+}
+
+impl<S: Send + Sync> FromRequest<S> for FormData {
+    type Rejection = FormRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Form(pairs) = Form::<Vec<(String, String)>>::from_request(req, state).await?;
+        Ok(Self(pairs))
+    }
+}
+
+/// Messages to show next to the inputs they are about, keyed by field name.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FormErrors(BTreeMap<String, Vec<String>>);
+
+impl FormErrors {
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0
+            .entry(field.to_string())
+            .or_default()
+            .push(message.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+
+    /// The first message for `field`, shown under its input.
+    pub fn first(&self, field: &str) -> Option<&str> {
+        self.0
+            .get(field)
+            .and_then(|messages| messages.first())
+            .map(String::as_str)
+    }
+
+    /// Parses a number input, recording `message` when it is not one.
+    pub fn parse<T: FromStr + Default>(&mut self, field: &str, value: &str, message: &str) -> T {
+        value.parse().unwrap_or_else(|_| {
+            self.add(field, message);
+            T::default()
+        })
+    }
+
+    /// Adds the errors of a `validator` check, unless the field already has
+    /// one (a number that did not parse is not also "out of range").
+    pub fn merge(&mut self, errors: &ValidationErrors) {
+        for (field, field_errors) in errors.field_errors() {
+            if self.0.contains_key(field.as_ref()) {
+                continue;
+            }
+            for error in field_errors {
+                let message = error
+                    .message
+                    .as_ref()
+                    .map(|message| message.to_string())
+                    .unwrap_or_else(|| error.code.to_string());
+                self.add(&field, message);
+            }
+        }
+    }
+
+    pub fn into_result(self) -> Result<(), FormErrors> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// Turns a unique constraint violation, like `interview_youtube_id_key`,
+    /// into an error on the first of `fields` the constraint names.
+    pub fn from_unique_violation(error: &sqlx::Error, fields: &[&str]) -> Option<FormErrors> {
+        let sqlx::Error::Database(error) = error else {
+            return None;
+        };
+        if !error.is_unique_violation() {
+            return None;
+        }
+        let constraint = error.constraint().unwrap_or_default();
+        let field = fields
+            .iter()
+            .find(|field| constraint.contains(*field))
+            .or(fields.first())?;
+
+        let mut errors = FormErrors::default();
+        errors.add(field, "Already used by another record");
+        Some(errors)
+    }
+}
+
+/// Where a form is sent and how, for its classic or htmx flavour. Both live
+/// under `/{resource}` and differ only in method: classic forms always post,
+/// htmx forms use `post` to create and `put` to edit.
+#[derive(Debug, Clone)]
+pub struct FormTarget {
+    pub heading: String,
+    pub action: String,
+    /// `post` or `put`
+    pub method: &'static str,
+    /// Where "Cancel" goes back to
+    pub cancel: String,
+    pub htmx: bool,
+}
+
+impl FormTarget {
+    pub fn create(resource: &str, heading: impl Into<String>, htmx: bool) -> Self {
+        let base = format!("/{}", resource);
+        Self {
+            heading: heading.into(),
+            action: base.clone(),
+            method: "post",
+            cancel: base,
+            htmx,
+        }
+    }
+
+    /// `cancel` is relative to the resource, such as `/12` for a detail page
+    /// or an empty string for the list.
+    pub fn edit(
+        resource: &str,
+        id: i32,
+        heading: impl Into<String>,
+        cancel: &str,
+        htmx: bool,
+    ) -> Self {
+        let base = format!("/{}", resource);
+        Self {
+            heading: heading.into(),
+            action: format!("{}/{}", base, id),
+            method: if htmx { "put" } else { "post" },
+            cancel: format!("{}{}", base, cancel),
+            htmx,
+        }
+    }
+
+    /// Status of a form shown again with its errors. htmx only swaps
+    /// successful responses, so it gets a 200.
+    pub fn error_status(&self) -> StatusCode {
+        if self.htmx {
+            StatusCode::OK
+        } else {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+    }
+}
+
+/// The page asking to confirm a delete, in its classic and htmx flavours.
+#[derive(Template)]
+#[template(path = "pages/confirm_delete.html", blocks = ["title", "main"])]
+pub struct ConfirmDeleteTemplate {
+    pub locale: Locale,
+    /// "movie", "quote" or "interview"
+    pub kind: &'static str,
+    pub label: String,
+    /// Where the delete is sent
+    pub action: String,
+    /// Where "Cancel" goes back to
+    pub cancel: String,
+    pub htmx: bool,
+}
+
+impl_page!(ConfirmDeleteTemplate);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Example {
+        #[validate(range(min = 1, message = "Runtime must be positive"))]
+        runtime: i32,
+        #[validate(length(min = 1, message = "Title cannot be empty"))]
+        title: String,
+    }
+
+    #[test]
+    fn parse_errors_win_over_validation_errors() {
+        let form = FormData(vec![
+            ("runtime".to_string(), " two hours ".to_string()),
+            ("title".to_string(), " ".to_string()),
+        ]);
+
+        let mut errors = FormErrors::default();
+        let example = Example {
+            runtime: errors.parse(
+                "runtime",
+                &form.value("runtime"),
+                "Runtime must be a number",
+            ),
+            title: form.value("title"),
+        };
+        errors.merge(&example.validate().unwrap_err());
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.first("runtime"), Some("Runtime must be a number"));
+        assert_eq!(errors.first("title"), Some("Title cannot be empty"));
+        assert_eq!(errors.first("plot"), None);
+    }
+}
@@ -0,0 +1,177 @@
+//! One route per web page for browsers and htmx alike. A navigation gets the
+//! whole layout; an htmx request, boosted links and forms included, only gets
+//! what goes in `<main>`.
+
+use askama::Template;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue, Uri, header};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use serde_json::json;
+use std::convert::Infallible;
+
+/// The htmx request headers of a web request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmxRequest {
+    /// `HX-Request`, sent with every htmx request
+    pub request: bool,
+    /// `HX-History-Restore-Request`, sent when the history cache misses
+    pub history_restore: bool,
+    /// `HX-Target`, the id of the element the response is swapped into
+    pub target: Option<String>,
+}
+
+impl HtmxRequest {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let value = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        Self {
+            request: value("HX-Request") == Some("true"),
+            history_restore: value("HX-History-Restore-Request") == Some("true"),
+            target: value("HX-Target").map(str::to_string),
+        }
+    }
+
+    /// Whether the response is swapped into the current page. A history
+    /// restore replaces the whole page, so it gets the layout too.
+    pub fn is_partial(&self) -> bool {
+        self.request && !self.history_restore
+    }
+
+    /// Whether htmx swaps the response into the element with this id.
+    pub fn targets(&self, id: &str) -> bool {
+        self.is_partial() && self.target.as_deref() == Some(id)
+    }
+
+    /// Renders a page whole, or only its title and `main` block for a swap.
+    pub fn render<T: Page>(&self, page: &T) -> askama::Result<Response> {
+        let html = if self.is_partial() {
+            format!(
+                "<title>{}</title>\n{}",
+                page.render_title()?.trim(),
+                page.render_main()?
+            )
+        } else {
+            page.render()?
+        };
+
+        Ok(fragment(html))
+    }
+
+    /// Where to go after a change was saved. Browsers follow a redirect;
+    /// htmx loads the page into `<main>` and pushes it to the history.
+    pub fn redirect(&self, url: &str) -> Response {
+        if self.request {
+            let location = json!({ "path": url, "target": "main" }).to_string();
+            match HeaderValue::from_str(&location) {
+                Ok(location) => ([("HX-Location", location)], "").into_response(),
+                Err(_) => Redirect::to(url).into_response(),
+            }
+        } else {
+            Redirect::to(url).into_response()
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for HtmxRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+/// HTML that depends on the htmx headers, so caches keep both versions.
+pub fn fragment(html: String) -> Response {
+    ([(header::VARY, "HX-Request, HX-Target")], Html(html)).into_response()
+}
+
+/// A page template extending `base.html` whose blocks can be rendered alone.
+/// Implemented with [`impl_page`].
+pub trait Page: Template {
+    fn render_title(&self) -> askama::Result<String>;
+    fn render_main(&self) -> askama::Result<String>;
+}
+
+/// Implements [`Page`] for templates declared with
+/// `blocks = ["title", "main"]`.
+macro_rules! impl_page {
+    ($($template:ty),+ $(,)?) => {
+        $(
+            impl $crate::htmx::Page for $template {
+                fn render_title(&self) -> askama::Result<String> {
+                    askama::Template::render(&self.as_title())
+                }
+
+                fn render_main(&self) -> askama::Result<String> {
+                    askama::Template::render(&self.as_main())
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_page;
+
+/// The pages used to live under a separate `/htmx` tree; old links and
+/// bookmarks move over for good.
+///
+/// The target always starts with a single `/`: `/htmx//evil.example` must not
+/// become the protocol-relative `//evil.example`, which browsers read as
+/// another host, and neither may `/\evil.example`.
+pub async fn legacy_redirect(uri: Uri) -> Redirect {
+    let path = uri.path().strip_prefix("/htmx").unwrap_or(uri.path());
+    let path = path.trim_start_matches(['/', '\\']);
+    let location = match uri.query() {
+        Some(query) => format!("/{}?{}", path, query),
+        None => format!("/{}", path),
+    };
+
+    Redirect::permanent(&location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_restores_get_the_whole_page() {
+        let mut headers = HeaderMap::new();
+        headers.insert("HX-Request", HeaderValue::from_static("true"));
+        headers.insert("HX-Target", HeaderValue::from_static("movie-grid"));
+        let htmx = HtmxRequest::from_headers(&headers);
+        assert!(htmx.is_partial());
+        assert!(htmx.targets("movie-grid"));
+
+        headers.insert(
+            "HX-History-Restore-Request",
+            HeaderValue::from_static("true"),
+        );
+        let htmx = HtmxRequest::from_headers(&headers);
+        assert!(!htmx.is_partial());
+        assert!(!htmx.targets("movie-grid"));
+        assert!(!HtmxRequest::default().is_partial());
+    }
+
+    async fn location(uri: &'static str) -> String {
+        let response = legacy_redirect(Uri::from_static(uri)).await.into_response();
+        response.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn legacy_redirect_stays_on_this_site() {
+        assert_eq!(
+            location("/htmx/movies?genre=Western").await,
+            "/movies?genre=Western"
+        );
+        assert_eq!(location("/htmx").await, "/");
+        assert_eq!(location("/htmx/").await, "/");
+        assert_eq!(location("/htmx//evil.example/x").await, "/evil.example/x");
+        assert_eq!(
+            location("/htmx///evil.example?a=b").await,
+            "/evil.example?a=b"
+        );
+        assert_eq!(location("/htmx/%5Cevil.example").await, "/%5Cevil.example");
+    }
+}
@@ -0,0 +1,447 @@
+//! Translations of the web pages. The texts live in Fluent files under
+//! `locales/{code}/main.ftl`, built into the binary. The locale of a request
+//! comes from a `/{code}` prefix on the URL, then the `lang` cookie, then
+//! `Accept-Language`, and English when none of them names one we have.
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderValue, Uri, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Redirect, Response};
+use chrono::{Datelike, NaiveDate};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_langneg::{NegotiationStrategy, negotiate_languages, parse_accepted_languages};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use unic_langid::LanguageIdentifier;
+
+/// The cookie remembering the chosen locale.
+pub const COOKIE: &str = "lang";
+
+/// A year, in seconds; how long the chosen locale is remembered.
+const COOKIE_MAX_AGE: u32 = 31_536_000;
+
+/// The Fluent files, by locale code.
+pub const CATALOGUES: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en/main.ftl")),
+    ("fr", include_str!("../locales/fr/main.ftl")),
+    ("es", include_str!("../locales/es/main.ftl")),
+];
+
+static BUNDLES: LazyLock<HashMap<Locale, FluentBundle<FluentResource>>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let source = CATALOGUES
+                .iter()
+                .find(|(code, _)| *code == locale.code())
+                .map(|(_, source)| source.to_string())
+                .unwrap_or_default();
+            let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+                tracing::error!(locale = locale.code(), ?errors, "Broken Fluent file");
+                resource
+            });
+
+            let mut bundle = FluentBundle::new_concurrent(vec![locale.langid()]);
+            // The texts go into HTML, where bidi isolation marks only get in the way
+            bundle.set_use_isolating(false);
+            if let Err(errors) = bundle.add_resource(resource) {
+                tracing::error!(locale = locale.code(), ?errors, "Duplicate Fluent messages");
+            }
+            (locale, bundle)
+        })
+        .collect()
+});
+
+/// A language the pages are translated into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+    Es,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Fr, Locale::Es];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+            Self::Es => "es",
+        }
+    }
+
+    /// The name of the language in that language, for the switcher.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::Fr => "Français",
+            Self::Es => "Español",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(code))
+    }
+
+    /// `path` under the prefix of the locale, such as `/fr/movies`.
+    pub fn prefixed(&self, path: &str) -> String {
+        match path {
+            "/" => format!("/{}", self.code()),
+            path => format!("/{}{}", self.code(), path),
+        }
+    }
+
+    fn langid(&self) -> LanguageIdentifier {
+        self.code().parse().unwrap_or_default()
+    }
+
+    /// The best match for an `Accept-Language` header.
+    pub fn negotiate(accept_language: &str) -> Self {
+        let requested = parse_accepted_languages(accept_language);
+        let available: Vec<LanguageIdentifier> =
+            Self::ALL.iter().map(|locale| locale.langid()).collect();
+        let default = Self::default().langid();
+        negotiate_languages(
+            &requested,
+            &available,
+            Some(&default),
+            NegotiationStrategy::Lookup,
+        )
+        .first()
+        .and_then(|langid| Self::from_code(langid.language.as_str()))
+        .unwrap_or_default()
+    }
+
+    /// The locale of the `lang` cookie, or else of `Accept-Language`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let from_cookie = headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == COOKIE)
+            .and_then(|(_, code)| Self::from_code(code));
+
+        from_cookie.unwrap_or_else(|| {
+            headers
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .map(Self::negotiate)
+                .unwrap_or_default()
+        })
+    }
+
+    /// The text of a message, or its id when no catalogue has it.
+    pub fn t(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    /// The text of a message with one argument.
+    pub fn t_with(&self, id: &str, name: &str, value: impl Display) -> String {
+        let mut args = FluentArgs::new();
+        args.set(name, value.to_string());
+        self.format(id, Some(&args))
+    }
+
+    /// The text of a message with several arguments.
+    pub fn t_args(&self, id: &str, args: &[(&str, String)]) -> String {
+        let args: FluentArgs = args
+            .iter()
+            .map(|(name, value)| (*name, value.clone()))
+            .collect();
+        self.format(id, Some(&args))
+    }
+
+    pub fn message(&self, message: &Message) -> String {
+        match message {
+            Message::Id(id, args) if args.is_empty() => self.t(id),
+            Message::Id(id, args) => self.t_args(id, args),
+            Message::Text(text) => text.clone(),
+        }
+    }
+
+    /// A date written out the way the language does, such as "October 10,
+    /// 1994" or "10 octobre 1994".
+    pub fn date(&self, date: &NaiveDate) -> String {
+        let month = self.t(&format!("month-{}", date.month()));
+        self.t_args(
+            "date-long",
+            &[
+                ("day", date.day().to_string()),
+                ("month", month),
+                ("year", date.year().to_string()),
+            ],
+        )
+    }
+
+    /// Falls back to English, then to the id, so a missing translation
+    /// never breaks a page.
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for locale in [*self, Self::default()] {
+            let Some(bundle) = BUNDLES.get(&locale) else {
+                continue;
+            };
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+
+            let mut errors = vec![];
+            let text = bundle.format_pattern(pattern, args, &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(id, locale = locale.code(), ?errors, "Fluent message failed");
+            }
+            return text.into_owned();
+        }
+
+        tracing::warn!(id, locale = self.code(), "Missing Fluent message");
+        id.to_string()
+    }
+}
+
+/// The locale taken off the front of the URL by [`locale_prefix`].
+#[derive(Debug, Clone, Copy)]
+struct PrefixLocale(Locale);
+
+impl<S: Send + Sync> FromRequestParts<S> for Locale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let locale = match parts.extensions.get::<PrefixLocale>() {
+            Some(PrefixLocale(locale)) => *locale,
+            None => Self::from_headers(&parts.headers),
+        };
+        Ok(locale)
+    }
+}
+
+/// A text to show, translated when it comes from the catalogue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A catalogue message and its arguments
+    Id(&'static str, Vec<(&'static str, String)>),
+    /// Shown as it is, such as the reason a request was rejected
+    Text(String),
+}
+
+impl Message {
+    pub fn id(id: &'static str) -> Self {
+        Self::Id(id, vec![])
+    }
+}
+
+/// Splits a `/{code}` prefix off a path: `/fr/movies` is `/movies` in French.
+fn split_prefix(path: &str) -> Option<(Locale, &str)> {
+    let rest = path.strip_prefix('/')?;
+    let (code, rest) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, ""),
+    };
+    let locale = Locale::from_code(code).filter(|locale| locale.code() == code)?;
+    Some((locale, if rest.is_empty() { "/" } else { rest }))
+}
+
+fn locale_cookie(locale: Locale) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Lax",
+        COOKIE,
+        locale.code(),
+        COOKIE_MAX_AGE
+    )
+}
+
+/// Middleware around the whole app, so it runs before routing. A locale
+/// prefix is taken off the path and remembered in the cookie, so the links
+/// on the page, which have no prefix, stay in that language.
+pub async fn locale_prefix(mut request: Request, next: Next) -> Response {
+    let prefixed = split_prefix(request.uri().path()).map(|(locale, path)| {
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path.to_string(),
+        };
+        (locale, path_and_query)
+    });
+
+    let Some((locale, path_and_query)) = prefixed else {
+        return vary_by_locale(next.run(request).await);
+    };
+    let mut parts = request.uri().clone().into_parts();
+    parts.path_and_query = path_and_query.parse().ok();
+    if let Ok(uri) = Uri::from_parts(parts) {
+        *request.uri_mut() = uri;
+    }
+    request.extensions_mut().insert(PrefixLocale(locale));
+
+    let mut response = vary_by_locale(next.run(request).await);
+    if let Ok(cookie) = HeaderValue::from_str(&locale_cookie(locale)) {
+        response.headers_mut().append(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+/// Pages differ by locale, so caches keep one copy per language.
+fn vary_by_locale(mut response: Response) -> Response {
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if is_html {
+        response.headers_mut().append(
+            header::VARY,
+            HeaderValue::from_static("Accept-Language, Cookie"),
+        );
+    }
+    response
+}
+
+/// `GET /lang/{code}`: the language switcher. Remembers the locale and goes
+/// back to the page it was picked on.
+pub async fn switch_locale(
+    axum::extract::Path(code): axum::extract::Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(locale) = Locale::from_code(&code) else {
+        return Redirect::to("/").into_response();
+    };
+
+    let back = headers
+        .get(header::REFERER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|referer| referer.parse::<Uri>().ok())
+        .filter(|referer| {
+            let host = headers
+                .get(header::HOST)
+                .and_then(|host| host.to_str().ok());
+            referer.authority().map(|authority| authority.as_str()) == host
+        })
+        .map(|referer| {
+            let path = split_prefix(referer.path())
+                .map(|(_, path)| path)
+                .unwrap_or(referer.path());
+            match referer.query() {
+                Some(query) => format!("{}?{}", path, query),
+                None => path.to_string(),
+            }
+        })
+        .unwrap_or_else(|| "/".to_string());
+
+    (
+        [(header::SET_COOKIE, locale_cookie(locale))],
+        Redirect::to(&back),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    /// The ids of the messages of a Fluent file.
+    fn message_ids(source: &str) -> BTreeSet<String> {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once('='))
+            .map(|(id, _)| id.trim().to_string())
+            .collect()
+    }
+
+    /// The message ids written out in the templates and the Rust sources,
+    /// such as `locale.t("nav-movies")` or `Message::id("error-database")`.
+    fn used_ids(dir: &Path, ids: &mut BTreeSet<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                used_ids(&path, ids);
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap_or_default();
+            for call in [
+                ".t(\"",
+                ".t_with(\"",
+                ".t_args(\"",
+                "Message::id(\"",
+                "Message::Id(\"",
+            ] {
+                for (_, rest) in source
+                    .match_indices(call)
+                    .map(|(i, _)| source.split_at(i + call.len()))
+                {
+                    if let Some((id, _)) = rest.split_once('"') {
+                        ids.insert(id.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_locale_translates_every_message() {
+        let english = message_ids(CATALOGUES[0].1);
+        for (code, source) in &CATALOGUES[1..] {
+            let ids = message_ids(source);
+            let untranslated: Vec<_> = english.difference(&ids).collect();
+            let unknown: Vec<_> = ids.difference(&english).collect();
+            assert!(
+                untranslated.is_empty(),
+                "untranslated in {}: {:?}",
+                code,
+                untranslated
+            );
+            assert!(
+                unknown.is_empty(),
+                "not in English but in {}: {:?}",
+                code,
+                unknown
+            );
+        }
+
+        let mut used = BTreeSet::new();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        used_ids(&root.join("templates"), &mut used);
+        used_ids(&root.join("src"), &mut used);
+        let missing: Vec<_> = used.difference(&english).collect();
+        assert!(
+            missing.is_empty(),
+            "used but not in the catalogue: {:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn locale_comes_from_prefix_cookie_or_accept_language() {
+        assert_eq!(split_prefix("/fr/movies"), Some((Locale::Fr, "/movies")));
+        assert_eq!(split_prefix("/es"), Some((Locale::Es, "/")));
+        assert_eq!(split_prefix("/movies"), None);
+        assert_eq!(split_prefix("/FR/movies"), None);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(Locale::from_headers(&headers), Locale::En);
+        headers.insert(
+            header::ACCEPT_LANGUAGE,
+            HeaderValue::from_static("de-DE,es-MX;q=0.8,en;q=0.5"),
+        );
+        assert_eq!(Locale::from_headers(&headers), Locale::Es);
+        headers.insert(header::COOKIE, HeaderValue::from_static("a=b; lang=fr"));
+        assert_eq!(Locale::from_headers(&headers), Locale::Fr);
+    }
+
+    #[test]
+    fn dates_follow_the_locale() {
+        let date = NaiveDate::from_ymd_opt(1994, 10, 14).unwrap();
+        assert_eq!(Locale::En.date(&date), "October 14, 1994");
+        assert_eq!(Locale::Fr.date(&date), "14 octobre 1994");
+        assert_eq!(Locale::Es.date(&date), "14 de octubre de 1994");
+    }
+}
@@ -0,0 +1,245 @@
+use crate::import::api::errors::ImportApiError;
+use crate::import::api::models::ImportFormat;
+use crate::import::api::parsers::{ParsedRecord, parse_records};
+use crate::movies::api::models::CreateMovieRequest;
+use crate::quotes::api::models::CreateQuoteRequest;
+use crate::store::Store;
+use crate::test_support::{create_database, drop_database};
+use axum::Router;
+use axum_test::TestServer;
+use serde_json::{Value, json};
+use sqlx::postgres::PgPool;
+
+fn texts(records: &[ParsedRecord<CreateQuoteRequest>]) -> Vec<Result<&str, &str>> {
+    records
+        .iter()
+        .map(|record| match record {
+            Ok(quote) => Ok(quote.text.as_str()),
+            Err(reason) => Err(reason.as_str()),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_json_array() {
+    let body = r#"[{"text": "Say what again"}, {"txt": "typo"}, {"text": "Zed's dead"}]"#;
+
+    let records = parse_records::<CreateQuoteRequest>(body, ImportFormat::Json).unwrap();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].as_ref().unwrap().text, "Say what again");
+    assert!(
+        records[1]
+            .as_ref()
+            .unwrap_err()
+            .contains("missing field `text`")
+    );
+    assert_eq!(records[2].as_ref().unwrap().text, "Zed's dead");
+}
+
+#[test]
+fn test_parse_json_rejects_a_payload_that_is_not_an_array() {
+    for body in [r#"{"text": "Say what again"}"#, r#"[{"text": "#] {
+        let error = parse_records::<CreateQuoteRequest>(body, ImportFormat::Json).unwrap_err();
+        assert!(matches!(error, ImportApiError::InvalidPayload(_)), "{body}");
+    }
+}
+
+#[test]
+fn test_parse_jsonl_reports_malformed_lines_by_number() {
+    let body = "{\"text\": \"Say what again\"}\n\n{\"text\": \n{\"txt\": \"typo\"}\n{\"text\": \"Zed's dead\"}\n";
+
+    let records = parse_records::<CreateQuoteRequest>(body, ImportFormat::Jsonl).unwrap();
+    let texts = texts(&records);
+
+    // The blank line is skipped but still counted
+    assert_eq!(texts.len(), 4);
+    assert_eq!(texts[0], Ok("Say what again"));
+    assert!(
+        texts[1].unwrap_err().starts_with("line 3: "),
+        "{:?}",
+        texts[1]
+    );
+    assert!(
+        texts[2]
+            .unwrap_err()
+            .starts_with("line 4: missing field `text`")
+    );
+    assert_eq!(texts[3], Ok("Zed's dead"));
+}
+
+#[test]
+fn test_parse_csv_quotes() {
+    let body = "text\nSay what again\n\"Zed's dead, baby\"\n";
+
+    let records = parse_records::<CreateQuoteRequest>(body, ImportFormat::Csv).unwrap();
+
+    assert_eq!(
+        texts(&records),
+        vec![Ok("Say what again"), Ok("Zed's dead, baby")]
+    );
+}
+
+#[test]
+fn test_parse_csv_movies() {
+    let header = "title,release_year,genre,plot,runtime,rating,release_date,image_url,youtube_id,\
+        production_details,budget,directors,producers,actors,writers,awards,nominations";
+    let body = format!(
+        "{header}\n\
+        Jackie Brown,1997,Crime,A heist,154,7.5,1997-12-25,https://example.com/jb.jpg,B5Ib3ToYmPA,\
+        A Band Apart,$12 million,Quentin Tarantino,Lawrence Bender,Pam Grier; Robert Forster,\
+        Quentin Tarantino,\"[{{\"\"name\"\":\"\"Academy Awards\"\",\"\"category\"\":\"\"Best Actor\"\",\"\"year\"\":1998,\"\"recipient\"\":\"\"Robert Forster\"\"}}]\",\n\
+        Death Proof,soon,Horror,A car,113,7.0,2007-04-06,https://example.com/dp.jpg,x,y,z,a,b,c,d,,\n\
+        Kill Bill,2003,Action,A bride,111,8.2,2003-10-10,https://example.com/kb.jpg,x,y,z,a,b,c,d,[oops,\n"
+    );
+
+    let records = parse_records::<CreateMovieRequest>(&body, ImportFormat::Csv).unwrap();
+
+    assert_eq!(records.len(), 3);
+    let movie = records[0].as_ref().unwrap();
+    assert_eq!(movie.title, "Jackie Brown");
+    assert_eq!(movie.actors, vec!["Pam Grier", "Robert Forster"]);
+    assert_eq!(movie.awards.len(), 1);
+    assert_eq!(movie.awards[0].recipient, "Robert Forster");
+    assert!(movie.nominations.is_empty());
+
+    let bad_year = records[1].as_ref().unwrap_err();
+    assert!(bad_year.contains("line: 3"), "{bad_year}");
+    assert!(
+        records[2]
+            .as_ref()
+            .unwrap_err()
+            .starts_with("invalid awards column")
+    );
+}
+
+#[test]
+fn test_parse_csv_rejects_a_payload_without_header() {
+    let error = parse_records::<CreateQuoteRequest>("", ImportFormat::Csv).unwrap_err();
+    assert!(matches!(error, ImportApiError::InvalidPayload(_)));
+}
+
+fn test_server(pool: &PgPool) -> TestServer {
+    let store = Store {
+        connection: pool.clone(),
+    };
+    let app = Router::new().nest("/api/v1", super::rest_api_router(&store));
+    TestServer::new(app).unwrap()
+}
+
+/// A quote that passes validation but is too long for its column, so it only
+/// fails once the insert reaches the database.
+fn overlong_quote() -> String {
+    "a".repeat(200)
+}
+
+async fn quote_count(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM quote")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_all_or_nothing_import_rolls_back_on_one_bad_record() {
+    let name = format!("tarantinors_import_all_{}", uuid::Uuid::new_v4().simple());
+    let pool = create_database(&name).await;
+    let server = test_server(&pool);
+
+    let response = server
+        .post("/api/v1/import")
+        .add_query_param("resource", "quotes")
+        .json(&json!([
+            { "text": "Say what again" },
+            { "text": overlong_quote() },
+            { "text": "Zed's dead" }
+        ]))
+        .await;
+
+    response.assert_status_ok();
+    let report = response.json::<Value>();
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["created"], 0);
+    assert_eq!(report["failed"], 1);
+    assert_eq!(report["records"][0]["status"], "skipped");
+    assert_eq!(
+        report["records"][0]["reason"],
+        "not imported: another record failed"
+    );
+    assert_eq!(report["records"][1]["status"], "failed");
+    assert_eq!(report["records"][2]["status"], "skipped");
+    assert_eq!(quote_count(&pool).await, 0);
+
+    // A record failing validation stops the import before any insert
+    let response = server
+        .post("/api/v1/import")
+        .add_query_param("resource", "quotes")
+        .json(&json!([{ "text": "Say what again" }, { "text": "" }]))
+        .await;
+
+    let report = response.json::<Value>();
+    assert_eq!(report["committed"], false);
+    assert_eq!(
+        report["records"][1]["field_errors"]["text"][0],
+        "Text cannot be empty"
+    );
+    assert_eq!(quote_count(&pool).await, 0);
+
+    drop_database(pool, &name).await;
+}
+
+#[tokio::test]
+async fn test_best_effort_import_reports_each_failure() {
+    let name = format!("tarantinors_import_best_{}", uuid::Uuid::new_v4().simple());
+    let pool = create_database(&name).await;
+    let server = test_server(&pool);
+
+    let body = [
+        json!({ "text": "Say what again" }).to_string(),
+        "{\"text\": ".to_string(),
+        json!({ "text": overlong_quote() }).to_string(),
+        json!({ "text": "" }).to_string(),
+        json!({ "text": "Say what again" }).to_string(),
+        json!({ "text": "Zed's dead" }).to_string(),
+    ]
+    .join("\n");
+
+    let response = server
+        .post("/api/v1/import")
+        .add_query_param("resource", "quotes")
+        .add_query_param("mode", "best_effort")
+        .text(body)
+        .content_type("application/x-ndjson")
+        .await;
+
+    response.assert_status_ok();
+    let report = response.json::<Value>();
+    assert_eq!(report["committed"], true);
+    assert_eq!(report["created"], 2);
+    assert_eq!(report["skipped"], 1);
+    assert_eq!(report["failed"], 3);
+
+    let records = report["records"].as_array().unwrap();
+    let statuses: Vec<&str> = records
+        .iter()
+        .map(|record| record["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            "created", "failed", "failed", "failed", "skipped", "created"
+        ]
+    );
+    assert!(
+        records[1]["reason"]
+            .as_str()
+            .unwrap()
+            .starts_with("line 2: ")
+    );
+    assert_eq!(records[2]["key"], overlong_quote());
+    assert_eq!(records[3]["reason"], "Record validation failed");
+    assert_eq!(records[4]["reason"], "duplicate of record 1");
+    assert_eq!(quote_count(&pool).await, 2);
+
+    drop_database(pool, &name).await;
+}
@@ -0,0 +1,58 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use thiserror::Error;
+use tracing::error;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportApiErrorResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ImportApiError {
+    #[error("Invalid import payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl ImportApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidPayload(_) => StatusCode::BAD_REQUEST,
+            Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn user_message(&self) -> String {
+        match self {
+            Self::InvalidPayload(reason) => format!("Invalid import payload: {}", reason),
+            Self::DatabaseError(_) => "A database error occurred".to_string(),
+        }
+    }
+
+    pub fn log_message(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl IntoResponse for ImportApiError {
+    fn into_response(self) -> Response {
+        let status_code = self.status_code();
+        let user_message = self.user_message();
+
+        error!(error = %self.log_message(), "{}", user_message);
+
+        let response = ImportApiErrorResponse {
+            message: user_message,
+        };
+
+        (status_code, Json(response)).into_response()
+    }
+}
@@ -0,0 +1,136 @@
+use crate::export::api::models::ArchiveFormat;
+use crate::import::api::errors::{ImportApiError, ImportApiErrorResponse};
+use crate::import::api::models::{
+    ArchiveImportParams, ArchiveImportReport, ImportFormat, ImportMode, ImportParams,
+    ImportRecordReport, ImportRecordStatus, ImportReport, ImportResource,
+};
+use crate::import::api::service::ApiService;
+use crate::store::Store;
+
+use axum::Extension;
+use axum::Json;
+use axum::extract::Query;
+use axum::http::{HeaderMap, header};
+use tracing::{Span, info, instrument};
+use utoipa::OpenApi;
+
+/// Bulk import movies, quotes or interviews
+///
+/// Accepts a JSON array, JSON lines or CSV. The format is taken from the `format`
+/// parameter, or else from the `Content-Type` header.
+#[utoipa::path(
+    post,
+    path = "/import",
+    params(ImportParams),
+    request_body(
+        description = "Records as a JSON array, JSON lines or CSV",
+        content(
+            (String = "application/json"),
+            (String = "application/x-ndjson"),
+            (String = "text/csv")
+        )
+    ),
+    responses(
+        (status = 200, description = "Per record import report", body = ImportReport),
+        (status = 400, description = "Payload could not be read", body = ImportApiErrorResponse),
+        (status = 500, description = "Database server error", body = ImportApiErrorResponse)
+    ),
+    tag = "Import API"
+)]
+#[instrument(skip_all, fields(resource = ?params.resource, mode = ?params.mode, created, failed))]
+pub async fn import_records(
+    Extension(store): Extension<Store>,
+    Query(params): Query<ImportParams>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<ImportReport>, ImportApiError> {
+    let format = match params.format {
+        Some(format) => format,
+        None => headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(ImportFormat::from_content_type)
+            .unwrap_or(ImportFormat::Json),
+    };
+
+    let service = ApiService::new(&store.connection);
+    let report = service
+        .import(params.resource, format, params.mode, &body)
+        .await?;
+
+    Span::current().record("created", report.created);
+    Span::current().record("failed", report.failed);
+    info!(
+        "imported {:?}: {} created, {} skipped, {} failed",
+        report.resource, report.created, report.skipped, report.failed
+    );
+    Ok(Json(report))
+}
+
+/// Import an export archive
+///
+/// Accepts the JSON or JSON lines archive produced by `/export`. Records that
+/// already exist are skipped, so importing the same archive twice is harmless.
+#[utoipa::path(
+    post,
+    path = "/import/archive",
+    params(ArchiveImportParams),
+    request_body(
+        description = "Archive as returned by the export endpoint",
+        content(
+            (String = "application/json"),
+            (String = "application/x-ndjson")
+        )
+    ),
+    responses(
+        (status = 200, description = "Per section import report", body = ArchiveImportReport),
+        (status = 400, description = "Archive could not be read", body = ImportApiErrorResponse),
+        (status = 500, description = "Database server error", body = ImportApiErrorResponse)
+    ),
+    tag = "Import API"
+)]
+#[instrument(skip_all, fields(mode = ?params.mode))]
+pub async fn import_archive(
+    Extension(store): Extension<Store>,
+    Query(params): Query<ArchiveImportParams>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<ArchiveImportReport>, ImportApiError> {
+    let format = match params.format {
+        Some(format) => format,
+        None => match headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(ImportFormat::from_content_type)
+        {
+            Some(ImportFormat::Jsonl) => ArchiveFormat::Jsonl,
+            _ => ArchiveFormat::Json,
+        },
+    };
+
+    let service = ApiService::new(&store.connection);
+    let report = service.import_archive(format, params.mode, &body).await?;
+
+    info!(
+        "imported archive: {} movies, {} quotes, {} interviews created",
+        report.movies.created, report.quotes.created, report.interviews.created
+    );
+    Ok(Json(report))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(import_records, import_archive),
+    components(schemas(
+        ImportReport,
+        ArchiveImportReport,
+        ImportRecordReport,
+        ImportRecordStatus,
+        ImportResource,
+        ImportFormat,
+        ImportMode,
+        ImportApiErrorResponse,
+    )),
+    modifiers()
+)]
+pub struct ImportApiDoc;
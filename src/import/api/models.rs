@@ -0,0 +1,260 @@
+use crate::export::api::models::ArchiveFormat;
+use crate::movies::api::models::{
+    CreateMovieAwardNominationRequest, CreateMovieAwardRequest, CreateMovieRequest,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportResource {
+    Movies,
+    Quotes,
+    Interviews,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// A JSON array of records
+    Json,
+    /// One JSON record per line
+    Jsonl,
+    /// A CSV file with a header row
+    Csv,
+}
+
+impl ImportFormat {
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime {
+            "application/json" => Some(Self::Json),
+            "application/x-ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(Self::Jsonl)
+            }
+            "text/csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Import every record or none of them
+    #[default]
+    AllOrNothing,
+    /// Import the valid records and report the rest
+    BestEffort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportParams {
+    /// Resource the records belong to
+    pub resource: ImportResource,
+    /// Payload format, defaults to the request `Content-Type`
+    pub format: Option<ImportFormat>,
+    /// How failed records affect the rest of the import
+    #[serde(default)]
+    pub mode: ImportMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ArchiveImportParams {
+    /// Archive layout, defaults to the request `Content-Type`
+    pub format: Option<ArchiveFormat>,
+    /// How failed records affect the rest of the import
+    #[serde(default)]
+    pub mode: ImportMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportRecordStatus {
+    Created,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRecordReport {
+    /// 1-based position of the record in the payload
+    pub record: usize,
+    /// Natural key of the record (title or quote text), when it could be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub status: ImportRecordStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_errors: Option<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    pub resource: ImportResource,
+    pub mode: ImportMode,
+    /// Whether the created records were committed to the database
+    pub committed: bool,
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub records: Vec<ImportRecordReport>,
+}
+
+impl ImportReport {
+    pub fn new(
+        resource: ImportResource,
+        mode: ImportMode,
+        committed: bool,
+        records: Vec<ImportRecordReport>,
+    ) -> Self {
+        let count = |status| records.iter().filter(|r| r.status == status).count();
+
+        Self {
+            resource,
+            mode,
+            committed,
+            created: count(ImportRecordStatus::Created),
+            skipped: count(ImportRecordStatus::Skipped),
+            failed: count(ImportRecordStatus::Failed),
+            records,
+        }
+    }
+}
+
+/// Outcome of importing an export archive, one report per section.
+///
+/// In `all_or_nothing` mode the three sections share one transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveImportReport {
+    pub mode: ImportMode,
+    /// Whether the created records were committed to the database
+    pub committed: bool,
+    pub movies: ImportReport,
+    pub quotes: ImportReport,
+    pub interviews: ImportReport,
+}
+
+/// Flat CSV row of a movie.
+///
+/// People columns hold `;` separated names, `awards` and `nominations` hold
+/// JSON arrays shaped like the JSON request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MovieCsvRow {
+    pub title: String,
+    pub release_year: i32,
+    pub genre: String,
+    pub plot: String,
+    pub runtime: i32,
+    pub rating: f32,
+    pub release_date: NaiveDate,
+    pub image_url: String,
+    pub youtube_id: String,
+    pub production_details: String,
+    pub budget: String,
+    pub directors: String,
+    pub producers: String,
+    pub actors: String,
+    pub writers: String,
+    #[serde(default)]
+    pub awards: String,
+    #[serde(default)]
+    pub nominations: String,
+}
+
+impl TryFrom<MovieCsvRow> for CreateMovieRequest {
+    type Error = String;
+
+    fn try_from(row: MovieCsvRow) -> Result<Self, Self::Error> {
+        let names = |column: &str| -> Vec<String> {
+            column
+                .split(';')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        let awards: Vec<CreateMovieAwardRequest> =
+            parse_json_column(&row.awards).map_err(|e| format!("invalid awards column: {}", e))?;
+        let nominations: Vec<CreateMovieAwardNominationRequest> =
+            parse_json_column(&row.nominations)
+                .map_err(|e| format!("invalid nominations column: {}", e))?;
+
+        Ok(CreateMovieRequest {
+            directors: names(&row.directors),
+            producers: names(&row.producers),
+            actors: names(&row.actors),
+            writers: names(&row.writers),
+            title: row.title,
+            release_year: row.release_year,
+            genre: row.genre,
+            plot: row.plot,
+            runtime: row.runtime,
+            rating: row.rating,
+            release_date: row.release_date,
+            image_url: row.image_url,
+            youtube_id: row.youtube_id,
+            production_details: row.production_details,
+            budget: row.budget,
+            awards,
+            nominations,
+        })
+    }
+}
+
+fn parse_json_column<T: serde::de::DeserializeOwned>(
+    column: &str,
+) -> Result<Vec<T>, serde_json::Error> {
+    if column.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(column)
+}
+
+impl ImportRecordReport {
+    pub fn created(record: usize, key: String, id: i32) -> Self {
+        Self {
+            record,
+            key: Some(key),
+            status: ImportRecordStatus::Created,
+            id: Some(id),
+            reason: None,
+            field_errors: None,
+        }
+    }
+
+    pub fn skipped(record: usize, key: String, reason: impl Into<String>) -> Self {
+        Self {
+            record,
+            key: Some(key),
+            status: ImportRecordStatus::Skipped,
+            id: None,
+            reason: Some(reason.into()),
+            field_errors: None,
+        }
+    }
+
+    pub fn failed(
+        record: usize,
+        key: Option<String>,
+        reason: impl Into<String>,
+        field_errors: Option<HashMap<String, Vec<String>>>,
+    ) -> Self {
+        Self {
+            record,
+            key,
+            status: ImportRecordStatus::Failed,
+            id: None,
+            reason: Some(reason.into()),
+            field_errors,
+        }
+    }
+}
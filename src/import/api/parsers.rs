@@ -0,0 +1,204 @@
+use crate::export::api::models::{ArchiveFormat, ArchiveHeader};
+use crate::import::api::errors::ImportApiError;
+use crate::import::api::models::{ImportFormat, MovieCsvRow};
+use crate::interviews::api::models::CreateInterviewRequest;
+use crate::movies::api::models::CreateMovieRequest;
+use crate::quotes::api::models::CreateQuoteRequest;
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+/// A create request that can be read from an import payload.
+pub trait ImportRecord: DeserializeOwned + Validate {
+    /// Shape of one CSV row of this record
+    type CsvRow: DeserializeOwned;
+
+    fn from_csv_row(row: Self::CsvRow) -> Result<Self, String>;
+
+    /// Natural key used to detect records that already exist
+    fn key(&self) -> &str;
+}
+
+impl ImportRecord for CreateMovieRequest {
+    type CsvRow = MovieCsvRow;
+
+    fn from_csv_row(row: Self::CsvRow) -> Result<Self, String> {
+        row.try_into()
+    }
+
+    fn key(&self) -> &str {
+        &self.title
+    }
+}
+
+impl ImportRecord for CreateQuoteRequest {
+    type CsvRow = Self;
+
+    fn from_csv_row(row: Self::CsvRow) -> Result<Self, String> {
+        Ok(row)
+    }
+
+    fn key(&self) -> &str {
+        &self.text
+    }
+}
+
+impl ImportRecord for CreateInterviewRequest {
+    type CsvRow = Self;
+
+    fn from_csv_row(row: Self::CsvRow) -> Result<Self, String> {
+        Ok(row)
+    }
+
+    fn key(&self) -> &str {
+        &self.title
+    }
+}
+
+/// A record read from the payload, or why it could not be read.
+pub type ParsedRecord<T> = Result<T, String>;
+
+/// Splits an import payload into records.
+///
+/// A payload that cannot be split at all is an error; a single record that
+/// cannot be deserialized is returned as `Err` so it shows up in the report.
+pub fn parse_records<T: ImportRecord>(
+    body: &str,
+    format: ImportFormat,
+) -> Result<Vec<ParsedRecord<T>>, ImportApiError> {
+    match format {
+        ImportFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_str(body).map_err(|e| {
+                ImportApiError::InvalidPayload(format!("expected a JSON array of records: {}", e))
+            })?;
+
+            Ok(values
+                .into_iter()
+                .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                .collect())
+        }
+        ImportFormat::Jsonl => Ok(body
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| format!("line {}: {}", index + 1, e))
+            })
+            .collect()),
+        ImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::Headers)
+                .from_reader(body.as_bytes());
+
+            let headers = reader.headers().map_err(|e| {
+                ImportApiError::InvalidPayload(format!("invalid CSV header row: {}", e))
+            })?;
+            if headers.is_empty() {
+                return Err(ImportApiError::InvalidPayload(
+                    "CSV payload has no header row".to_string(),
+                ));
+            }
+
+            Ok(reader
+                .deserialize::<T::CsvRow>()
+                .map(|row| row.map_err(|e| e.to_string()).and_then(T::from_csv_row))
+                .collect())
+        }
+    }
+}
+
+/// The records of an export archive, split by resource.
+pub struct ParsedArchive {
+    pub movies: Vec<ParsedRecord<CreateMovieRequest>>,
+    pub quotes: Vec<ParsedRecord<CreateQuoteRequest>>,
+    pub interviews: Vec<ParsedRecord<CreateInterviewRequest>>,
+}
+
+/// Reads an archive written by the export endpoint.
+///
+/// The header must name a known format and version; records are read like
+/// any other import payload.
+pub fn parse_archive(body: &str, format: ArchiveFormat) -> Result<ParsedArchive, ImportApiError> {
+    let mut archive = ParsedArchive {
+        movies: Vec::new(),
+        quotes: Vec::new(),
+        interviews: Vec::new(),
+    };
+
+    match format {
+        ArchiveFormat::Json => {
+            let mut document: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(body).map_err(|e| {
+                    ImportApiError::InvalidPayload(format!("expected a JSON archive: {}", e))
+                })?;
+
+            let header: ArchiveHeader =
+                serde_json::from_value(serde_json::Value::Object(document.clone()))
+                    .map_err(|e| invalid_header(&e.to_string()))?;
+            header.check().map_err(|e| invalid_header(&e))?;
+
+            archive.movies = parse_section(document.remove("movies"), "movies")?;
+            archive.quotes = parse_section(document.remove("quotes"), "quotes")?;
+            archive.interviews = parse_section(document.remove("interviews"), "interviews")?;
+        }
+        ArchiveFormat::Jsonl => {
+            let mut lines = body
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty());
+
+            let (_, first) = lines
+                .next()
+                .ok_or_else(|| invalid_header("archive is empty"))?;
+            let header: ArchiveHeader =
+                serde_json::from_str(first).map_err(|e| invalid_header(&e.to_string()))?;
+            header.check().map_err(|e| invalid_header(&e))?;
+
+            for (index, line) in lines {
+                let number = index + 1;
+                let mut entry: serde_json::Value = serde_json::from_str(line).map_err(|e| {
+                    ImportApiError::InvalidPayload(format!("line {}: {}", number, e))
+                })?;
+                let record = entry["record"].take();
+
+                match entry["resource"].as_str() {
+                    Some("movie") => archive.movies.push(parse_entry(record, number)),
+                    Some("quote") => archive.quotes.push(parse_entry(record, number)),
+                    Some("interview") => archive.interviews.push(parse_entry(record, number)),
+                    other => {
+                        return Err(ImportApiError::InvalidPayload(format!(
+                            "line {}: unknown resource {:?}",
+                            number, other
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(archive)
+}
+
+fn invalid_header(reason: &str) -> ImportApiError {
+    ImportApiError::InvalidPayload(format!("invalid archive header: {}", reason))
+}
+
+fn parse_section<T: ImportRecord>(
+    section: Option<serde_json::Value>,
+    name: &str,
+) -> Result<Vec<ParsedRecord<T>>, ImportApiError> {
+    match section {
+        None => Ok(Vec::new()),
+        Some(serde_json::Value::Array(values)) => Ok(values
+            .into_iter()
+            .map(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+            .collect()),
+        Some(_) => Err(ImportApiError::InvalidPayload(format!(
+            "archive section '{}' must be an array",
+            name
+        ))),
+    }
+}
+
+fn parse_entry<T: ImportRecord>(record: serde_json::Value, line: usize) -> ParsedRecord<T> {
+    serde_json::from_value(record).map_err(|e| format!("line {}: {}", line, e))
+}
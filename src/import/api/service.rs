@@ -0,0 +1,475 @@
+use crate::export::api::models::ArchiveFormat;
+use crate::import::api::errors::ImportApiError;
+use crate::import::api::models::{
+    ArchiveImportReport, ImportFormat, ImportMode, ImportRecordReport, ImportRecordStatus,
+    ImportReport, ImportResource,
+};
+use crate::import::api::parsers::{ImportRecord, ParsedRecord, parse_archive, parse_records};
+use crate::interviews::api::models::CreateInterviewRequest;
+use crate::interviews::api::service::ApiService as InterviewsApiService;
+use crate::interviews::data::repository::InterviewRepository;
+use crate::movies::api::models::CreateMovieRequest;
+use crate::movies::api::service::ApiService as MoviesApiService;
+use crate::movies::data::repository::MovieRepository;
+use crate::quotes::api::models::CreateQuoteRequest;
+use crate::quotes::api::service::ApiService as QuotesApiService;
+use crate::quotes::data::repository::QuoteRepository;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use validator::{Validate, ValidationErrors};
+
+const ABORTED_REASON: &str = "not imported: another record failed";
+
+/// Looks up and inserts one kind of record on behalf of the import.
+trait Importer {
+    type Record: ImportRecord;
+
+    async fn exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        key: &str,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn insert(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        record: Self::Record,
+    ) -> Result<i32, String>;
+}
+
+struct MovieImporter {
+    repo: MovieRepository,
+    service: MoviesApiService,
+}
+
+impl MovieImporter {
+    fn new(pool: &PgPool) -> Self {
+        Self {
+            repo: MovieRepository::new(pool),
+            service: MoviesApiService::new(pool),
+        }
+    }
+}
+
+impl Importer for MovieImporter {
+    type Record = CreateMovieRequest;
+
+    async fn exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        key: &str,
+    ) -> Result<bool, sqlx::Error> {
+        self.repo.movie_title_exists(tx, key).await
+    }
+
+    async fn insert(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        record: Self::Record,
+    ) -> Result<i32, String> {
+        self.service
+            .insert_movie(tx, record)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+struct QuoteImporter {
+    repo: QuoteRepository,
+    service: QuotesApiService,
+}
+
+impl QuoteImporter {
+    fn new(pool: &PgPool) -> Self {
+        Self {
+            repo: QuoteRepository::new(pool),
+            service: QuotesApiService::new(pool),
+        }
+    }
+}
+
+impl Importer for QuoteImporter {
+    type Record = CreateQuoteRequest;
+
+    async fn exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        key: &str,
+    ) -> Result<bool, sqlx::Error> {
+        self.repo.quote_text_exists(tx, key).await
+    }
+
+    async fn insert(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        record: Self::Record,
+    ) -> Result<i32, String> {
+        self.service
+            .insert_quote(tx, record)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+struct InterviewImporter {
+    repo: InterviewRepository,
+    service: InterviewsApiService,
+}
+
+impl InterviewImporter {
+    fn new(pool: &PgPool) -> Self {
+        Self {
+            repo: InterviewRepository::new(pool),
+            service: InterviewsApiService::new(pool),
+        }
+    }
+}
+
+impl Importer for InterviewImporter {
+    type Record = CreateInterviewRequest;
+
+    async fn exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        key: &str,
+    ) -> Result<bool, sqlx::Error> {
+        self.repo.interview_title_exists(tx, key).await
+    }
+
+    async fn insert(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        record: Self::Record,
+    ) -> Result<i32, String> {
+        self.service
+            .insert_interview(tx, record)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+pub struct ApiService {
+    pool: PgPool,
+}
+
+impl ApiService {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn import(
+        &self,
+        resource: ImportResource,
+        format: ImportFormat,
+        mode: ImportMode,
+        body: &str,
+    ) -> Result<ImportReport, ImportApiError> {
+        let (committed, records) = match resource {
+            ImportResource::Movies => {
+                let importer = MovieImporter::new(&self.pool);
+                self.import_records(&importer, parse_records(body, format)?, mode)
+                    .await?
+            }
+            ImportResource::Quotes => {
+                let importer = QuoteImporter::new(&self.pool);
+                self.import_records(&importer, parse_records(body, format)?, mode)
+                    .await?
+            }
+            ImportResource::Interviews => {
+                let importer = InterviewImporter::new(&self.pool);
+                self.import_records(&importer, parse_records(body, format)?, mode)
+                    .await?
+            }
+        };
+
+        Ok(ImportReport::new(resource, mode, committed, records))
+    }
+
+    /// Imports every section of an export archive.
+    ///
+    /// In `all_or_nothing` mode one failed record in any section rolls back
+    /// the whole archive.
+    pub async fn import_archive(
+        &self,
+        format: ArchiveFormat,
+        mode: ImportMode,
+        body: &str,
+    ) -> Result<ArchiveImportReport, ImportApiError> {
+        let mut tx = self.pool.begin().await?;
+
+        if mode == ImportMode::AllOrNothing {
+            let report = self.import_archive_in(&mut tx, format, body).await?;
+            if report.committed {
+                tx.commit().await?;
+            } else {
+                tx.rollback().await?;
+            }
+            return Ok(report);
+        }
+
+        let archive = parse_archive(body, format)?;
+
+        let movie_importer = MovieImporter::new(&self.pool);
+        let quote_importer = QuoteImporter::new(&self.pool);
+        let interview_importer = InterviewImporter::new(&self.pool);
+
+        let mut movies = prepare_records(&movie_importer, &mut tx, archive.movies).await?;
+        let mut quotes = prepare_records(&quote_importer, &mut tx, archive.quotes).await?;
+        let mut interviews =
+            prepare_records(&interview_importer, &mut tx, archive.interviews).await?;
+        tx.commit().await?;
+
+        insert_each(&movie_importer, &self.pool, &mut movies).await?;
+        insert_each(&quote_importer, &self.pool, &mut quotes).await?;
+        insert_each(&interview_importer, &self.pool, &mut interviews).await?;
+
+        Ok(archive_report(mode, true, movies, quotes, interviews))
+    }
+
+    /// Imports an archive all-or-nothing inside the caller's transaction.
+    ///
+    /// Nothing is committed here: the caller commits `tx` when the report is
+    /// `committed` and must roll it back otherwise.
+    pub async fn import_archive_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        format: ArchiveFormat,
+        body: &str,
+    ) -> Result<ArchiveImportReport, ImportApiError> {
+        let archive = parse_archive(body, format)?;
+
+        let movie_importer = MovieImporter::new(&self.pool);
+        let quote_importer = QuoteImporter::new(&self.pool);
+        let interview_importer = InterviewImporter::new(&self.pool);
+
+        let mut movies = prepare_records(&movie_importer, tx, archive.movies).await?;
+        let mut quotes = prepare_records(&quote_importer, tx, archive.quotes).await?;
+        let mut interviews = prepare_records(&interview_importer, tx, archive.interviews).await?;
+
+        let committed = !movies.has_failures()
+            && !quotes.has_failures()
+            && !interviews.has_failures()
+            && insert_in_transaction(&movie_importer, tx, &mut movies).await?
+            && insert_in_transaction(&quote_importer, tx, &mut quotes).await?
+            && insert_in_transaction(&interview_importer, tx, &mut interviews).await?;
+
+        if !committed {
+            movies.abort();
+            quotes.abort();
+            interviews.abort();
+        }
+
+        Ok(archive_report(
+            ImportMode::AllOrNothing,
+            committed,
+            movies,
+            quotes,
+            interviews,
+        ))
+    }
+
+    async fn import_records<I: Importer>(
+        &self,
+        importer: &I,
+        records: Vec<ParsedRecord<I::Record>>,
+        mode: ImportMode,
+    ) -> Result<(bool, Vec<ImportRecordReport>), ImportApiError> {
+        let mut tx = self.pool.begin().await?;
+        let mut prepared = prepare_records(importer, &mut tx, records).await?;
+
+        let committed = match mode {
+            ImportMode::AllOrNothing => {
+                if !prepared.has_failures()
+                    && insert_in_transaction(importer, &mut tx, &mut prepared).await?
+                {
+                    tx.commit().await?;
+                    true
+                } else {
+                    tx.rollback().await?;
+                    prepared.abort();
+                    false
+                }
+            }
+            ImportMode::BestEffort => {
+                tx.commit().await?;
+                insert_each(importer, &self.pool, &mut prepared).await?;
+                true
+            }
+        };
+
+        Ok((committed, prepared.reports))
+    }
+}
+
+/// Records that passed validation, waiting to be inserted.
+///
+/// Every pending record already has a report slot, marked as skipped until
+/// its insert succeeds or fails.
+struct PreparedRecords<T> {
+    reports: Vec<ImportRecordReport>,
+    pending: Vec<(usize, T)>,
+}
+
+impl<T> PreparedRecords<T> {
+    fn has_failures(&self) -> bool {
+        self.reports
+            .iter()
+            .any(|report| report.status == ImportRecordStatus::Failed)
+    }
+
+    /// Reports records created in a rolled back transaction as not imported.
+    fn abort(&mut self) {
+        for report in self
+            .reports
+            .iter_mut()
+            .filter(|report| report.status == ImportRecordStatus::Created)
+        {
+            let key = report.key.take().unwrap_or_default();
+            *report = ImportRecordReport::skipped(report.record, key, ABORTED_REASON);
+        }
+    }
+}
+
+/// Parses, validates and drops records that are duplicated or already exist
+/// as seen from `tx`.
+async fn prepare_records<I: Importer>(
+    importer: &I,
+    tx: &mut Transaction<'_, Postgres>,
+    records: Vec<ParsedRecord<I::Record>>,
+) -> Result<PreparedRecords<I::Record>, ImportApiError> {
+    let mut reports = Vec::with_capacity(records.len());
+    let mut pending = Vec::new();
+    let mut seen_keys: HashMap<String, usize> = HashMap::new();
+
+    for (index, parsed) in records.into_iter().enumerate() {
+        let number = index + 1;
+
+        let record = match parsed {
+            Ok(record) => record,
+            Err(reason) => {
+                reports.push(ImportRecordReport::failed(number, None, reason, None));
+                continue;
+            }
+        };
+
+        let key = record.key().to_string();
+        if let Err(errors) = record.validate() {
+            reports.push(ImportRecordReport::failed(
+                number,
+                Some(key),
+                "Record validation failed",
+                Some(extract_field_errors(&errors)),
+            ));
+            continue;
+        }
+
+        if let Some(first) = seen_keys.get(&key) {
+            let reason = format!("duplicate of record {}", first);
+            reports.push(ImportRecordReport::skipped(number, key, reason));
+            continue;
+        }
+        seen_keys.insert(key.clone(), number);
+
+        if importer.exists(tx, &key).await? {
+            reports.push(ImportRecordReport::skipped(number, key, "already exists"));
+            continue;
+        }
+
+        reports.push(ImportRecordReport::skipped(number, key, ABORTED_REASON));
+        pending.push((reports.len() - 1, record));
+    }
+
+    Ok(PreparedRecords { reports, pending })
+}
+
+fn archive_report(
+    mode: ImportMode,
+    committed: bool,
+    movies: PreparedRecords<CreateMovieRequest>,
+    quotes: PreparedRecords<CreateQuoteRequest>,
+    interviews: PreparedRecords<CreateInterviewRequest>,
+) -> ArchiveImportReport {
+    ArchiveImportReport {
+        mode,
+        committed,
+        movies: ImportReport::new(ImportResource::Movies, mode, committed, movies.reports),
+        quotes: ImportReport::new(ImportResource::Quotes, mode, committed, quotes.reports),
+        interviews: ImportReport::new(
+            ImportResource::Interviews,
+            mode,
+            committed,
+            interviews.reports,
+        ),
+    }
+}
+
+/// Inserts every pending record in `tx`, stopping at the first failure.
+///
+/// Returns `false` when a record failed; the caller must then roll back.
+async fn insert_in_transaction<I: Importer>(
+    importer: &I,
+    tx: &mut Transaction<'_, Postgres>,
+    prepared: &mut PreparedRecords<I::Record>,
+) -> Result<bool, ImportApiError> {
+    for (slot, record) in std::mem::take(&mut prepared.pending) {
+        let report = &mut prepared.reports[slot];
+        let key = report.key.take().unwrap_or_default();
+
+        match importer.insert(tx, record).await {
+            Ok(id) => *report = ImportRecordReport::created(report.record, key, id),
+            Err(reason) => {
+                *report = ImportRecordReport::failed(report.record, Some(key), reason, None);
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+/// Inserts every pending record in its own transaction.
+async fn insert_each<I: Importer>(
+    importer: &I,
+    pool: &PgPool,
+    prepared: &mut PreparedRecords<I::Record>,
+) -> Result<(), ImportApiError> {
+    for (slot, record) in std::mem::take(&mut prepared.pending) {
+        let report = &mut prepared.reports[slot];
+        let key = report.key.take().unwrap_or_default();
+
+        let mut tx = pool.begin().await?;
+        match importer.insert(&mut tx, record).await {
+            Ok(id) => {
+                tx.commit().await?;
+                *report = ImportRecordReport::created(report.record, key, id);
+            }
+            Err(reason) => {
+                tx.rollback().await?;
+                *report = ImportRecordReport::failed(report.record, Some(key), reason, None);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn extract_field_errors(errors: &ValidationErrors) -> HashMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .iter()
+        .map(|(field, field_errors)| {
+            let messages = field_errors
+                .iter()
+                .map(|error| {
+                    error
+                        .message
+                        .as_ref()
+                        .map(|cow| cow.to_string())
+                        .unwrap_or_else(|| "Invalid value".to_string())
+                })
+                .collect();
+            (field.to_string(), messages)
+        })
+        .collect()
+}
@@ -0,0 +1,19 @@
+pub mod api;
+
+#[cfg(test)]
+mod __tests;
+
+use axum::{Extension, Router, extract::DefaultBodyLimit, routing::post};
+
+use crate::store::Store;
+
+/// Catalogue imports are larger than the default 2MB request body limit.
+const IMPORT_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+pub fn rest_api_router(import_store: &Store) -> Router {
+    Router::new()
+        .route("/import", post(api::handlers::import_records))
+        .route("/import/archive", post(api::handlers::import_archive))
+        .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT))
+        .layer(Extension(import_store.clone()))
+}
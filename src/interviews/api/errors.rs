@@ -29,6 +29,12 @@ pub enum InterviewsApiError {
     #[error("interview not found")]
     NotFound(i32),
 
+    #[error("interview title taken")]
+    TitleTaken(String),
+
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
     #[error("Validation failed")]
     Validation(#[from] ValidationErrors),
 
@@ -43,6 +49,8 @@ impl InterviewsApiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::TitleTaken(_) => StatusCode::CONFLICT,
+            Self::InvalidQuery(_) => StatusCode::BAD_REQUEST,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
             Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -52,6 +60,8 @@ impl InterviewsApiError {
     pub fn user_message(&self) -> String {
         match self {
             Self::NotFound(id) => format!("Interview with ID {} not found", id),
+            Self::TitleTaken(title) => format!("Another interview is titled '{}'", title),
+            Self::InvalidQuery(reason) => reason.clone(),
             Self::Validation(_) => "Request validation failed".to_string(),
             Self::DatabaseError(_) => "A database error occurred".to_string(),
             Self::InternalError(_) => "An internal error occurred".to_string(),
@@ -61,6 +71,8 @@ impl InterviewsApiError {
     pub fn validation_details(&self) -> Option<ValidationDetails> {
         match self {
             Self::NotFound(id) => None,
+            Self::TitleTaken(_) => None,
+            Self::InvalidQuery(_) => None,
             Self::DatabaseError(_) => None,
             Self::InternalError(_) => None,
             Self::Validation(errors) => {
@@ -1,9 +1,9 @@
 use crate::interviews::{
     api::{
         errors::{InterviewApiErrorResponse, InterviewsApiError},
-        extractors::ValidatedJson,
+        extractors::{ValidatedJson, ValidatedQuery},
         models::{
-            CreateInterviewRequest, InterviewListResponse, InterviewMessage, InterviewResponse,
+            CreateInterviewRequest, InterviewListParams, InterviewListResponse, InterviewMessage, InterviewResponse,
         },
         service::ApiService,
     },
@@ -15,7 +15,7 @@ use axum::Json;
 use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use tracing::{error, info, instrument};
+use tracing::{Span, error, info, instrument};
 use utoipa::OpenApi;
 use crate::store::Store;
 
@@ -26,24 +26,30 @@ use crate::store::Store;
     path = "/interviews",
     request_body = CreateInterviewRequest,
     responses(
-        (status = 201, description = "Interview Created", body = CreateInterviewRequest),
+        (status = 201, description = "Interview Created", body = InterviewMessage),
         (status = 400, description = "Request Validation Error", body = InterviewApiErrorResponse),
         (status = 500, description = "Internal server error", body = InterviewApiErrorResponse)
     ),
     tag = "Interviews API"
 )]
-#[instrument]
+#[instrument(skip_all)]
 pub async fn add_new_interview(
     Extension(store): Extension<Store>,
     ValidatedJson(new_interview): ValidatedJson<CreateInterviewRequest>,
 ) -> Result<impl IntoResponse, InterviewsApiError> {
     let service = ApiService::new(&store.connection);
     let interview_title = new_interview.title.clone();
-    let _ = service.create_interview(new_interview).await?;
+    let id = service.create_interview(new_interview).await?;
 
     let message = format!("Interview '{}' added ", interview_title);
     info!(%message);
-    Ok((StatusCode::CREATED, Json(InterviewMessage { message })))
+    Ok((
+        StatusCode::CREATED,
+        Json(InterviewMessage {
+            message,
+            id: Some(id),
+        }),
+    ))
 }
 
 /// Get interview by ID
@@ -57,7 +63,7 @@ pub async fn add_new_interview(
     ),
     tag = "Interviews API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(interview_id = interview_id))]
 pub async fn get_interview(
     Extension(store): Extension<Store>,
     Path(interview_id): Path<i32>,
@@ -75,28 +81,61 @@ pub async fn get_interview(
     Ok(Json(interview))
 }
 
-/// Get list of interviews
+/// Get list of interviews, optionally paginated
 #[utoipa::path(
     get,
     path = "/interviews",
+    params(InterviewListParams),
     responses(
         (status = 200, description = "List of Interviews", body = InterviewListResponse),
+        (status = 400, description = "Invalid query parameters", body = InterviewApiErrorResponse),
         (status = 500, description = "Database server error", body = InterviewApiErrorResponse)
     ),
     tag = "Interviews API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(count))]
 pub async fn get_interviews(
     Extension(store): Extension<Store>,
+    ValidatedQuery(params): ValidatedQuery<InterviewListParams>,
 ) -> Result<impl IntoResponse, InterviewsApiError> {
     let service = ApiService::new(&store.connection);
 
-    let interviews = service.get_interviews().await?;
+    let interviews = service.get_interviews_page(&params).await?;
+    Span::current().record("count", interviews.interviews.len());
 
     info!("queried all interviews");
     Ok(Json(interviews))
 }
 
+/// Replace an interview
+#[utoipa::path(
+    put,
+    path = "/interviews/{interview_id}",
+    request_body = CreateInterviewRequest,
+    responses(
+        (status = 200, description = "Interview Updated", body = InterviewMessage),
+        (status = 400, description = "Request Validation Error", body = InterviewApiErrorResponse),
+        (status = 404, description = "Interview not found", body = InterviewApiErrorResponse),
+        (status = 409, description = "Title used by another interview", body = InterviewApiErrorResponse),
+        (status = 500, description = "Database server error", body = InterviewApiErrorResponse)
+    ),
+    tag = "Interviews API"
+)]
+#[instrument(skip_all, fields(interview_id = interview_id))]
+pub async fn update_interview(
+    Extension(store): Extension<Store>,
+    Path(interview_id): Path<i32>,
+    ValidatedJson(interview): ValidatedJson<CreateInterviewRequest>,
+) -> Result<impl IntoResponse, InterviewsApiError> {
+    let service = ApiService::new(&store.connection);
+    let interview_title = interview.title.clone();
+    service.update_interview(interview_id, interview).await?;
+
+    let message = format!("Interview '{}' updated", interview_title);
+    info!(%message);
+    Ok(Json(InterviewMessage { message, id: None }))
+}
+
 /// Delete interview by ID
 #[utoipa::path(
     delete,
@@ -108,7 +147,7 @@ pub async fn get_interviews(
     ),
     tag = "Interviews API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(interview_id = interview_id))]
 pub async fn remove_interview(
     Extension(store): Extension<Store>,
     Path(interview_id): Path<i32>,
@@ -120,12 +159,18 @@ pub async fn remove_interview(
     let message = format!("Quote {interview_id} deleted");
 
     info!(%message);
-    Ok(Json(InterviewMessage { message }))
+    Ok(Json(InterviewMessage { message, id: None }))
 }
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(add_new_interview, get_interview, get_interviews, remove_interview,),
+    paths(
+        add_new_interview,
+        get_interview,
+        get_interviews,
+        update_interview,
+        remove_interview,
+    ),
     components(schemas()),
     modifiers()
 )]
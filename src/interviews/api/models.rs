@@ -1,10 +1,11 @@
 use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use validator::{Validate, ValidationError};
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct InterviewResponse {
+    pub id: i32,
     pub title: String,
     pub description: String,
     pub youtube_id: String,
@@ -25,9 +26,28 @@ pub struct CreateInterviewRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct InterviewMessage {
     pub message: String,
+    /// Id of the record a create added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct InterviewListResponse {
     pub interviews: Vec<InterviewResponse>,
+    /// Interviews across all pages
+    pub total: i64,
+}
+
+/// Which page of the interview list to return.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct InterviewListParams {
+    /// Page size; every interview when omitted
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    /// Interviews to skip before the page starts
+    #[validate(range(min = 0, message = "Offset cannot be negative"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
 }
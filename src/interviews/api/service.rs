@@ -1,10 +1,10 @@
 use crate::interviews::api::errors::InterviewsApiError;
 use crate::interviews::api::models::{
-    CreateInterviewRequest, InterviewListResponse, InterviewResponse,
+    CreateInterviewRequest, InterviewListParams, InterviewListResponse, InterviewResponse,
 };
 use crate::interviews::data::repository::InterviewRepository;
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
 pub struct ApiService {
     repo: InterviewRepository,
@@ -20,22 +20,33 @@ impl ApiService {
     pub async fn create_interview(
         &self,
         new_interview: CreateInterviewRequest,
-    ) -> Result<(), InterviewsApiError> {
+    ) -> Result<i32, InterviewsApiError> {
         let mut tx = self.repo.pool.begin().await?;
 
-        let _ = self
+        let id = self.insert_interview(&mut tx, new_interview).await?;
+
+        tx.commit().await?;
+
+        Ok(id)
+    }
+
+    /// Inserts an interview inside `tx`, returning the new interview id.
+    pub async fn insert_interview(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_interview: CreateInterviewRequest,
+    ) -> Result<i32, InterviewsApiError> {
+        let interview = self
             .repo
             .create_interview(
-                &mut tx,
+                tx,
                 new_interview.title,
                 new_interview.description,
                 new_interview.youtube_id,
             )
             .await?;
 
-        tx.commit().await?;
-
-        Ok(())
+        Ok(interview.id)
     }
 
     pub async fn get_interview(
@@ -47,6 +58,7 @@ impl ApiService {
         match quote {
             None => Ok(None),
             Some(i) => Ok(Some(InterviewResponse {
+                id: i.id,
                 title: i.title,
                 description: i.description,
                 youtube_id: i.youtube_id,
@@ -54,20 +66,60 @@ impl ApiService {
         }
     }
 
-    pub async fn get_interviews(&self) -> Result<InterviewListResponse, InterviewsApiError> {
+    pub async fn get_interviews_page(
+        &self,
+        params: &InterviewListParams,
+    ) -> Result<InterviewListResponse, InterviewsApiError> {
         let interviews = self
             .repo
-            .get_interviews()
+            .get_interviews_page(params.limit, params.offset.unwrap_or(0))
             .await?
             .into_iter()
             .map(|i| InterviewResponse {
+                id: i.id,
                 title: i.title,
                 description: i.description,
                 youtube_id: i.youtube_id,
             })
             .collect();
 
-        Ok(InterviewListResponse { interviews })
+        let total = self.repo.count_interviews().await?;
+
+        Ok(InterviewListResponse { interviews, total })
+    }
+
+    /// Replaces every field of an interview; the title must stay unique.
+    pub async fn update_interview(
+        &self,
+        interview_id: i32,
+        interview: CreateInterviewRequest,
+    ) -> Result<(), InterviewsApiError> {
+        let _ = self
+            .get_interview(interview_id)
+            .await?
+            .ok_or(InterviewsApiError::NotFound(interview_id))?;
+
+        if let Some(other) = self.repo.get_interview_by_title(&interview.title).await?
+            && other.id != interview_id
+        {
+            return Err(InterviewsApiError::TitleTaken(interview.title));
+        }
+
+        let mut tx = self.repo.pool.begin().await?;
+
+        self.repo
+            .update_interview(
+                &mut tx,
+                interview_id,
+                interview.title,
+                interview.description,
+                interview.youtube_id,
+            )
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
     }
 
     pub async fn delete_interview(&self, interview_id: i32) -> Result<(), InterviewsApiError> {
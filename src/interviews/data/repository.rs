@@ -1,7 +1,9 @@
 use crate::interviews::data::entities::Interview;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::NaiveDateTime;
 use sqlx::{PgPool, Postgres, Transaction};
 
+use crate::store::traced_query;
+
 pub struct InterviewRepository {
     pub pool: PgPool,
 }
@@ -18,41 +20,178 @@ impl InterviewRepository {
         description: String,
         youtube_id: String,
     ) -> Result<Interview, sqlx::Error> {
-        sqlx::query_as!(
-            Interview,
-            r#"
-            INSERT INTO interview (title, description, youtube_id) VALUES ($1, $2, $3)
-            ON CONFLICT (title) DO UPDATE SET title = EXCLUDED.title
-            RETURNING *
-            "#,
-            title,
-            description,
-            youtube_id
+        traced_query(
+            "create_interview",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                INSERT INTO interview (title, description, youtube_id) VALUES ($1, $2, $3)
+                ON CONFLICT (title) DO UPDATE SET title = EXCLUDED.title
+                RETURNING *
+                "#,
+                title,
+                description,
+                youtube_id
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
     pub async fn get_interview(&self, interview_id: i32) -> Result<Option<Interview>, sqlx::Error> {
-        sqlx::query_as!(
-            Interview,
-            r#"
-            SELECT * FROM interview WHERE id = $1
-            "#,
-            interview_id
+        traced_query(
+            "get_interview",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                SELECT * FROM interview WHERE id = $1
+                "#,
+                interview_id
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn get_interview_by_title(
+        &self,
+        title: &str,
+    ) -> Result<Option<Interview>, sqlx::Error> {
+        traced_query(
+            "get_interview_by_title",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                SELECT * FROM interview WHERE title = $1
+                "#,
+                title
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    /// Whether an interview titled `title` exists, including rows written by `tx`.
+    pub async fn interview_title_exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        title: &str,
+    ) -> Result<bool, sqlx::Error> {
+        traced_query(
+            "interview_title_exists",
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(SELECT 1 FROM interview WHERE title = $1) AS "exists!"
+                "#,
+                title
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_optional(&self.pool)
         .await
     }
 
     pub async fn get_interviews(&self) -> Result<Vec<Interview>, sqlx::Error> {
-        sqlx::query_as!(
-            Interview,
-            r#"
-            SELECT * FROM interview ORDER BY title ASC
-            "#,
+        traced_query(
+            "get_interviews",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                SELECT * FROM interview ORDER BY title ASC
+                "#,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// One page of interviews in title order; `None` returns every interview after `offset`.
+    pub async fn get_interviews_page(
+        &self,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> Result<Vec<Interview>, sqlx::Error> {
+        traced_query(
+            "get_interviews_page",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                SELECT * FROM interview ORDER BY title ASC LIMIT $1 OFFSET $2
+                "#,
+                limit,
+                offset,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// The `limit` most recently added interviews, newest first.
+    pub async fn get_latest_interviews(&self, limit: i64) -> Result<Vec<Interview>, sqlx::Error> {
+        traced_query(
+            "get_latest_interviews",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                SELECT * FROM interview ORDER BY created_at DESC, id DESC LIMIT $1
+                "#,
+                limit
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn count_interviews(&self) -> Result<i64, sqlx::Error> {
+        traced_query(
+            "count_interviews",
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) AS "count!" FROM interview
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    /// When a interview was last added or edited; `None` without any.
+    pub async fn get_last_update(&self) -> Result<Option<NaiveDateTime>, sqlx::Error> {
+        traced_query(
+            "get_last_interview_update",
+            sqlx::query_scalar!(
+                r#"
+                SELECT MAX(updated_at) FROM interview
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn update_interview(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        interview_id: i32,
+        title: String,
+        description: String,
+        youtube_id: String,
+    ) -> Result<Interview, sqlx::Error> {
+        traced_query(
+            "update_interview",
+            sqlx::query_as!(
+                Interview,
+                r#"
+                UPDATE interview SET title = $2, description = $3, youtube_id = $4, updated_at = NOW()
+                WHERE id = $1
+                RETURNING *
+                "#,
+                interview_id,
+                title,
+                description,
+                youtube_id
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_all(&self.pool)
         .await
     }
 
@@ -61,13 +200,16 @@ impl InterviewRepository {
         tx: &mut Transaction<'_, Postgres>,
         interview_id: i32,
     ) -> Result<(), sqlx::Error> {
-        sqlx::query!(
-            r#"
-            DELETE FROM interview WHERE id = $1
-            "#,
-            interview_id
+        traced_query(
+            "delete_interview",
+            sqlx::query!(
+                r#"
+                DELETE FROM interview WHERE id = $1
+                "#,
+                interview_id
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
         Ok(())
@@ -3,7 +3,7 @@ pub mod data;
 pub mod web;
 
 use axum::{
-    routing::{delete, get, post}, Extension,
+    routing::{delete, get, post, put}, Extension,
     Router,
 };
 
@@ -16,7 +16,7 @@ pub fn rest_api_router(interview_store: &Store) -> Router {
         .route("/interviews/{quote_id}", get(api::handlers::get_interview))
         .route(
             "/interviews/{quote_id}",
-            delete(api::handlers::remove_interview),
+            put(api::handlers::update_interview).delete(api::handlers::remove_interview),
         )
         // .fallback(api::handlers::fallback_handler)
         .layer(Extension(interview_store.clone()));
@@ -27,16 +27,23 @@ pub fn rest_api_router(interview_store: &Store) -> Router {
 pub fn web_router(interview_store: &Store) -> Router {
     let router = Router::new()
         .route("/", get(web::handlers::list_interviews))
-        // .route("/{quote_id}", get(web::handlers::movie_details))
-        .layer(Extension(interview_store.clone()));
-
-    router
-}
-
-pub fn htmx_web_router(interview_store: &Store) -> Router {
-    let router = Router::new()
-        .route("/", get(web::handlers::htmx_list_interviews))
-        // .route("/{quote_id}", get(web::handlers::movie_details))
+        .route("/", post(web::handlers::create_interview))
+        .route("/new", get(web::handlers::new_interview_form))
+        .route("/{interview_id}", post(web::handlers::update_interview))
+        .route("/{interview_id}", put(web::handlers::update_interview))
+        .route("/{interview_id}", delete(web::handlers::delete_interview))
+        .route(
+            "/{interview_id}/edit",
+            get(web::handlers::edit_interview_form),
+        )
+        .route(
+            "/{interview_id}/delete",
+            get(web::handlers::confirm_delete_interview),
+        )
+        .route(
+            "/{interview_id}/delete",
+            post(web::handlers::delete_interview),
+        )
         .layer(Extension(interview_store.clone()));
 
     router
@@ -1 +1,29 @@
+use crate::forms::FormErrors;
+use crate::interviews::api::errors::InterviewsApiError;
+use crate::web_error::WebError;
 
+impl From<InterviewsApiError> for WebError {
+    fn from(error: InterviewsApiError) -> Self {
+        match error {
+            InterviewsApiError::NotFound(id) => Self::not_found("Interview", id),
+            InterviewsApiError::TitleTaken(title) => {
+                let mut errors = FormErrors::default();
+                errors.add("title", format!("Another interview is titled '{}'", title));
+                Self::InvalidForm(errors)
+            }
+            InterviewsApiError::Validation(validation) => {
+                let mut errors = FormErrors::default();
+                errors.merge(&validation);
+                Self::InvalidForm(errors)
+            }
+            InterviewsApiError::DatabaseError(e) => {
+                match FormErrors::from_unique_violation(&e, &["title", "youtube_id", "description"])
+                {
+                    Some(errors) => Self::InvalidForm(errors),
+                    None => Self::DatabaseError(e),
+                }
+            }
+            other => Self::InternalError(other.to_string()),
+        }
+    }
+}
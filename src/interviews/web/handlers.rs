@@ -1,38 +1,250 @@
-use crate::interviews::{
-
-    web::{
-        models::InterviewViewModel,
-        service::WebService,
-        templates::{HtmxInterviewsTemplate, InterviewsTemplate},
-    },
+use crate::forms::{ConfirmDeleteTemplate, FormData, FormErrors, FormTarget};
+use crate::htmx::{HtmxRequest, fragment};
+use crate::i18n::Locale;
+use crate::interviews::web::{
+    models::InterviewFormViewModel,
+    service::WebService,
+    templates::{HtmxInterviewCardsTemplate, InterviewFormTemplate, InterviewsTemplate},
 };
-
+use crate::pagination::PageQuery;
+use crate::store::Store;
+use crate::web_error::WebError;
 use askama::Template;
-use axum::{Extension, extract::Path, http::StatusCode, response::Html};
-use tracing::{info, instrument};
+use axum::{
+    Extension,
+    extract::{Path, Query},
+    response::{IntoResponse, Response},
+};
+use tracing::{Span, info, instrument};
 
-use crate::store::Store;
+/// The interview page; for htmx, later pages are only the cards, added after
+/// the last one as it scrolls into view.
+#[instrument(skip_all, fields(count, page))]
+pub async fn list_interviews(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Query(page): Query<PageQuery>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let (interviews, pagination) = service.get_interviews_page(page.number()).await?;
+    Span::current().record("count", interviews.len());
+    Span::current().record("page", pagination.page);
+
+    let interviews_page = if htmx.is_partial() && pagination.page > 1 {
+        fragment(
+            HtmxInterviewCardsTemplate {
+                interviews,
+                pagination,
+                locale,
+            }
+            .render()?,
+        )
+    } else {
+        htmx.render(&InterviewsTemplate {
+            interviews,
+            pagination,
+            locale,
+        })?
+    };
+
+    info!("queried interviews page {}", pagination.page);
+    Ok(interviews_page)
+}
+
+fn render_form(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: InterviewFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, askama::Error> {
+    htmx.render(&InterviewFormTemplate {
+        heading: target.heading,
+        form,
+        errors,
+        action: target.action,
+        method: target.method,
+        cancel: target.cancel,
+        htmx: target.htmx,
+        locale,
+    })
+}
+
+/// Shows the form again with the errors next to its inputs.
+fn form_errors(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: InterviewFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, WebError> {
+    let status = target.error_status();
+    let page = render_form(htmx, locale, target, form, errors)?;
+
+    Ok((status, page).into_response())
+}
+
+fn create_target(htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    FormTarget::create(
+        "interviews",
+        locale.t("interview-form-add"),
+        htmx.is_partial(),
+    )
+}
+
+fn edit_target(interview_id: i32, htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    FormTarget::edit(
+        "interviews",
+        interview_id,
+        locale.t("interview-form-edit"),
+        "",
+        htmx.is_partial(),
+    )
+}
+
+#[instrument(skip_all)]
+pub async fn new_interview_form(htmx: HtmxRequest, locale: Locale) -> Result<Response, WebError> {
+    let form = InterviewFormViewModel::default();
+
+    let page = render_form(
+        &htmx,
+        locale,
+        create_target(&htmx, locale),
+        form,
+        FormErrors::default(),
+    )?;
+
+    Ok(page)
+}
+
+#[instrument(skip_all, fields(interview_id))]
+pub async fn create_interview(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    form: FormData,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+    let interview_form = InterviewFormViewModel::from_form(&form);
+
+    match service.create_interview(&interview_form).await {
+        Ok(interview_id) => {
+            Span::current().record("interview_id", interview_id);
+            info!("created interview {}", interview_id);
+            Ok(htmx.redirect("/interviews"))
+        }
+        Err(WebError::InvalidForm(errors)) => form_errors(
+            &htmx,
+            locale,
+            create_target(&htmx, locale),
+            interview_form,
+            errors,
+        ),
+        Err(e) => Err(e),
+    }
+}
 
-#[instrument]
-pub async fn list_interviews(Extension(store): Extension<Store>) -> Html<String> {
+#[instrument(skip_all, fields(interview_id = interview_id))]
+pub async fn edit_interview_form(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(interview_id): Path<i32>,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
 
-    let interviews = service.get_interviews().await;
+    let interview = service
+        .get_interview(interview_id)
+        .await?
+        .ok_or(WebError::not_found("Interview", interview_id))?;
 
-    let interviews_template = InterviewsTemplate { interviews }.render().unwrap();
+    let form = InterviewFormViewModel::from_interview(&interview);
+    let page = render_form(
+        &htmx,
+        locale,
+        edit_target(interview_id, &htmx, locale),
+        form,
+        FormErrors::default(),
+    )?;
 
-    info!("queried all interviews");
-    Html(interviews_template)
+    Ok(page)
 }
 
-#[instrument]
-pub async fn htmx_list_interviews(Extension(store): Extension<Store>) -> Html<String> {
+/// Saves the edit form, sent as a `POST` without htmx and a `PUT` with it.
+#[instrument(skip_all, fields(interview_id = interview_id))]
+pub async fn update_interview(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(interview_id): Path<i32>,
+    form: FormData,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
+    let interview_form = InterviewFormViewModel::from_form(&form);
 
-    let interviews = service.get_interviews().await;
+    match service
+        .update_interview(interview_id, &interview_form)
+        .await
+    {
+        Ok(()) => {
+            info!("updated interview {}", interview_id);
+            Ok(htmx.redirect("/interviews"))
+        }
+        Err(WebError::InvalidForm(errors)) => form_errors(
+            &htmx,
+            locale,
+            edit_target(interview_id, &htmx, locale),
+            interview_form,
+            errors,
+        ),
+        Err(e) => Err(e),
+    }
+}
+
+#[instrument(skip_all, fields(interview_id = interview_id))]
+pub async fn confirm_delete_interview(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(interview_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let interview = service
+        .get_interview(interview_id)
+        .await?
+        .ok_or(WebError::not_found("Interview", interview_id))?;
+
+    let action = if htmx.is_partial() {
+        format!("/interviews/{}", interview_id)
+    } else {
+        format!("/interviews/{}/delete", interview_id)
+    };
+    let confirm_page = htmx.render(&ConfirmDeleteTemplate {
+        kind: "interview",
+        label: interview.title,
+        action,
+        cancel: "/interviews".to_string(),
+        htmx: htmx.is_partial(),
+        locale,
+    })?;
+
+    Ok(confirm_page)
+}
+
+/// Deletes a interview, sent as a `POST` without htmx and a `DELETE` with it.
+#[instrument(skip_all, fields(interview_id = interview_id))]
+pub async fn delete_interview(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    Path(interview_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
 
-    let htmx_interviews_template = HtmxInterviewsTemplate { interviews }.render().unwrap();
+    service.delete_interview(interview_id).await?;
 
-    info!("queried all interviews");
-    Html(htmx_interviews_template)
+    info!("deleted interview {}", interview_id);
+    Ok(htmx.redirect("/interviews"))
 }
@@ -1,9 +1,51 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use crate::forms::{FormData, FormErrors};
+use crate::interviews::api::models::CreateInterviewRequest;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InterviewViewModel {
+    pub id: i32,
     pub title: String,
     pub description: String,
     pub youtube_id: String,
 }
+
+/// The interview form as typed, so it can be shown again with its errors.
+#[derive(Debug, Clone, Default)]
+pub struct InterviewFormViewModel {
+    pub title: String,
+    pub description: String,
+    pub youtube_id: String,
+}
+
+impl InterviewFormViewModel {
+    pub fn from_interview(interview: &InterviewViewModel) -> Self {
+        Self {
+            title: interview.title.clone(),
+            description: interview.description.clone(),
+            youtube_id: interview.youtube_id.clone(),
+        }
+    }
+
+    pub fn from_form(form: &FormData) -> Self {
+        Self {
+            title: form.value("title"),
+            description: form.value("description"),
+            youtube_id: form.value("youtube_id"),
+        }
+    }
+
+    pub fn to_request(&self) -> Result<CreateInterviewRequest, FormErrors> {
+        let request = CreateInterviewRequest {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            youtube_id: self.youtube_id.clone(),
+        };
+        let mut errors = FormErrors::default();
+        if let Err(validation) = request.validate() {
+            errors.merge(&validation);
+        }
+        errors.into_result().map(|_| request)
+    }
+}
@@ -1,9 +1,14 @@
+use crate::interviews::api::errors::InterviewsApiError;
+use crate::interviews::api::service::ApiService;
 use crate::interviews::data::repository::InterviewRepository;
-// use crate::movies::web::errors::MoviesWebError;
-use crate::interviews::web::models::InterviewViewModel;
+use crate::web_error::WebError;
+use crate::interviews::web::models::{InterviewFormViewModel, InterviewViewModel};
+use crate::pagination::Pagination;
 
 use sqlx::PgPool;
 
+pub const INTERVIEWS_PER_PAGE: i64 = 5;
+
 pub struct WebService {
     repo: InterviewRepository,
 }
@@ -15,20 +20,86 @@ impl WebService {
         }
     }
 
-    pub async fn get_interviews(&self) -> Vec<InterviewViewModel> {
+    pub async fn get_interviews_page(
+        &self,
+        page: i64,
+    ) -> Result<(Vec<InterviewViewModel>, Pagination), WebError> {
+        let pagination = Pagination::new(page, INTERVIEWS_PER_PAGE);
         let interviews = self
             .repo
-            .get_interviews()
-            .await
-            .unwrap()
+            .get_interviews_page(Some(pagination.limit()), pagination.offset())
+            .await?
             .into_iter()
             .map(|i| InterviewViewModel {
+                id: i.id,
                 title: i.title,
                 description: i.description,
                 youtube_id: i.youtube_id,
             })
             .collect();
+        let total = self.repo.count_interviews().await?;
+
+        Ok((interviews, pagination.with_total(total)))
+    }
+
+    pub async fn get_interview(
+        &self,
+        interview_id: i32,
+    ) -> Result<Option<InterviewViewModel>, WebError> {
+        let interview = self
+            .repo
+            .get_interview(interview_id)
+            .await?
+            .map(|i| InterviewViewModel {
+                id: i.id,
+                title: i.title,
+                description: i.description,
+                youtube_id: i.youtube_id,
+            });
+
+        Ok(interview)
+    }
+
+    /// Saves a new interview from the form, returning its id. The API keeps
+    /// the existing interview when the title is taken; the form says so.
+    pub async fn create_interview(
+        &self,
+        form: &InterviewFormViewModel,
+    ) -> Result<i32, WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        if self
+            .repo
+            .get_interview_by_title(&request.title)
+            .await?
+            .is_some()
+        {
+            return Err(InterviewsApiError::TitleTaken(request.title).into());
+        }
+        let interview_id = ApiService::new(&self.repo.pool)
+            .create_interview(request)
+            .await?;
+
+        Ok(interview_id)
+    }
+
+    pub async fn update_interview(
+        &self,
+        interview_id: i32,
+        form: &InterviewFormViewModel,
+    ) -> Result<(), WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        ApiService::new(&self.repo.pool)
+            .update_interview(interview_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_interview(&self, interview_id: i32) -> Result<(), WebError> {
+        ApiService::new(&self.repo.pool)
+            .delete_interview(interview_id)
+            .await?;
 
-        interviews
+        Ok(())
     }
 }
@@ -1,14 +1,48 @@
-use crate::interviews::web::models::InterviewViewModel;
+use crate::forms::FormErrors;
+use crate::htmx::impl_page;
+use crate::i18n::Locale;
+use crate::interviews::web::models::{InterviewFormViewModel, InterviewViewModel};
+use crate::pagination::Pagination;
 use askama::Template;
 
 #[derive(Template)]
-#[template(path = "pages/interviews.html")]
+#[template(path = "pages/interviews.html", blocks = ["title", "main"])]
 pub struct InterviewsTemplate {
+    pub locale: Locale,
     pub interviews: Vec<InterviewViewModel>,
+    pub pagination: Pagination,
 }
 
 #[derive(Template)]
-#[template(path = "pages/htmx/interviews.html")]
-pub struct HtmxInterviewsTemplate {
+#[template(path = "partials/htmx/interview_cards.html")]
+pub struct HtmxInterviewCardsTemplate {
+    pub locale: Locale,
     pub interviews: Vec<InterviewViewModel>,
+    pub pagination: Pagination,
 }
+
+/// An interview card sent to the open pages when the interview is saved
+/// elsewhere.
+#[derive(Template)]
+#[template(path = "partials/live/interview.html")]
+pub struct LiveInterviewTemplate {
+    pub locale: Locale,
+    pub interview: InterviewViewModel,
+    /// Added at the top of the list rather than replacing its card
+    pub created: bool,
+}
+
+#[derive(Template)]
+#[template(path = "pages/interview_form.html", blocks = ["title", "main"])]
+pub struct InterviewFormTemplate {
+    pub locale: Locale,
+    pub heading: String,
+    pub form: InterviewFormViewModel,
+    pub errors: FormErrors,
+    pub action: String,
+    pub method: &'static str,
+    pub cancel: String,
+    pub htmx: bool,
+}
+
+impl_page!(InterviewsTemplate, InterviewFormTemplate);
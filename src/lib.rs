@@ -1,4 +1,20 @@
+pub mod client;
+pub mod export;
+pub mod feeds;
+pub mod forms;
+pub mod htmx;
+pub mod i18n;
+pub mod import;
 pub mod interviews;
+pub mod live;
+pub mod media;
 pub mod movies;
+pub mod pagination;
 pub mod quotes;
+pub mod seed;
+pub mod seo;
 pub mod store;
+pub mod telemetry;
+#[cfg(test)]
+mod test_support;
+pub mod web_error;
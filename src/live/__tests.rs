@@ -0,0 +1,95 @@
+use super::*;
+use crate::test_support::{create_database, drop_database, movie_request};
+
+async fn next_event(receiver: &mut broadcast::Receiver<LiveEvent>) -> LiveEvent {
+    tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("no change arrived")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn quote_changes_reach_the_event_stream() {
+    let name = "tarantinors_live_quote_changes";
+    let pool = create_database(name).await;
+    let live = LiveUpdates::start(&Store {
+        connection: pool.clone(),
+    });
+    let mut receiver = live.subscribe();
+    // Let the listener run LISTEN before anything is written
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let id: i32 = sqlx::query_scalar("INSERT INTO quote (text) VALUES ('Bingo.') RETURNING id")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let created = next_event(&mut receiver).await;
+    assert_eq!(created.name, "quote");
+    assert!(
+        created
+            .html(Locale::En)
+            .contains(r##"hx-swap-oob="afterbegin:#quote-cards""##)
+    );
+    assert!(created.html(Locale::En).contains("Bingo."));
+    assert!(created.html(Locale::Fr).contains(">Modifier</a>"));
+
+    sqlx::query("UPDATE quote SET text = 'Bingo, baby.' WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let updated = next_event(&mut receiver).await;
+    assert!(
+        updated
+            .html(Locale::En)
+            .contains(&format!(r#"id="quote-{}" hx-swap-oob="true""#, id))
+    );
+    assert!(updated.html(Locale::En).contains("Bingo, baby."));
+
+    sqlx::query("DELETE FROM quote WHERE id = $1")
+        .bind(id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let deleted = next_event(&mut receiver).await;
+    assert_eq!(
+        deleted.html(Locale::Fr),
+        format!(r#"<div id="quote-{}" hx-swap-oob="delete"></div>"#, id)
+    );
+
+    drop_database(pool, name).await;
+}
+
+#[tokio::test]
+async fn movie_cards_are_rendered_in_each_locale() {
+    let name = "tarantinors_live_movie_locales";
+    let pool = create_database(name).await;
+    let id = crate::movies::api::service::ApiService::new(&pool)
+        .create_movie(movie_request("Jackie Brown"))
+        .await
+        .unwrap();
+
+    let change = Change {
+        table: "movie".to_string(),
+        action: ChangeAction::Insert,
+        id,
+    };
+    let event = LiveEvent::render(&pool, &change).await.unwrap().unwrap();
+    assert!(
+        event
+            .html(Locale::En)
+            .contains(r#"alt="Poster of Jackie Brown""#)
+    );
+    assert!(
+        event
+            .html(Locale::Fr)
+            .contains(r#"alt="Affiche de Jackie Brown""#)
+    );
+    assert!(
+        event
+            .html(Locale::Es)
+            .contains(r#"alt="Póster de Jackie Brown""#)
+    );
+
+    drop_database(pool, name).await;
+}
@@ -0,0 +1,255 @@
+//! Live updates of the open pages. Triggers on the movie, quote and interview
+//! tables `NOTIFY` every change, so each server instance hears about writes
+//! made through any other, turns them into cards once and streams them to its
+//! browsers as server-sent events. The cards carry `hx-swap-oob` and put
+//! themselves in place on whatever page is open. They are rendered once per
+//! locale and each stream gets the one its page is shown in.
+
+#[cfg(test)]
+mod __tests;
+
+use crate::i18n::Locale;
+use crate::interviews::web::{
+    service::WebService as InterviewsWebService, templates::LiveInterviewTemplate,
+};
+use crate::movies::web::{service::WebService as MoviesWebService, templates::LiveMovieTemplate};
+use crate::quotes::web::{service::WebService as QuotesWebService, templates::LiveQuoteTemplate};
+use crate::store::Store;
+use crate::web_error::WebError;
+use askama::Template;
+use axum::{
+    Extension, Router,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+};
+use futures::Stream;
+use serde::Deserialize;
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The channel the `notify_change` triggers publish on.
+pub const CHANNEL: &str = "catalogue_changes";
+
+/// Changes kept for browsers that are slow to read them.
+const BUFFERED_CHANGES: usize = 64;
+
+/// Pause before listening again after the connection failed.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A row written to one of the watched tables, as sent by the trigger.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Change {
+    /// `movie`, `quote` or `interview`
+    pub table: String,
+    pub action: ChangeAction,
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// A change ready for the browsers: the event is named after the table and
+/// carries the out-of-band swaps to apply, in every locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveEvent {
+    pub name: String,
+    translations: Vec<(Locale, String)>,
+}
+
+/// Renders the swaps of a change once per locale.
+fn per_locale(
+    render: impl Fn(Locale) -> askama::Result<String>,
+) -> askama::Result<Vec<(Locale, String)>> {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| Ok((locale, render(locale)?.trim().to_string())))
+        .collect()
+}
+
+impl LiveEvent {
+    /// Turns a change into the cards to swap. `None` when the record is
+    /// already gone again by the time it is read.
+    pub async fn render(pool: &PgPool, change: &Change) -> Result<Option<Self>, WebError> {
+        let created = change.action == ChangeAction::Insert;
+        let translations = if change.action == ChangeAction::Delete {
+            Some(per_locale(|_| {
+                Ok(format!(
+                    r#"<div id="{}-{}" hx-swap-oob="delete"></div>"#,
+                    change.table, change.id
+                ))
+            })?)
+        } else {
+            match change.table.as_str() {
+                "movie" => match MoviesWebService::new(pool).get_movie(change.id).await? {
+                    Some(movie) => Some(per_locale(|locale| {
+                        LiveMovieTemplate {
+                            movie: movie.clone(),
+                            created,
+                            locale,
+                        }
+                        .render()
+                    })?),
+                    None => None,
+                },
+                "quote" => match QuotesWebService::new(pool).get_quote(change.id).await? {
+                    Some(quote) => Some(per_locale(|locale| {
+                        LiveQuoteTemplate {
+                            quote: quote.clone(),
+                            created,
+                            locale,
+                        }
+                        .render()
+                    })?),
+                    None => None,
+                },
+                "interview" => {
+                    match InterviewsWebService::new(pool)
+                        .get_interview(change.id)
+                        .await?
+                    {
+                        Some(interview) => Some(per_locale(|locale| {
+                            LiveInterviewTemplate {
+                                interview: interview.clone(),
+                                created,
+                                locale,
+                            }
+                            .render()
+                        })?),
+                        None => None,
+                    }
+                }
+                table => {
+                    return Err(WebError::InternalError(format!(
+                        "Unexpected change to table {}",
+                        table
+                    )));
+                }
+            }
+        };
+
+        Ok(translations.map(|translations| Self {
+            name: change.table.clone(),
+            translations,
+        }))
+    }
+
+    /// The swaps for a page shown in `locale`.
+    pub fn html(&self, locale: Locale) -> &str {
+        self.translations
+            .iter()
+            .find(|(translation, _)| *translation == locale)
+            .or(self.translations.first())
+            .map(|(_, html)| html.as_str())
+            .unwrap_or_default()
+    }
+}
+
+/// The changes heard from the database, for every open event stream.
+#[derive(Debug, Clone)]
+pub struct LiveUpdates {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl LiveUpdates {
+    /// Starts listening to the database in the background.
+    pub fn start(store: &Store) -> Self {
+        let (sender, _) = broadcast::channel(BUFFERED_CHANGES);
+        tokio::spawn(listen(store.connection.clone(), sender.clone()));
+
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Forwards the notifications of [`CHANNEL`] until the pool is closed.
+/// `PgListener` reconnects by itself; notifications sent while it was away
+/// are lost, which only means a card stays stale until the next reload.
+async fn listen(pool: PgPool, sender: broadcast::Sender<LiveEvent>) {
+    while !pool.is_closed() {
+        let mut listener = match PgListener::connect_with(&pool).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::warn!(error = %e, "Could not listen for catalogue changes");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+        if let Err(e) = listener.listen(CHANNEL).await {
+            tracing::warn!(error = %e, "Could not listen for catalogue changes");
+            tokio::time::sleep(RETRY_DELAY).await;
+            continue;
+        }
+
+        loop {
+            let notification = match listener.recv().await {
+                Ok(notification) => notification,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Lost the catalogue changes listener");
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    break;
+                }
+            };
+            // Nobody is watching; skip reading the record
+            if sender.receiver_count() == 0 {
+                continue;
+            }
+
+            let change = match serde_json::from_str::<Change>(notification.payload()) {
+                Ok(change) => change,
+                Err(e) => {
+                    tracing::warn!(error = %e, payload = notification.payload(), "Unreadable change");
+                    continue;
+                }
+            };
+            match LiveEvent::render(&pool, &change).await {
+                Ok(Some(event)) => {
+                    let _ = sender.send(event);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error = %e.log_message(), "Could not render a change"),
+            }
+        }
+    }
+}
+
+/// `GET /events`: the stream of changed cards the layout subscribes to, in
+/// the locale of the page.
+pub async fn events(
+    Extension(live): Extension<LiveUpdates>,
+    locale: Locale,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures::stream::unfold(live.subscribe(), move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let sse = Event::default().event(&event.name).data(event.html(locale));
+                    return Some((Ok(sse), receiver));
+                }
+                // The page keeps what it has; the next reload catches up
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Event stream fell behind");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+pub fn web_router(store: &Store) -> Router {
+    Router::new()
+        .route("/events", get(events))
+        .layer(Extension(LiveUpdates::start(store)))
+}
@@ -1,18 +1,37 @@
 mod docs;
+mod export;
+mod feeds;
+mod forms;
+mod htmx;
+mod i18n;
+mod import;
 mod interviews;
+mod live;
+mod media;
 mod movies;
+mod pagination;
 mod quotes;
+mod seo;
 mod store;
+#[cfg(test)]
+mod test_support;
+mod web_error;
 
 // mod tests;
 use axum::extract::Path;
 
 use axum::body::Body;
 use axum::http::Request;
-use axum::{response::Html, routing::get, Json, Router};
+use axum::response::Response;
+use axum::{
+    middleware,
+    routing::{any, get},
+    Json, Router,
+};
 
 use serde::{Deserialize, Serialize};
 
+use tower::Layer;
 use tower_http::{services::ServeDir, trace::TraceLayer};
 use tracing::{info, instrument};
 use tracing_subscriber::fmt::format::FmtSpan;
@@ -21,7 +40,39 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use askama::Template;
+use clap::{Parser, Subcommand};
+use htmx::HtmxRequest;
+use i18n::Locale;
+use media::storage::{LocalStorage, SharedStorage};
+use std::sync::Arc;
+use std::time::Duration;
 use store::Store;
+use tarantino_rs::{seed, telemetry};
+use web_error::WebError;
+
+#[derive(Parser)]
+#[command(name = "tarantinors", about = "Quentin Tarantino web app and API server")]
+struct Args {
+    #[command(subcommand)]
+    command: Option<ServerCommand>,
+}
+
+#[derive(Subcommand)]
+enum ServerCommand {
+    /// Run the web server (the default)
+    Serve,
+    /// Load the bundled Tarantino dataset; records that already exist are skipped
+    Seed {
+        /// Delete the whole catalogue, its uploaded images included, and
+        /// restart ids before seeding
+        #[arg(long)]
+        wipe: bool,
+    },
+    /// Download the posters linked from other hosts into the media storage
+    FetchPosters,
+    /// Delete the uploaded images no movie or still uses any more
+    PruneMedia,
+}
 
 fn init_tracing() {
     let rust_log = std::env::var(EnvFilter::DEFAULT_ENV)
@@ -41,45 +92,61 @@ fn init_tracing() {
                 .with_default_directive(LevelFilter::INFO.into())
                 .parse_lossy(rust_log),
         )
+        .with(telemetry::otel_layer("tarantinors"))
         .init();
 }
 
-fn make_app_router(db_store: &Store) -> Router {
+fn make_app_router(db_store: &Store, media_storage: &LocalStorage) -> Router {
+    let storage: SharedStorage = Arc::new(media_storage.clone());
     let movies_api_router = movies::rest_api_router(db_store);
     let movies_web_router = movies::web_router(db_store);
-    let movies_htmx_web_router = movies::htmx_web_router(db_store);
     let quotes_api_router = quotes::rest_api_router(db_store);
     let quotes_web_router = quotes::web_router(db_store);
-    let quotes_htmx_web_router = quotes::htmx_web_router(db_store);
     let interviews_api_router = interviews::rest_api_router(db_store);
     let interviews_web_router = interviews::web_router(db_store);
-    let interviews_htmx_web_router = interviews::htmx_web_router(db_store);
+    let import_api_router = import::rest_api_router(db_store);
+    let export_api_router = export::rest_api_router(db_store);
+    let media_api_router = media::rest_api_router(db_store, &storage);
 
-    let app_router = Router::new()
+    // Errors of the web pages come back as HTML, and as alerts for htmx
+    let web_router = Router::new()
         .route("/", get(home))
         .nest("/movies", movies_web_router)
         .nest("/quotes", quotes_web_router)
         .nest("/interviews", interviews_web_router)
-        .route("/htmx", get(htmx_home))
-        .nest("/htmx/movies", movies_htmx_web_router)
-        .nest("/htmx/quotes", quotes_htmx_web_router)
-        .nest("/htmx/interviews", interviews_htmx_web_router)
+        .route("/htmx", any(htmx::legacy_redirect))
+        .route("/htmx/{*path}", any(htmx::legacy_redirect))
+        .route("/lang/{code}", get(i18n::switch_locale))
+        .merge(live::web_router(db_store))
+        .merge(feeds::web_router(db_store))
+        .merge(seo::web_router(db_store))
+        .fallback(web_error::not_found)
+        .layer(middleware::from_fn(web_error::htmx_errors));
+
+    let app_router = Router::new()
+        .merge(web_router)
         .nest("/api/v1", movies_api_router)
         .nest("/api/v1", quotes_api_router)
         .nest("/api/v1", interviews_api_router)
+        .nest("/api/v1", import_api_router)
+        .nest("/api/v1", export_api_router)
+        .nest("/api/v1", media_api_router)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", docs::ApiDoc::openapi()))
         .nest_service("/static", ServeDir::new("static"))
+        .nest_service(media::MEDIA_URL, media_storage.service())
         .layer(
             TraceLayer::new_for_http().make_span_with(|request: &Request<Body>| {
                 let request_id = uuid::Uuid::new_v4();
-                tracing::span!(
+                let span = tracing::span!(
                     tracing::Level::INFO,
                     "request",
                     method = display(request.method()),
                     uri = display(request.uri()),
                     version = debug(request.version()),
                     request_id = display(request_id)
-                )
+                );
+                telemetry::set_parent_from_headers(&span, request.headers());
+                span
             }),
         );
     app_router
@@ -87,21 +154,126 @@ fn make_app_router(db_store: &Store) -> Router {
 #[tokio::main]
 async fn main() {
     let _ = dotenvy::dotenv();
+    let args = Args::parse();
 
     init_tracing();
 
     let db_store = Store::new().await;
+    let media_storage = LocalStorage::from_env();
+
+    match args.command {
+        Some(ServerCommand::Seed { wipe }) => {
+            run_seed(&db_store, &media_storage, wipe).await;
+            telemetry::shutdown();
+            return;
+        }
+        Some(ServerCommand::FetchPosters) => {
+            run_fetch_posters(&db_store, &media_storage).await;
+            telemetry::shutdown();
+            return;
+        }
+        Some(ServerCommand::PruneMedia) => {
+            run_prune_media(&db_store, &media_storage, media::prune::PRUNE_GRACE_PERIOD).await;
+            telemetry::shutdown();
+            return;
+        }
+        Some(ServerCommand::Serve) | None => {}
+    }
 
-    let app = make_app_router(&db_store);
+    // The locale prefix is taken off before routing, so it wraps the router
+    let app = middleware::from_fn(i18n::locale_prefix)
+        .layer(make_app_router(&db_store, &media_storage));
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
         .await
         .unwrap();
 
     info!("Listening on http://{}", listener.local_addr().unwrap());
-    axum::serve(listener, app)
+    axum::serve(listener, axum::ServiceExt::<Request<Body>>::into_make_service(app))
         .with_graceful_shutdown(shutdown_signal())
         .await
         .unwrap();
+
+    telemetry::shutdown();
+}
+
+async fn run_seed(db_store: &Store, media_storage: &LocalStorage, wipe: bool) {
+    match seed::seed(&db_store.connection, wipe).await {
+        Ok(report) if report.committed => {
+            info!(
+                "Seeded {} movies, {} quotes, {} interviews ({} records already present)",
+                report.movies.created,
+                report.quotes.created,
+                report.interviews.created,
+                report.movies.skipped + report.quotes.skipped + report.interviews.skipped
+            );
+            // The wiped records took the only links to the uploaded images
+            if wipe {
+                run_prune_media(db_store, media_storage, Duration::ZERO).await;
+            }
+        }
+        Ok(report) => {
+            eprintln!(
+                "Seed rolled back:\n{}",
+                serde_json::to_string_pretty(&report).unwrap_or_default()
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Seed failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn run_fetch_posters(db_store: &Store, media_storage: &LocalStorage) {
+    let storage: SharedStorage = Arc::new(media_storage.clone());
+    match media::fetch::fetch_posters(&db_store.connection, &storage).await {
+        Ok(report) => {
+            info!(
+                "Fetched {} posters into {} ({} failed)",
+                report.fetched,
+                media_storage.root().display(),
+                report.failed.len()
+            );
+            if !report.failed.is_empty() {
+                eprintln!(
+                    "Posters left as links:\n{}",
+                    serde_json::to_string_pretty(&report.failed).unwrap_or_default()
+                );
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Fetching posters failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn run_prune_media(db_store: &Store, media_storage: &LocalStorage, grace: Duration) {
+    let storage: SharedStorage = Arc::new(media_storage.clone());
+    match media::prune::prune_media(&db_store.connection, &storage, grace).await {
+        Ok(report) => {
+            info!(
+                "Removed {} unused media files from {} ({} kept, {} failed)",
+                report.removed,
+                media_storage.root().display(),
+                report.kept,
+                report.failed.len()
+            );
+            if !report.failed.is_empty() {
+                eprintln!(
+                    "Media files left behind:\n{}",
+                    serde_json::to_string_pretty(&report.failed).unwrap_or_default()
+                );
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Pruning media failed: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
 async fn shutdown_signal() {
@@ -117,25 +289,18 @@ struct Msg {
 }
 
 #[derive(Template)]
-#[template(path = "pages/home.html")]
-pub struct HomeTemplate;
-
-#[instrument]
-async fn home() -> Html<String> {
-    let home_template = HomeTemplate {}.render().unwrap();
-    info!("Welcome to Quentin Tarantino home page");
-    Html(home_template)
+#[template(path = "pages/home.html", blocks = ["title", "main"])]
+pub struct HomeTemplate {
+    pub locale: Locale,
 }
 
-#[derive(Template)]
-#[template(path = "pages/htmx/home.html")]
-pub struct HtmxHomeTemplate;
+htmx::impl_page!(HomeTemplate);
 
-#[instrument]
-async fn htmx_home() -> Html<String> {
-    let htmx_home_template = HtmxHomeTemplate {}.render().unwrap();
-    info!("Welcome to Quentin Tarantino htmx home page");
-    Html(htmx_home_template)
+#[instrument(skip_all)]
+async fn home(htmx: HtmxRequest, locale: Locale) -> Result<Response, WebError> {
+    let home_page = htmx.render(&HomeTemplate { locale })?;
+    info!("Welcome to Quentin Tarantino home page");
+    Ok(home_page)
 }
 
 #[instrument]
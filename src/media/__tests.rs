@@ -0,0 +1,464 @@
+use super::*;
+use crate::media::images::{ImageError, ImageType, THUMBNAIL_HEIGHT, THUMBNAIL_WIDTH, process};
+use crate::media::storage::{LocalStorage, MediaStorage, StorageError};
+use crate::movies::api::errors::MoviesApiError;
+use crate::movies::api::service::ApiService as MoviesApiService;
+use crate::test_support::{create_database, drop_database, movie_request, send};
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use image::{ImageFormat, RgbImage};
+use sqlx::PgPool;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn media_root(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", name, uuid::Uuid::new_v4().simple()))
+}
+
+/// A `width` by `height` picture encoded as `format`.
+fn picture(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    RgbImage::from_pixel(width, height, image::Rgb([229, 9, 20]))
+        .write_to(&mut Cursor::new(&mut bytes), format)
+        .unwrap();
+    bytes
+}
+
+/// A `multipart/form-data` body holding `bytes` as its `file` part.
+fn upload(method: &str, uri: &str, content_type: &str, bytes: &[u8]) -> Request<Body> {
+    let boundary = "tarantino-upload";
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"image\"\r\n\
+         Content-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn insert_movie(pool: &PgPool, title: &str, image_url: &str) -> i32 {
+    sqlx::query_scalar(
+        "INSERT INTO movie (title, release_year, plot, runtime, rating, release_date,
+                            image_url, youtube_id, production_details, budget)
+         VALUES ($1, 1997, 'A plot.', 154, 7.5, '1997-12-25', $2, '', '', '')
+         RETURNING id",
+    )
+    .bind(title)
+    .bind(image_url)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[test]
+fn thumbnails_and_keys_follow_the_media_urls() {
+    assert_eq!(
+        media_key("/media/posters/3-ab.png"),
+        Some("posters/3-ab.png")
+    );
+    assert_eq!(media_key("/media/"), None);
+    assert_eq!(media_key("https://example.com/media/x.png"), None);
+    assert_eq!(
+        thumbnail_key("posters/3-ab.png"),
+        "thumbnails/posters/3-ab.jpg"
+    );
+    assert_eq!(
+        thumbnail_url("/media/stills/3-ab.webp"),
+        "/media/thumbnails/stills/3-ab.jpg"
+    );
+    // Images on other hosts have no thumbnail of ours
+    assert_eq!(
+        thumbnail_url("https://example.com/poster.jpg"),
+        "https://example.com/poster.jpg"
+    );
+}
+
+#[tokio::test]
+async fn local_storage_stays_inside_its_root() {
+    let root = media_root("tarantino-media-keys");
+    let storage = LocalStorage::new(&root);
+
+    for key in [
+        "../escape.jpg",
+        "/etc/passwd",
+        "posters/../../escape.jpg",
+        "",
+    ] {
+        let error = storage.put(key, vec![1]).await.unwrap_err();
+        assert!(matches!(error, StorageError::InvalidKey(_)), "{}", key);
+    }
+    storage.put("posters/1.jpg", vec![1, 2]).await.unwrap();
+    assert_eq!(std::fs::read(root.join("posters/1.jpg")).unwrap(), [1, 2]);
+    storage.delete("posters/1.jpg").await.unwrap();
+    // Deleting twice is fine
+    storage.delete("posters/1.jpg").await.unwrap();
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn images_are_checked_against_their_content_type() {
+    let png = picture(600, 1200, ImageFormat::Png);
+
+    let image = process("image/png", png.clone()).unwrap();
+    assert_eq!(image.image_type, ImageType::Png);
+    let thumbnail =
+        image::load_from_memory_with_format(&image.thumbnail, ImageFormat::Jpeg).unwrap();
+    assert!(thumbnail.width() <= THUMBNAIL_WIDTH && thumbnail.height() <= THUMBNAIL_HEIGHT);
+    assert_eq!(thumbnail.height(), THUMBNAIL_HEIGHT);
+
+    assert!(matches!(
+        process("text/plain", png.clone()),
+        Err(ImageError::UnsupportedType(_))
+    ));
+    assert!(matches!(
+        process("image/jpeg", png.clone()),
+        Err(ImageError::Mismatch("JPEG"))
+    ));
+    assert!(matches!(
+        process("image/png", png[..png.len() / 2].to_vec()),
+        Err(ImageError::Unreadable(_))
+    ));
+    assert!(matches!(
+        process("image/png", vec![]),
+        Err(ImageError::Empty)
+    ));
+}
+
+#[tokio::test]
+async fn posters_and_stills_are_uploaded_to_the_media_storage() {
+    let name = "tarantinors_media_uploads";
+    let pool = create_database(name).await;
+    let root = media_root(name);
+    let storage: SharedStorage = Arc::new(LocalStorage::new(&root));
+    let router = rest_api_router(
+        &Store {
+            connection: pool.clone(),
+        },
+        &storage,
+    );
+    let movie_id = insert_movie(&pool, "Jackie Brown", "https://example.com/jb.jpg").await;
+    let png = picture(400, 600, ImageFormat::Png);
+
+    let poster = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{movie_id}/poster"),
+            "image/png",
+            &png,
+        ),
+    )
+    .await;
+    assert_eq!(poster.status, StatusCode::OK);
+    let poster = poster.json();
+    let image_url = poster["image_url"].as_str().unwrap().to_string();
+    let key = media_key(&image_url).unwrap();
+    assert!(key.starts_with(&format!("posters/{movie_id}-")) && key.ends_with(".png"));
+    assert_eq!(std::fs::read(root.join(key)).unwrap(), png);
+    assert!(root.join(thumbnail_key(key)).exists());
+    assert_eq!(poster["thumbnail_url"], thumbnail_url(&image_url));
+    let stored: String = sqlx::query_scalar("SELECT image_url FROM movie WHERE id = $1")
+        .bind(movie_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, image_url);
+
+    // A new poster replaces the files of the old one
+    let jpeg = picture(400, 600, ImageFormat::Jpeg);
+    let response = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{movie_id}/poster"),
+            "image/jpeg",
+            &jpeg,
+        ),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(!root.join(key).exists());
+    assert!(!root.join(thumbnail_key(key)).exists());
+
+    let error = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{movie_id}/poster"),
+            "text/plain",
+            b"hello",
+        ),
+    )
+    .await;
+    assert_eq!(error.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert!(
+        error.json()["message"]
+            .as_str()
+            .unwrap()
+            .contains("JPEG, PNG or WebP")
+    );
+    let response = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{movie_id}/poster"),
+            "image/jpeg",
+            &png,
+        ),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    let too_large = vec![0; images::MAX_IMAGE_BYTES + 1];
+    let response = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{movie_id}/poster"),
+            "image/png",
+            &too_large,
+        ),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::PAYLOAD_TOO_LARGE);
+    let response = send(
+        &router,
+        upload("PUT", "/movies/999/poster", "image/png", &png),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let still = send(
+        &router,
+        upload(
+            "POST",
+            &format!("/movies/{movie_id}/stills"),
+            "image/png",
+            &png,
+        ),
+    )
+    .await;
+    assert_eq!(still.status, StatusCode::CREATED);
+    let still = still.json();
+    let still_key = media_key(still["image_url"].as_str().unwrap())
+        .unwrap()
+        .to_string();
+    assert!(root.join(&still_key).exists());
+    let list = Request::builder()
+        .uri(format!("/movies/{movie_id}/stills"))
+        .body(Body::empty())
+        .unwrap();
+    let stills = send(&router, list).await;
+    assert_eq!(stills.status, StatusCode::OK);
+    assert_eq!(stills.json()["stills"].as_array().unwrap().len(), 1);
+    let remove = Request::builder()
+        .method("DELETE")
+        .uri(format!("/movies/{movie_id}/stills/{}", still["id"]))
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&router, remove).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(!root.join(&still_key).exists());
+    assert!(!root.join(thumbnail_key(&still_key)).exists());
+
+    drop_database(pool, name).await;
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn movies_only_use_the_media_files_issued_to_them() {
+    let name = "tarantinors_media_owners";
+    let pool = create_database(name).await;
+    let root = media_root(name);
+    let storage: SharedStorage = Arc::new(LocalStorage::new(&root));
+    let router = rest_api_router(
+        &Store {
+            connection: pool.clone(),
+        },
+        &storage,
+    );
+    let movies = MoviesApiService::new(&pool);
+    let jackie = movies
+        .create_movie(movie_request("Jackie Brown"))
+        .await
+        .unwrap();
+    let death_proof = movies
+        .create_movie(movie_request("Death Proof"))
+        .await
+        .unwrap();
+    let png = picture(400, 600, ImageFormat::Png);
+    let poster = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{jackie}/poster"),
+            "image/png",
+            &png,
+        ),
+    )
+    .await
+    .json();
+    let image_url = poster["image_url"].as_str().unwrap().to_string();
+    let key = media_key(&image_url).unwrap().to_string();
+
+    // Another movie cannot borrow the poster, on creation or on update
+    let mut borrowed = movie_request("Planet Terror");
+    borrowed.image_url = image_url.clone();
+    assert!(matches!(
+        movies.create_movie(borrowed.clone()).await,
+        Err(MoviesApiError::Validation(_))
+    ));
+    borrowed.title = "Death Proof".to_string();
+    assert!(matches!(
+        movies.update_movie(death_proof, borrowed).await,
+        Err(MoviesApiError::Validation(_))
+    ));
+    // Saving the movie again keeps its own poster
+    let mut kept = movie_request("Jackie Brown");
+    kept.image_url = image_url.clone();
+    movies.update_movie(jackie, kept).await.unwrap();
+
+    // A file some other record still points to survives its replacement
+    sqlx::query("UPDATE movie SET image_url = $1 WHERE id = $2")
+        .bind(&image_url)
+        .bind(death_proof)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = send(
+        &router,
+        upload(
+            "PUT",
+            &format!("/movies/{jackie}/poster"),
+            "image/png",
+            &png,
+        ),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(root.join(&key).exists());
+    assert!(root.join(thumbnail_key(&key)).exists());
+
+    drop_database(pool, name).await;
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn fetch_posters_copies_linked_posters_into_the_media_storage() {
+    let name = "tarantinors_media_fetch";
+    let pool = create_database(name).await;
+    let root = media_root(name);
+    let storage: SharedStorage = Arc::new(LocalStorage::new(&root));
+
+    let png = picture(300, 450, ImageFormat::Png);
+    let host = Router::new().route(
+        "/poster.png",
+        axum::routing::get(move || async move { ([(header::CONTENT_TYPE, "image/png")], png) }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let host_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, host).await.unwrap() });
+
+    let fetched = insert_movie(&pool, "Death Proof", &format!("{host_url}/poster.png")).await;
+    let missing = insert_movie(&pool, "Planet Terror", &format!("{host_url}/gone.png")).await;
+    insert_movie(&pool, "Kill Bill", "/media/posters/kill-bill.png").await;
+
+    let report = fetch::fetch_posters(&pool, &storage).await.unwrap();
+    assert_eq!(report.fetched, 1);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].movie_id, missing);
+    assert!(report.failed[0].reason.contains("404"));
+
+    let image_url: String = sqlx::query_scalar("SELECT image_url FROM movie WHERE id = $1")
+        .bind(fetched)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let key = media_key(&image_url).unwrap();
+    assert!(root.join(key).exists());
+    assert!(root.join(thumbnail_key(key)).exists());
+
+    drop_database(pool, name).await;
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn prune_media_removes_the_files_no_record_uses() {
+    let name = "tarantinors_media_prune";
+    let pool = create_database(name).await;
+    let root = media_root(name);
+    let storage: SharedStorage = Arc::new(LocalStorage::new(&root));
+
+    let kept = insert_movie(&pool, "Jackie Brown", "/media/posters/jb.png").await;
+    let deleted = insert_movie(&pool, "Death Proof", "/media/posters/dp.png").await;
+    sqlx::query("INSERT INTO movie_still (movie_id, image_url) VALUES ($1, $2), ($3, $4)")
+        .bind(kept)
+        .bind("/media/stills/jb.png")
+        .bind(deleted)
+        .bind("/media/stills/dp.png")
+        .execute(&pool)
+        .await
+        .unwrap();
+    for key in [
+        "posters/jb.png",
+        "posters/jb-old.png",
+        "posters/dp.png",
+        "stills/jb.png",
+        "stills/dp.png",
+    ] {
+        storage.put(key, vec![1]).await.unwrap();
+        storage.put(&thumbnail_key(key), vec![1]).await.unwrap();
+    }
+    // An upload still being written is never listed
+    std::fs::write(root.join("posters/upload.part"), [1]).unwrap();
+
+    // The old poster was replaced from the form; the other movie is deleted,
+    // taking its still along
+    sqlx::query("UPDATE movie SET image_url = 'https://example.com/jb.jpg' WHERE id = $1")
+        .bind(kept)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM movie WHERE id = $1")
+        .bind(deleted)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Fresh files might belong to an upload whose record is not saved yet
+    let report = prune::prune_media(&pool, &storage, Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert_eq!((report.removed, report.kept), (0, 10));
+
+    let report = prune::prune_media(&pool, &storage, Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!((report.removed, report.kept), (8, 2));
+    assert!(report.failed.is_empty());
+    let mut left: Vec<_> = storage
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|file| file.key)
+        .collect();
+    left.sort();
+    assert_eq!(left, vec!["stills/jb.png", "thumbnails/stills/jb.jpg"]);
+    assert!(root.join("posters/upload.part").exists());
+
+    drop_database(pool, name).await;
+    std::fs::remove_dir_all(root).unwrap();
+}
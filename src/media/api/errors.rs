@@ -0,0 +1,101 @@
+use crate::media::images::ImageError;
+use crate::media::storage::StorageError;
+use axum::{
+    Json,
+    extract::multipart::MultipartError,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use thiserror::Error;
+use tracing::error;
+use utoipa::ToSchema;
+
+#[derive(Serialize, ToSchema)]
+pub struct MediaApiErrorResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum MediaApiError {
+    #[error("movie not found")]
+    MovieNotFound(i32),
+
+    #[error("still not found")]
+    StillNotFound(i32),
+
+    #[error("Invalid upload: {0}")]
+    InvalidUpload(String),
+
+    #[error("Upload too large")]
+    UploadTooLarge,
+
+    #[error("Invalid image: {0}")]
+    Image(#[from] ImageError),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Image processing failed: {0}")]
+    Processing(#[from] tokio::task::JoinError),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<MultipartError> for MediaApiError {
+    fn from(err: MultipartError) -> Self {
+        match err.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => Self::UploadTooLarge,
+            _ => Self::InvalidUpload(err.body_text()),
+        }
+    }
+}
+
+impl MediaApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::MovieNotFound(_) | Self::StillNotFound(_) => StatusCode::NOT_FOUND,
+            Self::InvalidUpload(_) => StatusCode::BAD_REQUEST,
+            Self::UploadTooLarge | Self::Image(ImageError::TooLarge) => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Self::Image(ImageError::UnsupportedType(_)) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Image(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Storage(_) | Self::Processing(_) | Self::DatabaseError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    pub fn user_message(&self) -> String {
+        match self {
+            Self::MovieNotFound(id) => format!("Movie with id {} not found", id),
+            Self::StillNotFound(id) => format!("Still with id {} not found", id),
+            Self::InvalidUpload(reason) => format!("Invalid upload: {}", reason),
+            Self::UploadTooLarge => ImageError::TooLarge.to_string(),
+            Self::Image(e) => e.to_string(),
+            Self::Storage(_) | Self::Processing(_) => "The image could not be saved".to_string(),
+            Self::DatabaseError(_) => "A database error occurred".to_string(),
+        }
+    }
+
+    pub fn log_message(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl IntoResponse for MediaApiError {
+    fn into_response(self) -> Response {
+        let status_code = self.status_code();
+        let user_message = self.user_message();
+
+        error!(error = %self.log_message(), "{}", user_message);
+
+        let response = MediaApiErrorResponse {
+            message: user_message,
+        };
+
+        (status_code, Json(response)).into_response()
+    }
+}
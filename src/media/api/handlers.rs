@@ -0,0 +1,182 @@
+use crate::media::api::errors::{MediaApiError, MediaApiErrorResponse};
+use crate::media::api::models::{
+    ImageUpload, MediaMessage, PosterResponse, StillResponse, StillsResponse,
+};
+use crate::media::api::service::ApiService;
+use crate::media::images::MAX_IMAGE_BYTES;
+use crate::media::storage::SharedStorage;
+use crate::store::Store;
+
+use axum::Extension;
+use axum::Json;
+use axum::extract::Path;
+use axum::extract::multipart::{Multipart, MultipartRejection};
+use axum::http::StatusCode;
+use tracing::{info, instrument};
+use utoipa::OpenApi;
+
+/// The `file` part of an upload and its content type, refused as soon as it
+/// outgrows the largest image.
+async fn read_image(
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<(String, Vec<u8>), MediaApiError> {
+    let mut multipart =
+        multipart.map_err(|rejection| MediaApiError::InvalidUpload(rejection.body_text()))?;
+
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            if bytes.len() + chunk.len() > MAX_IMAGE_BYTES {
+                return Err(MediaApiError::UploadTooLarge);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        return Ok((content_type, bytes));
+    }
+
+    Err(MediaApiError::InvalidUpload(
+        "the form has no \"file\" part".to_string(),
+    ))
+}
+
+/// Upload a movie poster
+///
+/// Replaces the movie's `image_url` with the uploaded image, served under
+/// `/media`, and makes a thumbnail for the movie grid. A poster uploaded
+/// before is deleted.
+#[utoipa::path(
+    put,
+    path = "/movies/{movie_id}/poster",
+    request_body(content = ImageUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Poster saved", body = PosterResponse),
+        (status = 400, description = "Not a multipart form with a file part", body = MediaApiErrorResponse),
+        (status = 404, description = "Movie not found", body = MediaApiErrorResponse),
+        (status = 413, description = "Image larger than 10 MB", body = MediaApiErrorResponse),
+        (status = 415, description = "Not a JPEG, PNG or WebP image", body = MediaApiErrorResponse),
+        (status = 422, description = "Image unreadable or not of its content type", body = MediaApiErrorResponse),
+        (status = 500, description = "Internal server error", body = MediaApiErrorResponse)
+    ),
+    tag = "Media API"
+)]
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn upload_poster(
+    Extension(store): Extension<Store>,
+    Extension(storage): Extension<SharedStorage>,
+    Path(movie_id): Path<i32>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<Json<PosterResponse>, MediaApiError> {
+    let (content_type, bytes) = read_image(multipart).await?;
+
+    let service = ApiService::new(&store.connection, &storage);
+    let poster = service.set_poster(movie_id, &content_type, bytes).await?;
+
+    info!("saved poster {} of movie {}", poster.image_url, movie_id);
+    Ok(Json(poster))
+}
+
+/// Upload a movie still
+#[utoipa::path(
+    post,
+    path = "/movies/{movie_id}/stills",
+    request_body(content = ImageUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Still saved", body = StillResponse),
+        (status = 400, description = "Not a multipart form with a file part", body = MediaApiErrorResponse),
+        (status = 404, description = "Movie not found", body = MediaApiErrorResponse),
+        (status = 413, description = "Image larger than 10 MB", body = MediaApiErrorResponse),
+        (status = 415, description = "Not a JPEG, PNG or WebP image", body = MediaApiErrorResponse),
+        (status = 422, description = "Image unreadable or not of its content type", body = MediaApiErrorResponse),
+        (status = 500, description = "Internal server error", body = MediaApiErrorResponse)
+    ),
+    tag = "Media API"
+)]
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn upload_still(
+    Extension(store): Extension<Store>,
+    Extension(storage): Extension<SharedStorage>,
+    Path(movie_id): Path<i32>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Result<(StatusCode, Json<StillResponse>), MediaApiError> {
+    let (content_type, bytes) = read_image(multipart).await?;
+
+    let service = ApiService::new(&store.connection, &storage);
+    let still = service.add_still(movie_id, &content_type, bytes).await?;
+
+    info!("saved still {} of movie {}", still.id, movie_id);
+    Ok((StatusCode::CREATED, Json(still)))
+}
+
+/// Get the stills of a movie
+#[utoipa::path(
+    get,
+    path = "/movies/{movie_id}/stills",
+    responses(
+        (status = 200, description = "Stills of the movie", body = StillsResponse),
+        (status = 404, description = "Movie not found", body = MediaApiErrorResponse),
+        (status = 500, description = "Database server error", body = MediaApiErrorResponse)
+    ),
+    tag = "Media API"
+)]
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn get_stills(
+    Extension(store): Extension<Store>,
+    Extension(storage): Extension<SharedStorage>,
+    Path(movie_id): Path<i32>,
+) -> Result<Json<StillsResponse>, MediaApiError> {
+    let service = ApiService::new(&store.connection, &storage);
+    let stills = service.get_stills(movie_id).await?;
+
+    info!(
+        "queried {} stills of movie {}",
+        stills.stills.len(),
+        movie_id
+    );
+    Ok(Json(stills))
+}
+
+/// Delete a movie still and its files
+#[utoipa::path(
+    delete,
+    path = "/movies/{movie_id}/stills/{still_id}",
+    responses(
+        (status = 200, description = "Still deleted", body = MediaMessage),
+        (status = 404, description = "Still not found", body = MediaApiErrorResponse),
+        (status = 500, description = "Database server error", body = MediaApiErrorResponse)
+    ),
+    tag = "Media API"
+)]
+#[instrument(skip_all, fields(movie_id = movie_id, still_id = still_id))]
+pub async fn remove_still(
+    Extension(store): Extension<Store>,
+    Extension(storage): Extension<SharedStorage>,
+    Path((movie_id, still_id)): Path<(i32, i32)>,
+) -> Result<Json<MediaMessage>, MediaApiError> {
+    let service = ApiService::new(&store.connection, &storage);
+    service.delete_still(movie_id, still_id).await?;
+
+    let message = format!("Still {still_id} of movie {movie_id} deleted");
+
+    info!(%message);
+    Ok(Json(MediaMessage { message }))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(upload_poster, upload_still, get_stills, remove_still),
+    components(schemas(
+        ImageUpload,
+        PosterResponse,
+        StillResponse,
+        StillsResponse,
+        MediaMessage,
+        MediaApiErrorResponse
+    ))
+)]
+pub struct MediaApiDoc;
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The `multipart/form-data` body of an upload, for the API docs only: the
+/// handlers read the parts as they stream in.
+#[derive(Debug, ToSchema)]
+#[allow(dead_code)]
+pub struct ImageUpload {
+    /// A JPEG, PNG or WebP image of at most 10 MB
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct PosterResponse {
+    pub movie_id: i32,
+    pub image_url: String,
+    /// Grid-sized JPEG of the poster
+    pub thumbnail_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct StillResponse {
+    pub id: i32,
+    pub movie_id: i32,
+    pub image_url: String,
+    /// Grid-sized JPEG of the still
+    pub thumbnail_url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct StillsResponse {
+    pub stills: Vec<StillResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MediaMessage {
+    pub message: String,
+}
@@ -0,0 +1,170 @@
+use crate::media::api::errors::MediaApiError;
+use crate::media::api::models::{PosterResponse, StillResponse, StillsResponse};
+use crate::media::data::entities::MovieStill;
+use crate::media::data::repository::MediaRepository;
+use crate::media::images;
+use crate::media::storage::SharedStorage;
+use crate::media::{media_key, media_url, thumbnail_key, thumbnail_url};
+
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+pub struct ApiService {
+    repo: MediaRepository,
+    storage: SharedStorage,
+}
+
+impl ApiService {
+    pub fn new(pool: &PgPool, storage: &SharedStorage) -> Self {
+        Self {
+            repo: MediaRepository::new(pool),
+            storage: storage.clone(),
+        }
+    }
+
+    /// Saves a new poster for the movie and drops the files of the one it
+    /// replaces.
+    pub async fn set_poster(
+        &self,
+        movie_id: i32,
+        content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<PosterResponse, MediaApiError> {
+        if !self.repo.movie_exists(movie_id).await? {
+            return Err(MediaApiError::MovieNotFound(movie_id));
+        }
+
+        let key = self
+            .save(&format!("posters/{}", movie_id), content_type, bytes)
+            .await?;
+        let image_url = media_url(&key);
+
+        match self.repo.set_movie_image_url(movie_id, &image_url).await {
+            Ok(Some(previous)) => self.remove(&previous).await,
+            Ok(None) => {
+                self.remove(&image_url).await;
+                return Err(MediaApiError::MovieNotFound(movie_id));
+            }
+            Err(e) => {
+                self.remove(&image_url).await;
+                return Err(e.into());
+            }
+        }
+
+        Ok(PosterResponse {
+            movie_id,
+            thumbnail_url: thumbnail_url(&image_url),
+            image_url,
+        })
+    }
+
+    pub async fn add_still(
+        &self,
+        movie_id: i32,
+        content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<StillResponse, MediaApiError> {
+        if !self.repo.movie_exists(movie_id).await? {
+            return Err(MediaApiError::MovieNotFound(movie_id));
+        }
+
+        let key = self
+            .save(&format!("stills/{}", movie_id), content_type, bytes)
+            .await?;
+        let image_url = media_url(&key);
+
+        match self.repo.create_still(movie_id, &image_url).await {
+            Ok(still) => Ok(still_response(still)),
+            Err(e) => {
+                self.remove(&image_url).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    pub async fn get_stills(&self, movie_id: i32) -> Result<StillsResponse, MediaApiError> {
+        if !self.repo.movie_exists(movie_id).await? {
+            return Err(MediaApiError::MovieNotFound(movie_id));
+        }
+
+        let stills = self.repo.get_movie_stills(movie_id).await?;
+
+        Ok(StillsResponse {
+            stills: stills.into_iter().map(still_response).collect(),
+        })
+    }
+
+    pub async fn delete_still(&self, movie_id: i32, still_id: i32) -> Result<(), MediaApiError> {
+        let still = self
+            .repo
+            .delete_still(movie_id, still_id)
+            .await?
+            .ok_or(MediaApiError::StillNotFound(still_id))?;
+
+        self.remove(&still.image_url).await;
+
+        Ok(())
+    }
+
+    /// Checks the image, then stores it and its thumbnail under a new key
+    /// starting with `prefix`.
+    async fn save(
+        &self,
+        prefix: &str,
+        content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<String, MediaApiError> {
+        let content_type = content_type.to_string();
+        let image =
+            tokio::task::spawn_blocking(move || images::process(&content_type, bytes)).await??;
+
+        let key = format!(
+            "{}-{}.{}",
+            prefix,
+            Uuid::new_v4().simple(),
+            image.image_type.extension()
+        );
+        self.storage
+            .put(&thumbnail_key(&key), image.thumbnail)
+            .await?;
+        if let Err(e) = self.storage.put(&key, image.bytes).await {
+            self.remove(&media_url(&key)).await;
+            return Err(e.into());
+        }
+
+        Ok(key)
+    }
+
+    /// Deletes the files of an image no record points to any more; links to
+    /// other hosts and files still in use are left alone. A file that stays
+    /// behind is only wasted space, so failures are logged, not returned.
+    async fn remove(&self, url: &str) {
+        let Some(key) = media_key(url) else {
+            return;
+        };
+        match self.repo.image_url_in_use(url).await {
+            Ok(false) => {}
+            Ok(true) => return,
+            Err(e) => {
+                warn!(key, error = %e, "could not check whether a media file is in use");
+                return;
+            }
+        }
+
+        for key in [thumbnail_key(key), key.to_string()] {
+            if let Err(e) = self.storage.delete(&key).await {
+                warn!(key, error = %e, "could not delete media file");
+            }
+        }
+    }
+}
+
+fn still_response(still: MovieStill) -> StillResponse {
+    StillResponse {
+        id: still.id,
+        movie_id: still.movie_id,
+        thumbnail_url: thumbnail_url(&still.image_url),
+        image_url: still.image_url,
+    }
+}
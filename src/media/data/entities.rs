@@ -0,0 +1,11 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MovieStill {
+    pub id: i32,
+    pub movie_id: i32,
+    pub image_url: String,
+    pub created_at: NaiveDateTime,
+}
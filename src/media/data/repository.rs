@@ -0,0 +1,169 @@
+use crate::media::data::entities::MovieStill;
+use sqlx::PgPool;
+
+use crate::store::traced_query;
+
+pub struct MediaRepository {
+    pub pool: PgPool,
+}
+
+impl MediaRepository {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn movie_exists(&self, movie_id: i32) -> Result<bool, sqlx::Error> {
+        let exists = traced_query(
+            "movie_exists",
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (SELECT 1 FROM movie WHERE id = $1) AS "exists!"
+                "#,
+                movie_id
+            )
+            .fetch_one(&self.pool),
+        )
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Points the movie's poster at `image_url`, returning the address it
+    /// replaced, or `None` when there is no such movie.
+    pub async fn set_movie_image_url(
+        &self,
+        movie_id: i32,
+        image_url: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        traced_query(
+            "set_movie_image_url",
+            sqlx::query_scalar!(
+                r#"
+                UPDATE movie
+                SET image_url = $2
+                FROM (SELECT id, image_url FROM movie WHERE id = $1 FOR UPDATE) AS previous
+                WHERE movie.id = previous.id
+                RETURNING previous.image_url
+                "#,
+                movie_id,
+                image_url
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    /// The poster and still addresses of every movie, whatever their host.
+    pub async fn get_image_urls(&self) -> Result<Vec<String>, sqlx::Error> {
+        traced_query(
+            "get_image_urls",
+            sqlx::query_scalar!(
+                r#"
+                SELECT image_url AS "image_url!" FROM movie
+                UNION
+                SELECT image_url FROM movie_still
+                "#
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// Whether a poster or still still points at `image_url`.
+    pub async fn image_url_in_use(&self, image_url: &str) -> Result<bool, sqlx::Error> {
+        traced_query(
+            "image_url_in_use",
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM movie WHERE image_url = $1
+                    UNION ALL
+                    SELECT 1 FROM movie_still WHERE image_url = $1
+                ) AS "in_use!"
+                "#,
+                image_url
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    /// Movies whose poster is still a link to another site.
+    pub async fn get_external_posters(&self) -> Result<Vec<(i32, String)>, sqlx::Error> {
+        let posters = traced_query(
+            "get_external_posters",
+            sqlx::query!(
+                r#"
+                SELECT id, image_url FROM movie
+                WHERE image_url ILIKE 'http://%' OR image_url ILIKE 'https://%'
+                ORDER BY id ASC
+                "#
+            )
+            .fetch_all(&self.pool),
+        )
+        .await?;
+
+        Ok(posters
+            .into_iter()
+            .map(|poster| (poster.id, poster.image_url))
+            .collect())
+    }
+
+    pub async fn create_still(
+        &self,
+        movie_id: i32,
+        image_url: &str,
+    ) -> Result<MovieStill, sqlx::Error> {
+        traced_query(
+            "create_still",
+            sqlx::query_as!(
+                MovieStill,
+                r#"
+                INSERT INTO movie_still (movie_id, image_url) VALUES ($1, $2)
+                RETURNING *
+                "#,
+                movie_id,
+                image_url
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn get_movie_stills(&self, movie_id: i32) -> Result<Vec<MovieStill>, sqlx::Error> {
+        traced_query(
+            "get_movie_stills",
+            sqlx::query_as!(
+                MovieStill,
+                r#"
+                SELECT * FROM movie_still WHERE movie_id = $1 ORDER BY id ASC
+                "#,
+                movie_id
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// Deletes a still of the movie, returning it so its files can go too.
+    pub async fn delete_still(
+        &self,
+        movie_id: i32,
+        still_id: i32,
+    ) -> Result<Option<MovieStill>, sqlx::Error> {
+        traced_query(
+            "delete_still",
+            sqlx::query_as!(
+                MovieStill,
+                r#"
+                DELETE FROM movie_still WHERE id = $1 AND movie_id = $2
+                RETURNING *
+                "#,
+                still_id,
+                movie_id
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+}
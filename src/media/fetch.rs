@@ -0,0 +1,113 @@
+//! `tarantinors fetch-posters`: copies the posters still linked from other
+//! hosts into the media storage, so the grid keeps its images when those hosts
+//! go away. Downloads go through the same checks as uploads.
+
+use crate::media::api::service::ApiService;
+use crate::media::data::repository::MediaRepository;
+use crate::media::images::{ImageError, MAX_IMAGE_BYTES};
+use crate::media::storage::SharedStorage;
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Time allowed for each download.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Default, Serialize)]
+pub struct FetchReport {
+    /// Posters now in the media storage
+    pub fetched: usize,
+    /// Posters left as links, with the reason
+    pub failed: Vec<FetchFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FetchFailure {
+    pub movie_id: i32,
+    pub url: String,
+    pub reason: String,
+}
+
+/// Downloads the poster of every movie whose `image_url` is on another host
+/// and points the movie at the stored copy. A poster that cannot be fetched
+/// keeps its link and is reported; only database errors stop the run.
+pub async fn fetch_posters(
+    pool: &PgPool,
+    storage: &SharedStorage,
+) -> Result<FetchReport, sqlx::Error> {
+    let posters = MediaRepository::new(pool).get_external_posters().await?;
+    let service = ApiService::new(pool, storage);
+    let client = reqwest::Client::new();
+    let mut report = FetchReport::default();
+
+    for (movie_id, url) in posters {
+        let saved = match download(&client, &url).await {
+            Ok((content_type, bytes)) => service
+                .set_poster(movie_id, &content_type, bytes)
+                .await
+                .map_err(|e| e.user_message()),
+            Err(reason) => Err(reason),
+        };
+
+        match saved {
+            Ok(poster) => {
+                info!(
+                    movie_id,
+                    url,
+                    image_url = poster.image_url,
+                    "fetched poster"
+                );
+                report.fetched += 1;
+            }
+            Err(reason) => {
+                warn!(movie_id, url, reason, "could not fetch poster");
+                report.failed.push(FetchFailure {
+                    movie_id,
+                    url,
+                    reason,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// The body and content type at `url`, given up on as soon as it outgrows the
+/// largest image.
+async fn download(client: &reqwest::Client, url: &str) -> Result<(String, Vec<u8>), String> {
+    let mut response = client
+        .get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+
+    let too_large = ImageError::TooLarge.to_string();
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_IMAGE_BYTES as u64)
+    {
+        return Err(too_large);
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if bytes.len() + chunk.len() > MAX_IMAGE_BYTES {
+            return Err(too_large);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok((content_type, bytes))
+}
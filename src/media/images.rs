@@ -0,0 +1,129 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::{ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use thiserror::Error;
+
+/// Largest accepted upload.
+pub const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Largest accepted side, so a small file cannot unpack into gigabytes.
+const MAX_IMAGE_SIDE: u32 = 10_000;
+
+/// Box the grid thumbnails fit in, the 2:3 of a poster.
+pub const THUMBNAIL_WIDTH: u32 = 300;
+pub const THUMBNAIL_HEIGHT: u32 = 450;
+
+const THUMBNAIL_QUALITY: u8 = 80;
+
+#[derive(Debug, Error)]
+pub enum ImageError {
+    #[error("the image is empty")]
+    Empty,
+
+    #[error("the image is larger than {} MB", MAX_IMAGE_BYTES / 1024 / 1024)]
+    TooLarge,
+
+    #[error("unsupported content type {0:?}; send a JPEG, PNG or WebP image")]
+    UnsupportedType(String),
+
+    #[error("the file is not the {0} image its content type announces")]
+    Mismatch(&'static str),
+
+    #[error("the image cannot be read: {0}")]
+    Unreadable(String),
+}
+
+/// The image formats accepted for posters and stills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageType {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl ImageType {
+    /// The type of a `Content-Type` value, parameters aside.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        match essence.to_ascii_lowercase().as_str() {
+            "image/jpeg" | "image/jpg" | "image/pjpeg" => Some(Self::Jpeg),
+            "image/png" => Some(Self::Png),
+            "image/webp" => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "JPEG",
+            Self::Png => "PNG",
+            Self::Webp => "WebP",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+
+    fn format(&self) -> ImageFormat {
+        match self {
+            Self::Jpeg => ImageFormat::Jpeg,
+            Self::Png => ImageFormat::Png,
+            Self::Webp => ImageFormat::WebP,
+        }
+    }
+}
+
+/// A checked image and its JPEG thumbnail.
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub image_type: ImageType,
+    pub bytes: Vec<u8>,
+    pub thumbnail: Vec<u8>,
+}
+
+/// Checks that `bytes` are a whole image of an accepted type, the one
+/// `content_type` announces, and shrinks it to a thumbnail. Decoding is CPU
+/// bound; run it off the async workers.
+pub fn process(content_type: &str, bytes: Vec<u8>) -> Result<ProcessedImage, ImageError> {
+    if bytes.is_empty() {
+        return Err(ImageError::Empty);
+    }
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(ImageError::TooLarge);
+    }
+
+    let image_type = ImageType::from_content_type(content_type)
+        .ok_or_else(|| ImageError::UnsupportedType(content_type.to_string()))?;
+    // The announced type is the client's word; the first bytes tell
+    if image::guess_format(&bytes).ok() != Some(image_type.format()) {
+        return Err(ImageError::Mismatch(image_type.name()));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_SIDE);
+    limits.max_image_height = Some(MAX_IMAGE_SIDE);
+    let mut reader = ImageReader::with_format(Cursor::new(&bytes), image_type.format());
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|e| ImageError::Unreadable(e.to_string()))?;
+
+    let thumbnail = image
+        .thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+        .into_rgb8();
+    let mut thumbnail_bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut thumbnail_bytes, THUMBNAIL_QUALITY)
+        .encode_image(&thumbnail)
+        .map_err(|e| ImageError::Unreadable(e.to_string()))?;
+
+    Ok(ProcessedImage {
+        image_type,
+        bytes,
+        thumbnail: thumbnail_bytes,
+    })
+}
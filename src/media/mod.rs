@@ -0,0 +1,83 @@
+//! Posters and stills kept by the site instead of linked from other hosts.
+//! Uploads are checked, shrunk to a JPEG thumbnail for the grid and saved in
+//! a [`MediaStorage`](storage::MediaStorage), whose files are served under
+//! [`MEDIA_URL`]. The thumbnail of a file is found from its key, so records
+//! only keep the address of the full image.
+
+pub mod api;
+pub mod data;
+pub mod fetch;
+pub mod images;
+pub mod prune;
+pub mod storage;
+
+#[cfg(test)]
+mod __tests;
+
+use axum::{
+    Extension, Router,
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post, put},
+};
+
+use crate::media::images::MAX_IMAGE_BYTES;
+use crate::media::storage::SharedStorage;
+use crate::store::Store;
+
+/// Where the files of the media storage are served.
+pub const MEDIA_URL: &str = "/media";
+
+/// Room for the multipart framing around the largest image.
+const UPLOAD_BODY_LIMIT: usize = MAX_IMAGE_BYTES + 64 * 1024;
+
+/// The address the file of `key` is served at.
+pub fn media_url(key: &str) -> String {
+    format!("{}/{}", MEDIA_URL, key)
+}
+
+/// The key of a file of the media storage from its address, `None` for
+/// images on other hosts.
+pub fn media_key(url: &str) -> Option<&str> {
+    url.strip_prefix(MEDIA_URL)
+        .and_then(|path| path.strip_prefix('/'))
+        .filter(|key| !key.is_empty())
+}
+
+/// The key of the thumbnail of the image saved under `key`:
+/// `posters/3-1f0c.png` has `thumbnails/posters/3-1f0c.jpg`.
+pub fn thumbnail_key(key: &str) -> String {
+    let stem = match key.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => stem,
+        _ => key,
+    };
+    format!("thumbnails/{}.jpg", stem)
+}
+
+/// The grid-sized version of an image: the thumbnail of our own files, the
+/// image itself when it is on another host.
+pub fn thumbnail_url(url: &str) -> String {
+    match media_key(url) {
+        Some(key) => media_url(&thumbnail_key(key)),
+        None => url.to_string(),
+    }
+}
+
+pub fn rest_api_router(media_store: &Store, storage: &SharedStorage) -> Router {
+    Router::new()
+        .route(
+            "/movies/{movie_id}/poster",
+            put(api::handlers::upload_poster),
+        )
+        .route("/movies/{movie_id}/stills", get(api::handlers::get_stills))
+        .route(
+            "/movies/{movie_id}/stills",
+            post(api::handlers::upload_still),
+        )
+        .route(
+            "/movies/{movie_id}/stills/{still_id}",
+            delete(api::handlers::remove_still),
+        )
+        .layer(DefaultBodyLimit::max(UPLOAD_BODY_LIMIT))
+        .layer(Extension(media_store.clone()))
+        .layer(Extension(storage.clone()))
+}
@@ -0,0 +1,89 @@
+//! `tarantinors prune-media`: deletes the files of the media storage that no
+//! movie or still points to any more. Deleting a movie, changing its
+//! `image_url` from the form or the JSON API, and `seed --wipe` only change
+//! the database, so the files they drop stay behind until this runs.
+
+use crate::media::data::repository::MediaRepository;
+use crate::media::storage::{SharedStorage, StorageError};
+use crate::media::{media_key, thumbnail_key};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Files written more recently are kept: an upload is saved before the record
+/// that points to it.
+pub const PRUNE_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Error)]
+pub enum PruneError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    #[error("{0}")]
+    StorageError(#[from] StorageError),
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PruneReport {
+    /// Files deleted
+    pub removed: usize,
+    /// Files still in use, or too recent to tell
+    pub kept: usize,
+    /// Files that could not be deleted, with the reason
+    pub failed: Vec<PruneFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PruneFailure {
+    pub key: String,
+    pub reason: String,
+}
+
+/// Deletes every stored file older than `grace` that is neither the image of
+/// a movie or still nor its thumbnail. A file that cannot be deleted is
+/// reported; only listing the files or reading the database stops the run.
+pub async fn prune_media(
+    pool: &PgPool,
+    storage: &SharedStorage,
+    grace: Duration,
+) -> Result<PruneReport, PruneError> {
+    // Listed first, so a file uploaded meanwhile is either too recent or
+    // already has its record
+    let files = storage.list().await?;
+    let used: HashSet<String> = MediaRepository::new(pool)
+        .get_image_urls()
+        .await?
+        .iter()
+        .filter_map(|url| media_key(url))
+        .flat_map(|key| [key.to_string(), thumbnail_key(key)])
+        .collect();
+
+    let now = SystemTime::now();
+    let mut report = PruneReport::default();
+    for file in files {
+        let age = now.duration_since(file.modified).unwrap_or_default();
+        if used.contains(&file.key) || age < grace {
+            report.kept += 1;
+            continue;
+        }
+
+        match storage.delete(&file.key).await {
+            Ok(()) => {
+                info!(key = file.key, "removed unused media file");
+                report.removed += 1;
+            }
+            Err(e) => {
+                warn!(key = file.key, error = %e, "could not remove media file");
+                report.failed.push(PruneFailure {
+                    key: file.key,
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
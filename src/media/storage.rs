@@ -0,0 +1,157 @@
+use futures::future::BoxFuture;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use thiserror::Error;
+use tower_http::services::ServeDir;
+
+/// Directory of the local storage when `MEDIA_ROOT` is not set.
+const DEFAULT_MEDIA_ROOT: &str = "media";
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("invalid media key {0:?}")]
+    InvalidKey(String),
+
+    #[error("media storage I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Somewhere to keep uploaded files, named by keys such as
+/// `posters/3-1f0c….jpg`. Whatever the backend, the file of a key is served at
+/// [`media_url`](super::media_url) of that key.
+pub trait MediaStorage: Send + Sync + std::fmt::Debug {
+    /// Saves `bytes` under `key`, replacing what was there.
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    /// Removes `key`; removing a missing file is not an error.
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    /// Every stored file, thumbnails included, but not uploads still being
+    /// written.
+    fn list(&self) -> BoxFuture<'_, Result<Vec<StoredFile>, StorageError>>;
+}
+
+/// A file of the storage and when it was last written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredFile {
+    pub key: String,
+    pub modified: SystemTime,
+}
+
+pub type SharedStorage = Arc<dyn MediaStorage>;
+
+/// Files in a directory on the server.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The directory named by `MEDIA_ROOT`, `./media` by default.
+    pub fn from_env() -> Self {
+        let root = std::env::var("MEDIA_ROOT").unwrap_or_else(|_| DEFAULT_MEDIA_ROOT.to_string());
+        Self::new(root)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Serves the files, to be nested at [`MEDIA_URL`](super::MEDIA_URL).
+    pub fn service(&self) -> ServeDir {
+        ServeDir::new(&self.root)
+    }
+
+    /// The file of `key`, refusing keys that would leave the root.
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let relative = Path::new(key);
+        let is_plain = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_plain {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+impl MediaStorage for LocalStorage {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+
+            // Written aside first so the file is never served half written
+            let partial = path.with_extension("part");
+            tokio::fs::write(&partial, bytes).await?;
+            tokio::fs::rename(&partial, &path).await?;
+
+            Ok(())
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.path(key)?).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn list(&self) -> BoxFuture<'_, Result<Vec<StoredFile>, StorageError>> {
+        Box::pin(async move {
+            let mut files = Vec::new();
+            let mut dirs = vec![self.root.clone()];
+
+            while let Some(dir) = dirs.pop() {
+                let mut entries = match tokio::fs::read_dir(&dir).await {
+                    Ok(entries) => entries,
+                    // Nothing was uploaded yet
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e.into()),
+                };
+
+                while let Some(entry) = entries.next_entry().await? {
+                    let path = entry.path();
+                    let metadata = entry.metadata().await?;
+                    if metadata.is_dir() {
+                        dirs.push(path);
+                        continue;
+                    }
+                    if path
+                        .extension()
+                        .is_some_and(|extension| extension == "part")
+                    {
+                        continue;
+                    }
+
+                    let Ok(relative) = path.strip_prefix(&self.root) else {
+                        continue;
+                    };
+                    let key = relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    files.push(StoredFile {
+                        key,
+                        modified: metadata.modified()?,
+                    });
+                }
+            }
+
+            files.sort_by(|a, b| a.key.cmp(&b.key));
+            Ok(files)
+        })
+    }
+}
@@ -17,12 +17,18 @@ pub enum MoviesApiError {
     #[error("movie not found")]
     MovieNotFound(i32),
 
+    #[error("movie title taken")]
+    TitleTaken(String),
+
     #[error("Persons not found")]
     NoPersonsFoundForRole(String, i32),
 
     #[error("Validation failed")]
     Validation(#[from] ValidationErrors),
 
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+
     #[error("Database error: {0}")]
     DatabaseError(#[from] sqlx::Error),
 
@@ -47,8 +53,10 @@ impl MoviesApiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::MovieNotFound(_) => StatusCode::NOT_FOUND,
+            Self::TitleTaken(_) => StatusCode::CONFLICT,
             Self::NoPersonsFoundForRole(_, _) => StatusCode::NOT_FOUND,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidQuery(_) => StatusCode::BAD_REQUEST,
             Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -57,7 +65,9 @@ impl MoviesApiError {
     pub fn message(&self) -> String {
         match self {
             Self::MovieNotFound(id) => format!("Movie with ID {} not found", id),
+            Self::TitleTaken(title) => format!("Another movie is titled '{}'", title),
             Self::Validation(_) => "Request validation failed".to_string(),
+            Self::InvalidQuery(reason) => reason.clone(),
             Self::NoPersonsFoundForRole(role, movie_id) => format!(
                 "Requested persons of role '{}' for movie id({}) not found",
                 role, movie_id
@@ -70,6 +80,7 @@ impl MoviesApiError {
     pub fn details(&self) -> Option<ValidationDetails> {
         match self {
             Self::MovieNotFound(_) => None,
+            Self::TitleTaken(_) => None,
             Self::Validation(errors) => {
                 let field_errors = self.extract_field_errors(&errors);
                 let error_count = field_errors.values().map(|v| v.len()).sum();
@@ -80,6 +91,7 @@ impl MoviesApiError {
                 })
             }
             Self::NoPersonsFoundForRole(_, _) => None,
+            Self::InvalidQuery(_) => None,
             Self::DatabaseError(_) => None,
             Self::InternalError(_) => None,
         }
@@ -118,4 +130,3 @@ impl IntoResponse for MoviesApiError {
         (status, Json(response)).into_response()
     }
 }
-
@@ -1,9 +1,10 @@
 use crate::movies::api::errors::{ApiErrorResponse, MoviesApiError};
-use crate::movies::api::extractors::ValidatedJson;
+use crate::movies::api::extractors::{ValidatedJson, ValidatedQuery};
 use crate::movies::api::models::{
     Actors, CreateMovieAwardNominationRequest, CreateMovieAwardRequest, CreateMovieRequest,
     Directors, MovieAwardNominationResponse, MovieAwardResponse, MovieAwardsResponse,
-    MovieListResponse, MovieNominationsResponse, MovieResponse, MoviesMessage, Producers, Writers,
+    MovieListParams, MovieListResponse, MovieNominationsResponse, MovieResponse, MoviesMessage,
+    Producers, Writers,
 };
 use crate::movies::api::service::ApiService;
 
@@ -14,7 +15,7 @@ use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 
-use tracing::{error, info, instrument};
+use tracing::{Span, error, info, instrument};
 use utoipa::OpenApi;
 
 /// Create a new movie
@@ -23,24 +24,60 @@ use utoipa::OpenApi;
     path = "/movies",
     request_body = CreateMovieRequest,
     responses(
-        (status = 201, description = "Movie Created", body = CreateMovieRequest),
+        (status = 201, description = "Movie Created", body = MoviesMessage),
         (status = 400, description = "Request Validation Error", body = ApiErrorResponse),
+        (status = 409, description = "Title used by another movie", body = ApiErrorResponse),
         (status = 500, description = "Internal server error", body = ApiErrorResponse)
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all)]
 pub async fn add_movie(
     Extension(store): Extension<Store>,
     ValidatedJson(new_movie): ValidatedJson<CreateMovieRequest>,
 ) -> Result<impl IntoResponse, MoviesApiError> {
     let movie_title = new_movie.title.clone();
     let service = ApiService::new(&store.connection);
-    let _ = service.create_movie(new_movie).await?;
+    let id = service.create_movie(new_movie).await?;
 
     let message = format!("Movie '{}' added ", movie_title);
     info!(%message);
-    Ok((StatusCode::CREATED, Json(MoviesMessage { message })))
+    Ok((
+        StatusCode::CREATED,
+        Json(MoviesMessage {
+            message,
+            id: Some(id),
+        }),
+    ))
+}
+
+/// Replace a movie with its people, awards and nominations
+#[utoipa::path(
+    put,
+    path = "/movies/{movie_id}",
+    request_body = CreateMovieRequest,
+    responses(
+        (status = 200, description = "Movie Updated", body = MoviesMessage),
+        (status = 400, description = "Request Validation Error", body = ApiErrorResponse),
+        (status = 404, description = "Movie not found", body = ApiErrorResponse),
+        (status = 409, description = "Title used by another movie", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    ),
+    tag = "Movies API"
+)]
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn update_movie(
+    Extension(store): Extension<Store>,
+    Path(movie_id): Path<i32>,
+    ValidatedJson(movie): ValidatedJson<CreateMovieRequest>,
+) -> Result<Json<MoviesMessage>, MoviesApiError> {
+    let movie_title = movie.title.clone();
+    let service = ApiService::new(&store.connection);
+    service.update_movie(movie_id, movie).await?;
+
+    let message = format!("Movie '{}' updated", movie_title);
+    info!(%message);
+    Ok(Json(MoviesMessage { message, id: None }))
 }
 
 /// Get movie by ID
@@ -54,7 +91,7 @@ pub async fn add_movie(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id))]
 pub async fn get_movie(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
@@ -72,25 +109,29 @@ pub async fn get_movie(
     Ok(Json(movie))
 }
 
-/// Get list of movies
+/// Get list of movies, optionally filtered, sorted and paginated
 #[utoipa::path(
     get,
     path = "/movies",
+    params(MovieListParams),
     responses(
         (status = 200, description = "List of Movies", body = MovieListResponse),
+        (status = 400, description = "Invalid query parameters", body = ApiErrorResponse),
         (status = 500, description = "Database server error", body = ApiErrorResponse)
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(count))]
 pub async fn get_movies(
     Extension(store): Extension<Store>,
+    ValidatedQuery(params): ValidatedQuery<MovieListParams>,
 ) -> Result<Json<MovieListResponse>, MoviesApiError> {
     // let movies = store.get_movies().await?;
     let service = ApiService::new(&store.connection);
-    let movies = service.get_movies().await?;
+    let (movies, total) = service.find_movies(&params).await?;
+    Span::current().record("count", movies.len());
     info!("queried all movies");
-    Ok(Json::from(MovieListResponse { movies }))
+    Ok(Json::from(MovieListResponse { movies, total }))
 }
 
 /// Get movie actors by ID
@@ -104,7 +145,7 @@ pub async fn get_movies(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_actors(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
@@ -113,6 +154,8 @@ pub async fn get_movie_actors(
 
     let movie_actors = service.get_movie_actors(movie_id).await?;
 
+    Span::current().record("count", movie_actors.len());
+
     info!("queried movie {movie_id} actors");
     Ok(Json::from(Actors {
         actors: movie_actors,
@@ -130,13 +173,14 @@ pub async fn get_movie_actors(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_directors(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
 ) -> Result<Json<Directors>, MoviesApiError> {
     let service = ApiService::new(&store.connection);
     let movie_directors = service.get_movie_directors(movie_id).await?;
+    Span::current().record("count", movie_directors.len());
 
     info!("queried movie {movie_id} directors");
     Ok(Json::from(Directors {
@@ -155,13 +199,14 @@ pub async fn get_movie_directors(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_producers(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
 ) -> Result<Json<Producers>, MoviesApiError> {
     let service = ApiService::new(&store.connection);
     let movie_producers = service.get_movie_producers(movie_id).await?;
+    Span::current().record("count", movie_producers.len());
 
     info!("queried movie {movie_id} producers");
     Ok(Json::from(Producers {
@@ -180,13 +225,14 @@ pub async fn get_movie_producers(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_writers(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
 ) -> Result<Json<Writers>, MoviesApiError> {
     let service = ApiService::new(&store.connection);
     let movie_writers = service.get_movie_writers(movie_id).await?;
+    Span::current().record("count", movie_writers.len());
 
     info!("queried movie {movie_id} writers");
     Ok(Json::from(Writers {
@@ -205,7 +251,7 @@ pub async fn get_movie_writers(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_awards(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
@@ -214,6 +260,8 @@ pub async fn get_movie_awards(
 
     let movie_awards = service.get_movie_awards(movie_id).await?;
 
+    Span::current().record("count", movie_awards.len());
+
     // if movie_awards.is_empty() && !store.is_movie_id_exists(movie_id).await? {
     //     return Err(MoviesApiError::MovieNotFound(movie_id));
     // }
@@ -235,13 +283,14 @@ pub async fn get_movie_awards(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id, count))]
 pub async fn get_movie_nominations(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
 ) -> Result<Json<MovieNominationsResponse>, MoviesApiError> {
     let service = ApiService::new(&store.connection);
     let movie_nominations = service.get_movie_nominations(movie_id).await?;
+    Span::current().record("count", movie_nominations.len());
 
     // if movie_nominations.is_empty() && !store.is_movie_id_exists(movie_id).await? {
     //     return Err(MoviesApiError::MovieNotFound(movie_id));
@@ -264,7 +313,7 @@ pub async fn get_movie_nominations(
     ),
     tag = "Movies API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(movie_id = movie_id))]
 pub async fn remove_movie(
     Extension(store): Extension<Store>,
     Path(movie_id): Path<i32>,
@@ -275,7 +324,7 @@ pub async fn remove_movie(
     let message = format!("Movie {movie_id} deleted");
 
     info!(%message);
-    Ok(Json(MoviesMessage { message }))
+    Ok(Json(MoviesMessage { message, id: None }))
 }
 
 pub async fn fallback_handler(uri: axum::http::Uri) -> impl IntoResponse {
@@ -301,6 +350,7 @@ pub async fn fallback_handler(uri: axum::http::Uri) -> impl IntoResponse {
         get_movie_awards,
         get_movie_nominations,
         add_movie,
+        update_movie,
         remove_movie
     ),
     components(schemas(
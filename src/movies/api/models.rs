@@ -1,7 +1,7 @@
 use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
-use validator::{Validate, ValidationError};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidateUrl, ValidationError};
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct CreateMovieAwardRequest {
@@ -47,6 +47,15 @@ fn validate_date(date: &NaiveDate) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Posters are optional; when set they link to other hosts or files of the
+/// media storage. Which files a movie may use is checked by the service.
+fn validate_image_url(url: &str) -> Result<(), ValidationError> {
+    if url.is_empty() || url.validate_url() || crate::media::media_key(url).is_some() {
+        return Ok(());
+    }
+    Err(ValidationError::new("url"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateMovieRequest {
     #[validate(length(min = 1, message = "Title cannot be empty"))]
@@ -66,9 +75,12 @@ pub struct CreateMovieRequest {
         message = "Release date must be in the past"
     ))]
     pub release_date: NaiveDate,
-    #[validate(url(message = "Image URL must be a valid URL"))]
+    #[validate(custom(
+        function = "validate_image_url",
+        message = "Image URL must be a valid URL"
+    ))]
     pub image_url: String,
-    #[validate(length(min = 1, message = "YouTube ID cannot be empty"))]
+    /// Empty when the movie has no trailer.
     pub youtube_id: String,
     #[validate(length(min = 1, message = "Production details cannot be empty"))]
     pub production_details: String,
@@ -88,6 +100,7 @@ pub struct CreateMovieRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MovieResponse {
+    pub id: i32,
     pub title: String,
     pub release_year: i32,
     pub genre: String,
@@ -138,9 +151,84 @@ pub struct MovieNominationsResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MoviesMessage {
     pub message: String,
+    /// Id of the record a create added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MovieListResponse {
     pub movies: Vec<MovieResponse>,
+    /// Movies matching the filters across all pages
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MovieSort {
+    #[default]
+    ReleaseYear,
+    Title,
+    Rating,
+    Runtime,
+}
+
+impl MovieSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MovieSort::ReleaseYear => "release_year",
+            MovieSort::Title => "title",
+            MovieSort::Rating => "rating",
+            MovieSort::Runtime => "runtime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Filters and ordering for the movie list; filters combine with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MovieListParams {
+    /// Text found in the title or plot, ignoring case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// The whole title, ignoring case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Genre name, ignoring case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genre: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<i32>,
+    /// Part of a director's name, ignoring case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub director: Option<String>,
+    /// Part of an actor's name, ignoring case
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[validate(range(min = 0.0, max = 10.0, message = "Rating must be between 0 and 10"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_rating: Option<f32>,
+    /// Defaults to release year, ties ordered by title
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: MovieSort,
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrder,
+    /// Page size; every matching movie when omitted
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    /// Matching movies to skip before the page starts
+    #[validate(range(min = 0, message = "Offset cannot be negative"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
 }
@@ -1,11 +1,14 @@
 use crate::movies::api::errors::MoviesApiError;
 use crate::movies::api::models::{
-    CreateMovieRequest, MovieAwardNominationResponse, MovieAwardResponse, MovieResponse,
+    CreateMovieRequest, MovieAwardNominationResponse, MovieAwardResponse, MovieListParams,
+    MovieResponse, SortOrder,
 };
-use crate::movies::data::repository::MovieRepository;
+use crate::movies::data::entities::Movie;
+use crate::movies::data::repository::{MovieFilter, MoviePage, MovieRepository};
 use futures::stream::{self, StreamExt};
 use futures::TryStreamExt;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
+use validator::{ValidationError, ValidationErrors};
 pub struct ApiService {
     repo: MovieRepository,
 }
@@ -17,86 +20,165 @@ impl ApiService {
         }
     }
 
-    pub async fn create_movie(&self, new_movie: CreateMovieRequest) -> Result<(), MoviesApiError> {
+    pub async fn create_movie(&self, new_movie: CreateMovieRequest) -> Result<i32, MoviesApiError> {
+        if self
+            .repo
+            .get_movie_by_title(&new_movie.title)
+            .await?
+            .is_some()
+        {
+            return Err(MoviesApiError::TitleTaken(new_movie.title));
+        }
+
         let mut tx = self.repo.pool.begin().await?;
 
+        let id = self.insert_movie(&mut tx, new_movie).await?;
+
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Inserts a movie with its genre, people, awards and nominations inside `tx`,
+    /// returning the new movie id. The caller decides when to commit.
+    pub async fn insert_movie(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_movie: CreateMovieRequest,
+    ) -> Result<i32, MoviesApiError> {
+        check_media_url(&new_movie.image_url, None)?;
+
         // 1. Movie
         let movie = self
             .repo
             .create_movie(
-                &mut tx,
-                new_movie.title,
+                tx,
+                new_movie.title.clone(),
                 new_movie.release_year,
-                new_movie.plot,
+                new_movie.plot.clone(),
                 new_movie.runtime,
                 new_movie.rating,
                 new_movie.release_date,
-                new_movie.image_url,
-                new_movie.youtube_id,
-                new_movie.budget,
-                new_movie.production_details,
+                new_movie.image_url.clone(),
+                new_movie.youtube_id.clone(),
+                new_movie.budget.clone(),
+                new_movie.production_details.clone(),
             )
             .await?;
 
         let movie_id = movie.id;
 
+        self.insert_movie_relations(tx, movie_id, new_movie).await?;
+
+        Ok(movie_id)
+    }
+
+    /// Replaces a movie and everything attached to it; the title must stay unique.
+    pub async fn update_movie(
+        &self,
+        movie_id: i32,
+        movie: CreateMovieRequest,
+    ) -> Result<(), MoviesApiError> {
+        let current = self
+            .repo
+            .get_movie_by_id(movie_id)
+            .await?
+            .ok_or_else(|| MoviesApiError::MovieNotFound(movie_id))?;
+
+        if let Some(other) = self.repo.get_movie_by_title(&movie.title).await?
+            && other.id != movie_id
+        {
+            return Err(MoviesApiError::TitleTaken(movie.title));
+        }
+        check_media_url(&movie.image_url, Some(&current.image_url))?;
+
+        let mut tx = self.repo.pool.begin().await?;
+
+        let updated = Movie {
+            title: movie.title.clone(),
+            release_year: movie.release_year,
+            plot: movie.plot.clone(),
+            runtime: movie.runtime,
+            rating: movie.rating,
+            release_date: movie.release_date,
+            image_url: movie.image_url.clone(),
+            youtube_id: movie.youtube_id.clone(),
+            budget: movie.budget.clone(),
+            production_details: movie.production_details.clone(),
+            ..current
+        };
+        self.repo.update_movie(&mut tx, &updated).await?;
+        self.repo.delete_movie_relations(&mut tx, movie_id).await?;
+        self.insert_movie_relations(&mut tx, movie_id, movie).await?;
+        self.repo.prune_orphans(&mut tx).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Attaches the genre, people, awards and nominations of `new_movie` to `movie_id`.
+    async fn insert_movie_relations(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        movie_id: i32,
+        new_movie: CreateMovieRequest,
+    ) -> Result<(), MoviesApiError> {
         // 2. Genre
-        let new_genre = self.repo.create_genre(&mut tx, new_movie.genre).await?;
+        let new_genre = self.repo.create_genre(tx, new_movie.genre).await?;
         let genre_id = new_genre.id;
 
         let _ = self
             .repo
-            .create_movie_genre(&mut tx, movie_id, genre_id)
+            .create_movie_genre(tx, movie_id, genre_id)
             .await?;
 
         // 3. Roles
         // Directors
         for director in new_movie.directors {
-            let person = self.repo.create_person(&mut tx, director).await?;
+            let person = self.repo.create_person(tx, director).await?;
             let person_id = person.id;
             self.repo
-                .create_director(&mut tx, movie_id, person_id)
+                .create_director(tx, movie_id, person_id)
                 .await?;
         }
 
         // Producers
         for producer in new_movie.producers {
-            let person = self.repo.create_person(&mut tx, producer).await?;
+            let person = self.repo.create_person(tx, producer).await?;
             let person_id = person.id;
             self.repo
-                .create_producer(&mut tx, movie_id, person_id)
+                .create_producer(tx, movie_id, person_id)
                 .await?;
         }
 
         // Writers
         for writer in new_movie.writers {
-            let person = self.repo.create_person(&mut tx, writer).await?;
+            let person = self.repo.create_person(tx, writer).await?;
             let person_id = person.id;
             self.repo
-                .create_writer(&mut tx, movie_id, person_id)
+                .create_writer(tx, movie_id, person_id)
                 .await?;
         }
 
         // Actors
         for actor in new_movie.actors {
-            let person = self.repo.create_person(&mut tx, actor).await?;
+            let person = self.repo.create_person(tx, actor).await?;
             let person_id = person.id;
 
-            self.repo.create_actor(&mut tx, movie_id, person_id).await?;
+            self.repo.create_actor(tx, movie_id, person_id).await?;
         }
 
         // 4. Awards
         for new_award in new_movie.awards {
-            let award = self.repo.create_award(&mut tx, new_award.name).await?;
+            let award = self.repo.create_award(tx, new_award.name).await?;
             let award_id = award.id;
             let award_category = self
                 .repo
-                .create_award_category(&mut tx, award_id, new_award.category)
+                .create_award_category(tx, award_id, new_award.category)
                 .await?;
             let category_id = award_category.id;
             self.repo
                 .create_movie_award(
-                    &mut tx,
+                    tx,
                     movie_id,
                     category_id,
                     new_award.year,
@@ -107,16 +189,16 @@ impl ApiService {
 
         // 5. Nominations
         for new_nomination in new_movie.nominations {
-            let award = self.repo.create_award(&mut tx, new_nomination.name).await?;
+            let award = self.repo.create_award(tx, new_nomination.name).await?;
             let award_id = award.id;
             let award_category = self
                 .repo
-                .create_award_category(&mut tx, award_id, new_nomination.category)
+                .create_award_category(tx, award_id, new_nomination.category)
                 .await?;
             let category_id = award_category.id;
             self.repo
                 .create_movie_nomination(
-                    &mut tx,
+                    tx,
                     movie_id,
                     category_id,
                     new_nomination.year,
@@ -125,7 +207,6 @@ impl ApiService {
                 .await?;
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
@@ -159,6 +240,7 @@ impl ApiService {
         );
 
         let movie_response = MovieResponse {
+            id: movie_id,
             title: movie.title,
             release_year: movie.release_year,
             genre: movie_genre?.name,
@@ -182,7 +264,41 @@ impl ApiService {
 
     pub async fn get_movies(&self) -> Result<Vec<MovieResponse>, MoviesApiError> {
         let movies_ids = self.repo.get_all_movies_ids().await?;
-        let concurrency = movies_ids.len();
+        self.get_movies_by_ids(movies_ids).await
+    }
+
+    /// One page of the movies matching `params`, and how many match in all.
+    pub async fn find_movies(
+        &self,
+        params: &MovieListParams,
+    ) -> Result<(Vec<MovieResponse>, i64), MoviesApiError> {
+        let filter = MovieFilter {
+            text: params.q.as_deref(),
+            title: params.title.as_deref(),
+            genre: params.genre.as_deref(),
+            year: params.year,
+            decade: None,
+            min_rating: params.min_rating,
+            director: params.director.as_deref(),
+            actor: params.actor.as_deref(),
+        };
+        let page = MoviePage {
+            sort: params.sort.as_str(),
+            descending: params.order == SortOrder::Desc,
+            limit: params.limit,
+            offset: params.offset.unwrap_or(0),
+        };
+        let (movies_ids, total) = self.repo.find_movies_ids(&filter, &page).await?;
+        let movies = self.get_movies_by_ids(movies_ids).await?;
+
+        Ok((movies, total))
+    }
+
+    async fn get_movies_by_ids(
+        &self,
+        movies_ids: Vec<i32>,
+    ) -> Result<Vec<MovieResponse>, MoviesApiError> {
+        let concurrency = movies_ids.len().max(1);
 
         let movies = stream::iter(movies_ids)
             .map(|movie_id| self.get_movie(movie_id))
@@ -354,3 +470,20 @@ impl ApiService {
         Ok(nominations)
     }
 }
+
+/// Files of the media storage are attached by the media endpoints only, under
+/// keys they issued for that movie. A request may keep the poster the movie
+/// already has, but not point it at any other file.
+fn check_media_url(image_url: &str, current: Option<&str>) -> Result<(), MoviesApiError> {
+    if crate::media::media_key(image_url).is_none() || Some(image_url) == current {
+        return Ok(());
+    }
+
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "image_url",
+        ValidationError::new("media_url")
+            .with_message("Upload posters through the media endpoints".into()),
+    );
+    Err(errors.into())
+}
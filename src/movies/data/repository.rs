@@ -1,6 +1,8 @@
 use chrono::{NaiveDate, NaiveDateTime};
 use sqlx::{PgPool, Postgres, Transaction};
 
+use crate::store::traced_query;
+
 use crate::movies::data::entities::{
     Award, AwardCategory, Genre, Movie, MovieAward, MovieAwardNomination, MovieAwardWon,
     MovieGenre, MovieNomination, MovieRole, Person,
@@ -24,6 +26,31 @@ impl std::fmt::Display for PersonRole {
     }
 }
 
+/// Conditions a movie has to meet to be listed; `None` matches everything.
+#[derive(Debug, Default)]
+pub struct MovieFilter<'a> {
+    pub text: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub genre: Option<&'a str>,
+    pub year: Option<i32>,
+    /// Any year of a decade, such as 1990 for the nineties
+    pub decade: Option<i32>,
+    pub min_rating: Option<f32>,
+    pub director: Option<&'a str>,
+    pub actor: Option<&'a str>,
+}
+
+/// Which slice of the matching movies to return, and in what order.
+#[derive(Debug)]
+pub struct MoviePage<'a> {
+    /// `release_year`, `title`, `rating` or `runtime`; ties go by release year then title
+    pub sort: &'a str,
+    pub descending: bool,
+    /// `None` returns every movie after `offset`
+    pub limit: Option<i64>,
+    pub offset: i64,
+}
+
 pub struct MovieRepository {
     pub pool: PgPool,
 }
@@ -38,16 +65,19 @@ impl MovieRepository {
         tx: &mut Transaction<'_, Postgres>,
         person_name: String,
     ) -> Result<Person, sqlx::Error> {
-        sqlx::query_as!(
-            Person,
-            r#"
-            INSERT INTO person (name ) VALUES ($1)
-            ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
-            RETURNING *
-            "#,
-            person_name
+        traced_query(
+            "create_person",
+            sqlx::query_as!(
+                Person,
+                r#"
+                INSERT INTO person (name ) VALUES ($1)
+                ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+                RETURNING *
+                "#,
+                person_name
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -56,16 +86,19 @@ impl MovieRepository {
         tx: &mut Transaction<'_, Postgres>,
         genre_name: String,
     ) -> Result<Genre, sqlx::Error> {
-        sqlx::query_as!(
-            Genre,
-            r#"
-            INSERT INTO genre (name ) VALUES ($1)
-            ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
-            RETURNING *
-            "#,
-            genre_name
+        traced_query(
+            "create_genre",
+            sqlx::query_as!(
+                Genre,
+                r#"
+                INSERT INTO genre (name ) VALUES ($1)
+                ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+                RETURNING *
+                "#,
+                genre_name
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -74,16 +107,19 @@ impl MovieRepository {
         tx: &mut Transaction<'_, Postgres>,
         award_name: String,
     ) -> Result<Award, sqlx::Error> {
-        sqlx::query_as!(
-            Award,
-            r#"
-            INSERT INTO award (name) VALUES  ($1)
-            ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
-            RETURNING *
-            "#,
-            award_name
+        traced_query(
+            "create_award",
+            sqlx::query_as!(
+                Award,
+                r#"
+                INSERT INTO award (name) VALUES  ($1)
+                ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+                RETURNING *
+                "#,
+                award_name
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -94,20 +130,23 @@ impl MovieRepository {
         person_id: i32,
         role: PersonRole,
     ) -> Result<MovieRole, sqlx::Error> {
-        sqlx::query_as!(
-            MovieRole,
-            r#"
-            INSERT INTO movie_role (movie_id, person_id, role_id)
-            VALUES ($1, $2, (SELECT id FROM role WHERE name = $3))
-            ON CONFLICT (movie_id, person_id, role_id)
-            DO UPDATE SET role_id = EXCLUDED.role_id
-            RETURNING *
-            "#,
-            movie_id,
-            person_id,
-            role.to_string()
+        traced_query(
+            "create_movie_role",
+            sqlx::query_as!(
+                MovieRole,
+                r#"
+                INSERT INTO movie_role (movie_id, person_id, role_id)
+                VALUES ($1, $2, (SELECT id FROM role WHERE name = $3))
+                ON CONFLICT (movie_id, person_id, role_id)
+                DO UPDATE SET role_id = EXCLUDED.role_id
+                RETURNING *
+                "#,
+                movie_id,
+                person_id,
+                role.to_string()
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -165,30 +204,33 @@ impl MovieRepository {
         budget: String,
         production_details: String,
     ) -> Result<Movie, sqlx::Error> {
-        sqlx::query_as!(
-            Movie,
-            r#"
-        INSERT INTO movie (
-            title, release_year,  plot, runtime, rating,
-            release_date, image_url, youtube_id, budget, production_details
-        ) VALUES (
-            $1,$2,$3,$4,$5,$6,$7,$8,$9,$10
-        )
-        ON CONFLICT (title) DO UPDATE SET title = EXCLUDED.title
-        RETURNING *
-        "#,
-            title,
-            release_year,
-            plot,
-            runtime,
-            rating,
-            release_date,
-            image_url,
-            youtube_id,
-            budget,
-            production_details,
-        )
-        .fetch_one(&mut **tx)
+        traced_query(
+            "create_movie",
+            sqlx::query_as!(
+                Movie,
+                r#"
+            INSERT INTO movie (
+                title, release_year,  plot, runtime, rating,
+                release_date, image_url, youtube_id, budget, production_details
+            ) VALUES (
+                $1,$2,$3,$4,$5,$6,$7,$8,$9,$10
+            )
+            ON CONFLICT (title) DO UPDATE SET title = EXCLUDED.title
+            RETURNING *
+            "#,
+                title,
+                release_year,
+                plot,
+                runtime,
+                rating,
+                release_date,
+                image_url,
+                youtube_id,
+                budget,
+                production_details,
+            )
+            .fetch_one(&mut **tx),
+        )
         .await
     }
 
@@ -198,18 +240,21 @@ impl MovieRepository {
         movie_id: i32,
         genre_id: i32,
     ) -> Result<MovieGenre, sqlx::Error> {
-        sqlx::query_as!(
-            MovieGenre,
-            r#"
-            INSERT INTO movie_genre (movie_id, genre_id)
-            VALUES ($1,$2)
-            ON CONFLICT (movie_id, genre_id) DO UPDATE SET genre_id = EXCLUDED.genre_id
-            RETURNING *
-            "#,
-            movie_id,
-            genre_id
+        traced_query(
+            "create_movie_genre",
+            sqlx::query_as!(
+                MovieGenre,
+                r#"
+                INSERT INTO movie_genre (movie_id, genre_id)
+                VALUES ($1,$2)
+                ON CONFLICT (movie_id, genre_id) DO UPDATE SET genre_id = EXCLUDED.genre_id
+                RETURNING *
+                "#,
+                movie_id,
+                genre_id
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -219,17 +264,20 @@ impl MovieRepository {
         award_id: i32,
         category_name: String,
     ) -> Result<AwardCategory, sqlx::Error> {
-        sqlx::query_as!(
-            AwardCategory,
-            r#"
-            INSERT INTO award_category (award_id, category) VALUES ($1, $2)
-            ON CONFLICT (award_id, category) DO UPDATE SET category = EXCLUDED.category
-            RETURNING *
-            "#,
-            award_id,
-            category_name,
+        traced_query(
+            "create_award_category",
+            sqlx::query_as!(
+                AwardCategory,
+                r#"
+                INSERT INTO award_category (award_id, category) VALUES ($1, $2)
+                ON CONFLICT (award_id, category) DO UPDATE SET category = EXCLUDED.category
+                RETURNING *
+                "#,
+                award_id,
+                category_name,
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -241,20 +289,23 @@ impl MovieRepository {
         year: i32,
         recipient: &str,
     ) -> Result<MovieAward, sqlx::Error> {
-        sqlx::query_as!(
-            MovieAward,
-            r#"
-            INSERT INTO movie_award (movie_id, award_category_id, year, recipient)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT DO NOTHING
-            RETURNING *
-            "#,
-            movie_id,
-            award_category_id,
-            year,
-            recipient
+        traced_query(
+            "create_movie_award",
+            sqlx::query_as!(
+                MovieAward,
+                r#"
+                INSERT INTO movie_award (movie_id, award_category_id, year, recipient)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT DO NOTHING
+                RETURNING *
+                "#,
+                movie_id,
+                award_category_id,
+                year,
+                recipient
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -266,20 +317,23 @@ impl MovieRepository {
         year: i32,
         nominee: String,
     ) -> Result<MovieNomination, sqlx::Error> {
-        sqlx::query_as!(
-            MovieNomination,
-            r#"
-            INSERT INTO movie_nomination (movie_id, award_category_id, year, nominee)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT DO NOTHING
-            RETURNING *
-            "#,
-            movie_id,
-            award_category_id,
-            year,
-            nominee
+        traced_query(
+            "create_movie_nomination",
+            sqlx::query_as!(
+                MovieNomination,
+                r#"
+                INSERT INTO movie_nomination (movie_id, award_category_id, year, nominee)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT DO NOTHING
+                RETURNING *
+                "#,
+                movie_id,
+                award_category_id,
+                year,
+                nominee
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
@@ -288,81 +342,223 @@ impl MovieRepository {
         tx: &mut Transaction<'_, Postgres>,
         movie_id: i32,
     ) -> Result<(), sqlx::Error> {
-        let res = sqlx::query!(
-            r#"
-        DELETE FROM movie WHERE id = $1
-        "#,
-            movie_id
+        traced_query(
+            "delete_movie",
+            sqlx::query!(
+                r#"
+            DELETE FROM movie WHERE id = $1
+            "#,
+                movie_id
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
-        // if res.rows_affected() == 0 {
-        //     return Err(MoviesApiError::MovieNotFound(movie_id));
-        // }
+        self.prune_orphans(tx).await
+    }
 
-        // 2. Prune orphaned people
-        sqlx::query!(
-            r#"
-        DELETE FROM person
-        WHERE id NOT IN (
-            SELECT person_id FROM movie_role
-        )
-        "#
+    /// Overwrites the columns of `movie.id`; timestamps are set by the database.
+    pub async fn update_movie(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        movie: &Movie,
+    ) -> Result<(), sqlx::Error> {
+        traced_query(
+            "update_movie",
+            sqlx::query!(
+                r#"
+            UPDATE movie SET
+                title = $2, release_year = $3, plot = $4, runtime = $5, rating = $6,
+                release_date = $7, image_url = $8, youtube_id = $9, budget = $10,
+                production_details = $11, updated_at = NOW()
+            WHERE id = $1
+            "#,
+                movie.id,
+                movie.title,
+                movie.release_year,
+                movie.plot,
+                movie.runtime,
+                movie.rating,
+                movie.release_date,
+                movie.image_url,
+                movie.youtube_id,
+                movie.budget,
+                movie.production_details,
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
-        // 3. Prune orphaned award_categories
-        sqlx::query!(
-            r#"
-        DELETE FROM award_category
-        WHERE id NOT IN (
-            SELECT award_category_id FROM movie_award
-            UNION
-            SELECT award_category_id FROM movie_nomination
+        Ok(())
+    }
+
+    /// Detaches the genre, people, awards and nominations of a movie, leaving
+    /// orphans for `prune_orphans`.
+    pub async fn delete_movie_relations(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        movie_id: i32,
+    ) -> Result<(), sqlx::Error> {
+        traced_query(
+            "delete_movie_genres",
+            sqlx::query!("DELETE FROM movie_genre WHERE movie_id = $1", movie_id)
+                .execute(&mut **tx),
         )
-        "#
+        .await?;
+        traced_query(
+            "delete_movie_roles",
+            sqlx::query!("DELETE FROM movie_role WHERE movie_id = $1", movie_id)
+                .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
+        traced_query(
+            "delete_movie_awards",
+            sqlx::query!("DELETE FROM movie_award WHERE movie_id = $1", movie_id)
+                .execute(&mut **tx),
+        )
+        .await?;
+        traced_query(
+            "delete_movie_nominations",
+            sqlx::query!(
+                "DELETE FROM movie_nomination WHERE movie_id = $1",
+                movie_id
+            )
+            .execute(&mut **tx),
+        )
+        .await?;
+
+        Ok(())
+    }
 
-        // 4. Prune orphaned awards
-        sqlx::query!(
-            r#"
-        DELETE FROM award
-        WHERE id NOT IN (
-            SELECT award_id FROM award_category
+    /// Removes people, awards and genres no movie refers to any more.
+    pub async fn prune_orphans(&self, tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::Error> {
+        // 1. Prune orphaned people
+        traced_query(
+            "prune_orphaned_persons",
+            sqlx::query!(
+                r#"
+            DELETE FROM person
+            WHERE id NOT IN (
+                SELECT person_id FROM movie_role
+            )
+            "#
+            )
+            .execute(&mut **tx),
         )
-        "#
+        .await?;
+
+        // 2. Prune orphaned award_categories
+        traced_query(
+            "prune_orphaned_award_categories",
+            sqlx::query!(
+                r#"
+            DELETE FROM award_category
+            WHERE id NOT IN (
+                SELECT award_category_id FROM movie_award
+                UNION
+                SELECT award_category_id FROM movie_nomination
+            )
+            "#
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
-        // 5. Prune orphaned genre
-        sqlx::query!(
-            r#"
-        DELETE FROM genre
-        WHERE id NOT IN (
-            SELECT genre_id FROM movie_genre
+        // 3. Prune orphaned awards
+        traced_query(
+            "prune_orphaned_awards",
+            sqlx::query!(
+                r#"
+            DELETE FROM award
+            WHERE id NOT IN (
+                SELECT award_id FROM award_category
+            )
+            "#
+            )
+            .execute(&mut **tx),
         )
-        "#
+        .await?;
+
+        // 4. Prune orphaned genre
+        traced_query(
+            "prune_orphaned_genres",
+            sqlx::query!(
+                r#"
+            DELETE FROM genre
+            WHERE id NOT IN (
+                SELECT genre_id FROM movie_genre
+            )
+            "#
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
         Ok(())
     }
 
     pub async fn get_movie_by_id(&self, movie_id: i32) -> Result<Option<Movie>, sqlx::Error> {
-        sqlx::query_as!(
-            Movie,
-            r#"
-            SELECT * FROM movie WHERE id = $1
-            "#,
-            movie_id
+        traced_query(
+            "get_movie_by_id",
+            sqlx::query_as!(
+                Movie,
+                r#"
+                SELECT * FROM movie WHERE id = $1
+                "#,
+                movie_id
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn get_movie_by_title(&self, title: &str) -> Result<Option<Movie>, sqlx::Error> {
+        traced_query(
+            "get_movie_by_title",
+            sqlx::query_as!(
+                Movie,
+                r#"
+                SELECT * FROM movie WHERE title = $1
+                "#,
+                title
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    /// Whether a movie titled `title` exists, as seen from inside `tx`.
+    pub async fn movie_title_exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        title: &str,
+    ) -> Result<bool, sqlx::Error> {
+        traced_query(
+            "movie_title_exists",
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(SELECT 1 FROM movie WHERE title = $1) AS "exists!"
+                "#,
+                title
+            )
+            .fetch_one(&mut **tx),
+        )
+        .await
+    }
+
+    /// The `limit` most recently added movies, newest first.
+    pub async fn get_latest_movies(&self, limit: i64) -> Result<Vec<Movie>, sqlx::Error> {
+        traced_query(
+            "get_latest_movies",
+            sqlx::query_as!(
+                Movie,
+                r#"
+                SELECT * FROM movie ORDER BY created_at DESC, id DESC LIMIT $1
+                "#,
+                limit
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_optional(&self.pool)
         .await
     }
 
@@ -371,17 +567,20 @@ impl MovieRepository {
         movie_id: i32,
         role: PersonRole,
     ) -> Result<Vec<Person>, sqlx::Error> {
-        let persons = sqlx::query_as!(
-            Person,
-            r#"
-            SELECT p.*
-            FROM person p INNER JOIN movie_role mr ON p.id = mr.person_id
-            WHERE mr.movie_id = $1 AND mr.role_id in (SELECT id FROM role WHERE name = $2)
-            "#,
-            movie_id,
-            role.to_string()
+        let persons = traced_query(
+            "get_persons_by_role",
+            sqlx::query_as!(
+                Person,
+                r#"
+                SELECT p.*
+                FROM person p INNER JOIN movie_role mr ON p.id = mr.person_id
+                WHERE mr.movie_id = $1 AND mr.role_id in (SELECT id FROM role WHERE name = $2)
+                "#,
+                movie_id,
+                role.to_string()
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_all(&self.pool)
         .await?;
 
         Ok(persons)
@@ -429,18 +628,22 @@ impl MovieRepository {
         &self,
         movie_id: i32,
     ) -> Result<Vec<MovieAwardWon>, sqlx::Error> {
-        sqlx::query_as!(
-            MovieAwardWon,
-            r#"
-            SELECT a.name AS name, ac.category, ma.year, ma.recipient
-            FROM movie_award ma
-                    JOIN award_category ac ON ma.award_category_id = ac.id
-                    JOIN award a ON ac.award_id = a.id
-            WHERE ma.movie_id = $1
-            "#,
-            movie_id
+        traced_query(
+            "get_movie_awards_won",
+            sqlx::query_as!(
+                MovieAwardWon,
+                r#"
+                SELECT a.name AS name, ac.category, ma.year, ma.recipient
+                FROM movie_award ma
+                        JOIN award_category ac ON ma.award_category_id = ac.id
+                        JOIN award a ON ac.award_id = a.id
+                WHERE ma.movie_id = $1
+                ORDER BY ma.year, a.name, ac.category, ma.recipient
+                "#,
+                movie_id
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_all(&self.pool)
         .await
     }
 
@@ -448,41 +651,170 @@ impl MovieRepository {
         &self,
         movie_id: i32,
     ) -> Result<Vec<MovieAwardNomination>, sqlx::Error> {
-        sqlx::query_as!(
-            MovieAwardNomination,
-            r#"
-            SELECT a.name AS name, ac.category, mn.year, mn.nominee
-            FROM movie_nomination mn
-                    JOIN award_category ac ON mn.award_category_id = ac.id
-                    JOIN award a ON ac.award_id = a.id
-            WHERE mn.movie_id = $1
-            "#,
-            movie_id
+        traced_query(
+            "get_movie_awards_nominations",
+            sqlx::query_as!(
+                MovieAwardNomination,
+                r#"
+                SELECT a.name AS name, ac.category, mn.year, mn.nominee
+                FROM movie_nomination mn
+                        JOIN award_category ac ON mn.award_category_id = ac.id
+                        JOIN award a ON ac.award_id = a.id
+                WHERE mn.movie_id = $1
+                ORDER BY mn.year, a.name, ac.category, mn.nominee
+                "#,
+                movie_id
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_all(&self.pool)
         .await
     }
 
     pub async fn get_movie_genre(&self, movie_id: i32) -> Result<Genre, sqlx::Error> {
-        sqlx::query_as!(
-            Genre,
-            r#"
-            SELECT * FROM genre
-            WHERE id IN (SELECT genre_id FROM movie_genre WHERE movie_id = $1)
-            "#,
-            movie_id
+        traced_query(
+            "get_movie_genre",
+            sqlx::query_as!(
+                Genre,
+                r#"
+                SELECT * FROM genre
+                WHERE id IN (SELECT genre_id FROM movie_genre WHERE movie_id = $1)
+                "#,
+                movie_id
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    /// Ids of one page of the movies matching `filter`, and how many match in all.
+    pub async fn find_movies_ids(
+        &self,
+        filter: &MovieFilter<'_>,
+        page: &MoviePage<'_>,
+    ) -> Result<(Vec<i32>, i64), sqlx::Error> {
+        let found = traced_query(
+            "find_movies_ids",
+            sqlx::query!(
+                r#"
+                WITH matched AS (
+                    SELECT m.id, m.title, m.release_year, m.rating, m.runtime FROM movie m
+                    WHERE ($1::TEXT IS NULL OR STRPOS(LOWER(m.title), LOWER($1)) > 0 OR STRPOS(LOWER(m.plot), LOWER($1)) > 0)
+                      AND ($2::TEXT IS NULL OR LOWER(m.title) = LOWER($2))
+                      AND ($3::TEXT IS NULL OR EXISTS (
+                            SELECT 1 FROM movie_genre mg JOIN genre g ON g.id = mg.genre_id
+                            WHERE mg.movie_id = m.id AND LOWER(g.name) = LOWER($3)))
+                      AND ($4::INT IS NULL OR m.release_year = $4)
+                      AND ($5::REAL IS NULL OR m.rating >= $5)
+                      AND ($6::TEXT IS NULL OR EXISTS (
+                            SELECT 1 FROM movie_role mr
+                                JOIN person p ON p.id = mr.person_id
+                                JOIN role r ON r.id = mr.role_id
+                            WHERE mr.movie_id = m.id AND r.name = 'Director' AND STRPOS(LOWER(p.name), LOWER($6)) > 0))
+                      AND ($7::TEXT IS NULL OR EXISTS (
+                            SELECT 1 FROM movie_role mr
+                                JOIN person p ON p.id = mr.person_id
+                                JOIN role r ON r.id = mr.role_id
+                            WHERE mr.movie_id = m.id AND r.name = 'Actor' AND STRPOS(LOWER(p.name), LOWER($7)) > 0))
+                      AND ($12::INT IS NULL OR m.release_year / 10 = $12 / 10)
+                )
+                SELECT
+                    ARRAY(
+                        SELECT id FROM matched
+                        ORDER BY
+                            CASE WHEN $8 = 'title' AND NOT $9 THEN title END ASC,
+                            CASE WHEN $8 = 'title' AND $9 THEN title END DESC,
+                            CASE WHEN $8 = 'rating' AND NOT $9 THEN rating END ASC,
+                            CASE WHEN $8 = 'rating' AND $9 THEN rating END DESC,
+                            CASE WHEN $8 = 'runtime' AND NOT $9 THEN runtime END ASC,
+                            CASE WHEN $8 = 'runtime' AND $9 THEN runtime END DESC,
+                            CASE WHEN NOT $9 THEN release_year END ASC,
+                            CASE WHEN $9 THEN release_year END DESC,
+                            CASE WHEN NOT $9 THEN title END ASC,
+                            CASE WHEN $9 THEN title END DESC
+                        LIMIT $10 OFFSET $11
+                    ) AS "ids!",
+                    (SELECT COUNT(*) FROM matched) AS "total!"
+                "#,
+                filter.text,
+                filter.title,
+                filter.genre,
+                filter.year,
+                filter.min_rating,
+                filter.director,
+                filter.actor,
+                page.sort,
+                page.descending,
+                page.limit,
+                page.offset,
+                filter.decade,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await?;
+
+        Ok((found.ids, found.total))
+    }
+
+    /// Names of the genres at least one movie has, for filter choices.
+    pub async fn get_genres_names(&self) -> Result<Vec<String>, sqlx::Error> {
+        traced_query(
+            "get_genres_names",
+            sqlx::query_scalar!(
+                r#"
+                SELECT DISTINCT g.name FROM genre g
+                    JOIN movie_genre mg ON mg.genre_id = g.id
+                ORDER BY g.name
+                "#,
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_one(&self.pool)
         .await
     }
 
+    /// First years of the decades movies were released in, oldest first.
+    pub async fn get_release_decades(&self) -> Result<Vec<i32>, sqlx::Error> {
+        traced_query(
+            "get_release_decades",
+            sqlx::query_scalar!(
+                r#"
+                SELECT DISTINCT release_year / 10 * 10 AS "decade!" FROM movie
+                ORDER BY 1
+                "#,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// Every movie id with when the movie was last edited, in id order.
+    pub async fn get_movies_updates(&self) -> Result<Vec<(i32, NaiveDateTime)>, sqlx::Error> {
+        let updates = traced_query(
+            "get_movies_updates",
+            sqlx::query!(
+                r#"
+                SELECT id, updated_at FROM movie ORDER BY id
+                "#,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await?;
+
+        Ok(updates
+            .into_iter()
+            .map(|movie| (movie.id, movie.updated_at))
+            .collect())
+    }
+
     pub async fn get_all_movies_ids(&self) -> Result<Vec<i32>, sqlx::Error> {
-        sqlx::query_scalar!(
-            r#"
-        SELECT id from movie ORDER BY release_year;
-        "#,
+        traced_query(
+            "get_all_movies_ids",
+            sqlx::query_scalar!(
+                r#"
+            SELECT id from movie ORDER BY release_year, title;
+            "#,
+            )
+            .fetch_all(&self.pool),
         )
-        .fetch_all(&self.pool)
         .await
     }
 }
@@ -4,7 +4,7 @@ pub mod web;
 
 use axum::{
     Extension, Router,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
 };
 
 use crate::store::Store;
@@ -37,6 +37,7 @@ pub fn rest_api_router(movie_store: &Store) -> Router {
             "/movies/{movie_id}/nominations",
             get(api::handlers::get_movie_nominations),
         )
+        .route("/movies/{movie_id}", put(api::handlers::update_movie))
         .route("/movies/{movie_id}", delete(api::handlers::remove_movie))
         .fallback(api::handlers::fallback_handler)
         .layer(Extension(movie_store.clone()));
@@ -47,16 +48,19 @@ pub fn rest_api_router(movie_store: &Store) -> Router {
 pub fn web_router(movie_store: &Store) -> Router {
     let router = Router::new()
         .route("/", get(web::handlers::list_movies))
+        .route("/", post(web::handlers::create_movie))
+        .route("/new", get(web::handlers::new_movie_form))
+        .route("/rows/{list}", get(web::handlers::movie_form_row))
         .route("/{movie_id}", get(web::handlers::movie_details))
-        .layer(Extension(movie_store.clone()));
-
-    router
-}
-
-pub fn htmx_web_router(movie_store: &Store) -> Router {
-    let router = Router::new()
-        .route("/", get(web::handlers::htmx_list_movies))
-        .route("/{movie_id}", get(web::handlers::htmx_movie_details))
+        .route("/{movie_id}", post(web::handlers::update_movie))
+        .route("/{movie_id}", put(web::handlers::update_movie))
+        .route("/{movie_id}", delete(web::handlers::delete_movie))
+        .route("/{movie_id}/edit", get(web::handlers::edit_movie_form))
+        .route(
+            "/{movie_id}/delete",
+            get(web::handlers::confirm_delete_movie),
+        )
+        .route("/{movie_id}/delete", post(web::handlers::delete_movie))
         .layer(Extension(movie_store.clone()));
 
     router
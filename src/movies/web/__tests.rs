@@ -0,0 +1,153 @@
+use crate::store::Store;
+use crate::test_support::{create_database, drop_database, send};
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::routing::any;
+
+/// A request to the movie pages with `form` as its urlencoded body.
+fn request(method: &str, uri: &str, form: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(form.to_string()))
+        .unwrap()
+}
+
+const MOVIE_FORM: &str = "title=Reservoir+Dogs&release_year=1992&genre=Crime&plot=A+heist+gone+wrong.\
+    &runtime=99&rating=8.3&release_date=1992-10-23&image_url=https%3A%2F%2Fexample.com%2Fposter.jpg\
+    &youtube_id=abcdefghijk&production_details=Live+America&budget=%241.2+million\
+    &directors=Quentin+Tarantino&producers=Lawrence+Bender&writers=Quentin+Tarantino\
+    &actors=Harvey+Keitel&actors=Tim+Roth&actors=\
+    &award_name=Sitges&award_category=Best+Director&award_year=1992&award_person=Quentin+Tarantino\
+    &nomination_name=&nomination_category=&nomination_year=&nomination_person=";
+
+#[tokio::test]
+async fn test_movie_form_round_trip() {
+    let name = "tarantinors_web_movie_forms";
+    let pool = create_database(name).await;
+    let store = Store {
+        connection: pool.clone(),
+    };
+    let app = Router::new().nest("/movies", crate::movies::web_router(&store));
+
+    // Errors come back inline, next to the inputs, with what was typed
+    let invalid = MOVIE_FORM.replace("release_year=1992", "release_year=soon");
+    let response = send(&app, request("POST", "/movies", &invalid)).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(response.body.contains("Release year must be a number"));
+    assert!(response.body.contains(r#"value="Tim Roth""#));
+
+    let response = send(&app, request("POST", "/movies", MOVIE_FORM)).await;
+    assert_eq!(response.status, StatusCode::SEE_OTHER);
+    let movie_id: i32 = response
+        .header(header::LOCATION)
+        .trim_start_matches("/movies/")
+        .parse()
+        .unwrap();
+
+    let response = send(&app, request("POST", "/movies", MOVIE_FORM)).await;
+    assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(response.body.contains("Another movie is titled"));
+
+    let edit = format!("/movies/{}/edit", movie_id);
+    let response = send(&app, request("GET", &edit, "")).await;
+    assert!(response.body.contains(r#"value="Harvey Keitel""#));
+    assert!(response.body.contains(r#"value="Sitges""#));
+
+    // Deleting asks first; only the post removes the movie
+    let delete = format!("/movies/{}/delete", movie_id);
+    let response = send(&app, request("GET", &delete, "")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.contains("Delete this movie?"));
+
+    let response = send(&app, request("POST", &delete, "")).await;
+    assert_eq!(
+        (response.status, response.header(header::LOCATION)),
+        (StatusCode::SEE_OTHER, "/movies")
+    );
+
+    let detail = format!("/movies/{}", movie_id);
+    let response = send(&app, request("GET", &detail, "")).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    drop_database(pool, name).await;
+}
+
+#[tokio::test]
+async fn test_movie_grid_filters() {
+    let name = "tarantinors_web_movie_grid";
+    let pool = create_database(name).await;
+    let store = Store {
+        connection: pool.clone(),
+    };
+    let app = Router::new()
+        .nest("/movies", crate::movies::web_router(&store))
+        .route("/htmx/{*path}", any(crate::htmx::legacy_redirect));
+
+    let django = MOVIE_FORM
+        .replace("title=Reservoir+Dogs", "title=Django+Unchained")
+        .replace("release_year=1992", "release_year=2012")
+        .replace("release_date=1992-10-23", "release_date=2012-12-25")
+        .replace("genre=Crime", "genre=Western");
+    for form in [MOVIE_FORM, django.as_str()] {
+        let response = send(&app, request("POST", "/movies", form)).await;
+        assert_eq!(response.status, StatusCode::SEE_OTHER);
+    }
+
+    let htmx = |query: &str, target: &str| {
+        let request = Request::builder()
+            .uri(format!("/movies?{}", query))
+            .header("HX-Request", "true")
+            .header("HX-Target", target)
+            .body(Body::empty())
+            .unwrap();
+        let app = app.clone();
+        async move { send(&app, request).await.body }
+    };
+    let grid = |query: &str| htmx(query, "movie-grid");
+
+    // The form's own swaps get just the grid
+    let body = grid("q=&genre=western&decade=&min_rating=").await;
+    assert!(body.starts_with(r#"<div id="movie-grid""#));
+    assert!(body.contains("Django Unchained"));
+    assert!(!body.contains("Reservoir Dogs"));
+
+    // Live updates only add new movies to a grid no filter could leave them
+    // out of
+    assert!(!body.contains(r#"id="movie-cards""#));
+    let body = grid("q=&genre=&decade=&min_rating=").await;
+    assert!(body.contains(r#"id="movie-cards""#));
+
+    let body = grid("decade=1995").await;
+    assert!(body.contains("Reservoir Dogs"));
+    assert!(!body.contains("Django Unchained"));
+
+    let body = grid("q=heist&min_rating=9").await;
+    assert!(body.contains("No movies match these filters."));
+
+    // Infinite scroll asks for the cards of later pages only
+    let body = grid("genre=western&page=2").await;
+    assert!(!body.contains(r#"id="movie-grid""#));
+    assert!(!body.contains("Django Unchained"));
+
+    // A shared link gets the filters already chosen
+    let body = send(&app, request("GET", "/movies?genre=Western", ""))
+        .await
+        .body;
+    assert!(body.contains(r#"<option value="Western" selected>"#));
+    assert!(body.starts_with("<!DOCTYPE html>"));
+
+    // Navigating with htmx only swaps the page content
+    let body = htmx("genre=Western", "main").await;
+    assert!(body.starts_with("<title>Filmography"));
+    assert!(body.contains(r#"<option value="Western" selected>"#));
+    assert!(!body.contains("<html"));
+
+    let response = send(&app, request("GET", "/htmx/movies?genre=Western", "")).await;
+    assert_eq!(response.status, StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(response.header(header::LOCATION), "/movies?genre=Western");
+
+    drop_database(pool, name).await;
+}
@@ -1,80 +1,328 @@
-use crate::movies::{
-    web::{
-        errors::MoviesWebError,
-        models::MovieViewModel,
-        service::WebService,
-        templates::{
-            BaseTemplate, HomeTemplate, HtmxMovieDetailsTemplate, HtmxMoviesTemplate,
-            MovieDetailsTemplate, MoviesTemplate,
-        },
+use crate::forms::{ConfirmDeleteTemplate, FormData, FormErrors, FormTarget};
+use crate::htmx::{HtmxRequest, fragment};
+use crate::i18n::Locale;
+use crate::movies::web::{
+    models::{AwardRowViewModel, MovieFormViewModel, MovieGridParams, MovieMetaViewModel},
+    service::WebService,
+    templates::{
+        HtmxMovieCardsTemplate, HtmxMovieGridTemplate, HtmxMovieRowTemplate, MovieDetailsTemplate,
+        MovieFormTemplate, MoviesTemplate,
     },
 };
+use crate::seo::site_url;
 
 use askama::Template;
-use axum::{Extension, extract::Path, http::StatusCode, response::Html};
-use tracing::{info, instrument};
+use axum::{
+    Extension,
+    extract::{Path, Query},
+    http::HeaderMap,
+    response::{Html, IntoResponse, Response},
+};
+use tracing::{Span, info, instrument};
 
 use crate::store::Store;
-#[instrument]
+use crate::web_error::WebError;
+#[instrument(skip_all, fields(movie_id = movie_id))]
 pub async fn movie_details(
     Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    headers: HeaderMap,
     Path(movie_id): Path<i32>,
-) -> Result<Html<String>, MoviesWebError> {
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
 
     let movie = service
         .get_movie(movie_id)
         .await?
-        .ok_or_else(|| MoviesWebError::NotFound(movie_id))?;
+        .ok_or_else(|| WebError::not_found("Movie", movie_id))?;
 
-    let movie_details_template = MovieDetailsTemplate { movie }.render()?;
+    let stills = service.get_movie_stills(movie_id).await?;
+    let meta = MovieMetaViewModel::new(&movie, &site_url(&headers), &locale);
+    let movie_details_page = htmx.render(&MovieDetailsTemplate {
+        movie,
+        stills,
+        meta,
+        locale,
+    })?;
 
     info!("queried movie {}", movie_id);
-    Ok(Html(movie_details_template))
+    Ok(movie_details_page)
 }
 
-#[instrument]
+/// The movie page, narrowed by its filters. When the filter form asks,
+/// through htmx's `HX-Target` header, only the grid is sent back; later pages
+/// are only the cards, added after the last one as it scrolls into view.
+#[instrument(skip_all, fields(count, page))]
 pub async fn list_movies(
     Extension(store): Extension<Store>,
-) -> Result<Html<String>, MoviesWebError> {
+    htmx: HtmxRequest,
+    locale: Locale,
+    Query(params): Query<MovieGridParams>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let (movies, pagination) = service.find_movies(&params, params.page()).await?;
+    let filters = service.movie_filters(&params).await?;
+    Span::current().record("count", movies.len());
+    Span::current().record("page", pagination.page);
+
+    let movies_page = if htmx.is_partial() && pagination.page > 1 {
+        let cards = HtmxMovieCardsTemplate {
+            locale,
+            movies,
+            filters,
+            pagination,
+        };
+        fragment(cards.render()?)
+    } else if htmx.targets("movie-grid") {
+        let grid = HtmxMovieGridTemplate {
+            movies,
+            filters,
+            pagination,
+            locale,
+        };
+        fragment(grid.render()?)
+    } else {
+        htmx.render(&MoviesTemplate {
+            movies,
+            filters,
+            pagination,
+            locale,
+        })?
+    };
+
+    info!("queried movies page {}", pagination.page);
+    Ok(movies_page)
+}
+
+/// Renders the movie form; forms without htmx get a blank row per list to
+/// type in, htmx forms add rows on demand.
+fn render_form(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: MovieFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, askama::Error> {
+    let form = if target.htmx {
+        form
+    } else {
+        form.without_blank_rows().with_blank_rows()
+    };
+
+    htmx.render(&MovieFormTemplate {
+        heading: target.heading,
+        form,
+        errors,
+        action: target.action,
+        method: target.method,
+        cancel: target.cancel,
+        htmx: target.htmx,
+        locale,
+    })
+}
+
+/// Shows the form again with the errors next to its inputs.
+fn form_errors(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: MovieFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, WebError> {
+    let status = target.error_status();
+    let page = render_form(htmx, locale, target, form, errors)?;
+
+    Ok((status, page).into_response())
+}
+
+fn create_target(htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    FormTarget::create("movies", locale.t("movie-form-add"), htmx.is_partial())
+}
+
+fn edit_target(movie_id: i32, title: &str, htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    let cancel = format!("/{}", movie_id);
+    let heading = locale.t_with("movie-form-edit", "title", title);
+    FormTarget::edit("movies", movie_id, heading, &cancel, htmx.is_partial())
+}
+
+#[instrument(skip_all)]
+pub async fn new_movie_form(htmx: HtmxRequest, locale: Locale) -> Result<Response, WebError> {
+    let form = MovieFormViewModel::default().with_blank_rows();
+
+    let target = create_target(&htmx, locale);
+    let page = render_form(&htmx, locale, target, form, FormErrors::default())?;
+
+    Ok(page)
+}
+
+#[instrument(skip_all, fields(movie_id))]
+pub async fn create_movie(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    form: FormData,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
+    let movie_form = MovieFormViewModel::from_form(&form);
+
+    if form.pressed("more_rows") {
+        let page = render_form(
+            &htmx,
+            locale,
+            create_target(&htmx, locale),
+            movie_form,
+            FormErrors::default(),
+        )?;
+        return Ok(page);
+    }
 
-    let movies = service.get_movies().await?;
+    match service.create_movie(&movie_form).await {
+        Ok(movie_id) => {
+            Span::current().record("movie_id", movie_id);
+            info!("created movie {}", movie_id);
+            Ok(htmx.redirect(&format!("/movies/{}", movie_id)))
+        }
+        Err(WebError::InvalidForm(errors)) => form_errors(
+            &htmx,
+            locale,
+            create_target(&htmx, locale),
+            movie_form,
+            errors,
+        ),
+        Err(e) => Err(e),
+    }
+}
 
-    let movies_template = MoviesTemplate { movies }.render()?;
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn edit_movie_form(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(movie_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
 
-    info!("queried all movies");
-    Ok(Html(movies_template))
+    let movie = service
+        .get_movie(movie_id)
+        .await?
+        .ok_or_else(|| WebError::not_found("Movie", movie_id))?;
+
+    let target = edit_target(movie_id, &movie.title, &htmx, locale);
+    let form = MovieFormViewModel::from_movie(&movie);
+    Ok(render_form(
+        &htmx,
+        locale,
+        target,
+        form,
+        FormErrors::default(),
+    )?)
 }
 
-#[instrument]
-pub async fn htmx_movie_details(
+/// Saves the edit form, sent as a `POST` without htmx and a `PUT` with it.
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn update_movie(
     Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
     Path(movie_id): Path<i32>,
-) -> Result<Html<String>, MoviesWebError> {
+    form: FormData,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+    let movie_form = MovieFormViewModel::from_form(&form);
+    let target = edit_target(movie_id, &movie_form.title, &htmx, locale);
+
+    if form.pressed("more_rows") {
+        let page = render_form(&htmx, locale, target, movie_form, FormErrors::default())?;
+        return Ok(page);
+    }
+
+    match service.update_movie(movie_id, &movie_form).await {
+        Ok(()) => {
+            info!("updated movie {}", movie_id);
+            Ok(htmx.redirect(&format!("/movies/{}", movie_id)))
+        }
+        Err(WebError::InvalidForm(errors)) => {
+            form_errors(&htmx, locale, target, movie_form, errors)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn confirm_delete_movie(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(movie_id): Path<i32>,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
 
     let movie = service
         .get_movie(movie_id)
         .await?
-        .ok_or_else(|| MoviesWebError::NotFound(movie_id))?;
+        .ok_or_else(|| WebError::not_found("Movie", movie_id))?;
 
-    let htmx_movie_details_template = HtmxMovieDetailsTemplate { movie }.render()?;
+    let action = if htmx.is_partial() {
+        format!("/movies/{}", movie_id)
+    } else {
+        format!("/movies/{}/delete", movie_id)
+    };
+    let confirm_page = htmx.render(&ConfirmDeleteTemplate {
+        kind: "movie",
+        label: movie.title,
+        action,
+        cancel: format!("/movies/{}", movie_id),
+        htmx: htmx.is_partial(),
+        locale,
+    })?;
 
-    info!("htmx queried movie {}", movie_id);
-    Ok(Html(htmx_movie_details_template))
+    Ok(confirm_page)
 }
 
-#[instrument]
-pub async fn htmx_list_movies(
+/// Deletes a movie, sent as a `POST` without htmx and a `DELETE` with it.
+#[instrument(skip_all, fields(movie_id = movie_id))]
+pub async fn delete_movie(
     Extension(store): Extension<Store>,
-) -> Result<Html<String>, MoviesWebError> {
+    htmx: HtmxRequest,
+    Path(movie_id): Path<i32>,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
 
-    let movies = service.get_movies().await?;
+    service.delete_movie(movie_id).await?;
+
+    info!("deleted movie {}", movie_id);
+    Ok(htmx.redirect("/movies"))
+}
+
+/// A blank row for one of the repeatable lists of the movie form.
+#[instrument(skip_all, fields(list = list))]
+pub async fn movie_form_row(
+    locale: Locale,
+    Path(list): Path<String>,
+) -> Result<Html<String>, WebError> {
+    const LISTS: [&str; 6] = [
+        "directors",
+        "producers",
+        "actors",
+        "writers",
+        "awards",
+        "nominations",
+    ];
+    if !LISTS.contains(&list.as_str()) {
+        return Err(WebError::ValidationError(format!(
+            "The movie form has no '{}' list",
+            list
+        )));
+    }
 
-    let htmx_movies_template = HtmxMoviesTemplate { movies }.render()?;
+    let row_template = HtmxMovieRowTemplate {
+        list,
+        row: AwardRowViewModel::default(),
+        locale,
+    }
+    .render()?;
 
-    info!("htmx queried all movies");
-    Ok(Html(htmx_movies_template))
+    Ok(Html(row_template))
 }
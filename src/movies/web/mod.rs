@@ -3,3 +3,6 @@ pub mod handlers;
 pub mod models;
 pub mod service;
 pub mod templates;
+
+#[cfg(test)]
+mod __tests;
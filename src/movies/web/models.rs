@@ -1,5 +1,15 @@
+use crate::forms::{FormData, FormErrors};
+use crate::i18n::Locale;
+use crate::media::thumbnail_url;
+use crate::movies::api::models::{
+    CreateMovieAwardNominationRequest, CreateMovieAwardRequest, CreateMovieRequest,
+};
+use crate::pagination::page_number;
+use crate::seo::absolute_url;
 use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MovieViewModel {
@@ -47,6 +57,143 @@ impl MovieViewModel {
     pub fn has_nominations(&self) -> bool {
         !self.nominations.is_empty()
     }
+
+    /// The poster at grid size.
+    pub fn thumbnail_url(&self) -> String {
+        thumbnail_url(&self.image_url)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MovieStillViewModel {
+    pub image_url: String,
+    pub thumbnail_url: String,
+}
+
+/// Longest page description; search engines cut longer ones.
+const DESCRIPTION_CHARS: usize = 160;
+
+/// What link previews and search engines read of a movie page: its meta,
+/// OpenGraph and Twitter card tags and its schema.org `Movie`.
+#[derive(Debug, Clone)]
+pub struct MovieMetaViewModel {
+    /// Absolute address of the page in the locale it is shown in
+    pub url: String,
+    /// Absolute address of the page in every locale, by language code, and
+    /// of the negotiated one as `x-default`
+    pub alternates: Vec<(&'static str, String)>,
+    pub title: String,
+    /// The start of the plot
+    pub description: String,
+    /// Absolute address of the poster
+    pub image: String,
+    /// JSON-LD, safe to put in a `<script>`
+    pub json_ld: String,
+}
+
+impl MovieMetaViewModel {
+    /// The tags of `movie`'s page shown in `locale` on `site`, the address
+    /// relative posters are resolved against.
+    pub fn new(movie: &MovieViewModel, site: &str, locale: &Locale) -> Self {
+        let path = format!("/movies/{}", movie.id);
+        let url = format!("{}{}", site, locale.prefixed(&path));
+        let alternates = std::iter::once(("x-default", format!("{}{}", site, path)))
+            .chain(Locale::ALL.iter().map(|alternate| {
+                (
+                    alternate.code(),
+                    format!("{}{}", site, alternate.prefixed(&path)),
+                )
+            }))
+            .collect();
+        let image = absolute_url(site, &movie.image_url);
+        let json_ld = Self::movie_json_ld(movie, &url, &image)
+            .to_string()
+            .replace("</", "<\\/");
+
+        Self {
+            title: format!("{} ({})", movie.title, movie.release_year),
+            description: excerpt(&movie.plot, DESCRIPTION_CHARS),
+            image,
+            url,
+            alternates,
+            json_ld,
+        }
+    }
+
+    /// The schema.org `Movie` of the page.
+    fn movie_json_ld(movie: &MovieViewModel, url: &str, image: &str) -> serde_json::Value {
+        let people = |names: &[String]| -> Vec<serde_json::Value> {
+            names
+                .iter()
+                .map(|name| json!({ "@type": "Person", "name": name }))
+                .collect()
+        };
+        let awards: Vec<String> = movie
+            .awards
+            .iter()
+            .map(|award| match &award.recipient {
+                Some(recipient) => format!(
+                    "{} {}, {} ({})",
+                    award.name, award.category, recipient, award.year
+                ),
+                None => format!("{} {} ({})", award.name, award.category, award.year),
+            })
+            .collect();
+
+        let mut movie_json = json!({
+            "@context": "https://schema.org",
+            "@type": "Movie",
+            "name": movie.title,
+            "url": url,
+            "image": image,
+            "description": movie.plot,
+            "datePublished": movie.release_date.to_string(),
+            "genre": movie.genre,
+            "duration": format!("PT{}M", movie.runtime),
+            "director": people(&movie.directors),
+            "producer": people(&movie.producers),
+            "author": people(&movie.writers),
+            "actor": people(&movie.actors),
+            "aggregateRating": {
+                "@type": "AggregateRating",
+                // Widened from `f32` as written, not as 8.300000190734863
+                "ratingValue": (f64::from(movie.rating) * 10.0).round() / 10.0,
+                "bestRating": 10,
+                "worstRating": 0,
+            },
+        });
+        if !awards.is_empty() {
+            movie_json["award"] = json!(awards);
+        }
+        if !movie.youtube_id.is_empty() {
+            movie_json["trailer"] = json!({
+                "@type": "VideoObject",
+                "name": format!("{} trailer", movie.title),
+                "embedUrl": format!("https://www.youtube.com/embed/{}", movie.youtube_id),
+                "thumbnailUrl": image,
+            });
+        }
+
+        movie_json
+    }
+}
+
+/// The start of `text`, cut at a word and marked with an ellipsis when longer
+/// than `max_chars`.
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    // One character more tells whether the last word is whole
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(space) => cut[..space].to_string(),
+        None => cut.chars().take(max_chars - 1).collect(),
+    };
+    let cut = cut.trim_end_matches(|c: char| c.is_whitespace() || c.is_ascii_punctuation());
+    format!("{}…", cut)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -65,11 +212,349 @@ pub struct MovieAwardNominationViewModel {
     pub nominee: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct ErrorViewModel {
-    pub code: u16,
-    pub message: String,
-    pub details: Option<String>,
-    pub show_suggestions: bool,
+/// Filters of the htmx movie grid, as sent by its form. Every field is text
+/// so the "Any" choices, sent empty, and stray values just mean no filter.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MovieGridParams {
+    pub q: String,
+    pub genre: String,
+    pub decade: String,
+    pub min_rating: String,
+    pub page: String,
+}
+
+impl MovieGridParams {
+    pub fn page(&self) -> i64 {
+        page_number(&self.page)
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        Some(self.q.trim()).filter(|q| !q.is_empty())
+    }
+
+    pub fn genre(&self) -> Option<&str> {
+        Some(self.genre.as_str()).filter(|genre| !genre.is_empty())
+    }
+
+    pub fn decade(&self) -> Option<i32> {
+        self.decade.parse().ok()
+    }
+
+    pub fn min_rating(&self) -> Option<f32> {
+        self.min_rating.parse().ok()
+    }
+}
+
+/// The filter form above the htmx movie grid: the current choices and the
+/// ones on offer.
+#[derive(Debug, Clone, Default)]
+pub struct MovieFiltersViewModel {
+    pub q: String,
+    pub genre: String,
+    pub decade: Option<i32>,
+    pub min_rating: Option<f32>,
+    pub genres: Vec<String>,
+    pub decades: Vec<i32>,
+    pub ratings: Vec<i32>,
+}
+
+impl MovieFiltersViewModel {
+    pub fn new(params: &MovieGridParams, genres: Vec<String>, decades: Vec<i32>) -> Self {
+        Self {
+            q: params.text().unwrap_or_default().to_string(),
+            genre: params.genre.clone(),
+            decade: params.decade(),
+            min_rating: params.min_rating(),
+            genres,
+            decades,
+            ratings: vec![6, 7, 8, 9],
+        }
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        !self.q.is_empty()
+            || !self.genre.is_empty()
+            || self.decade.is_some()
+            || self.min_rating.is_some()
+    }
+
+    pub fn is_genre(&self, genre: &str) -> bool {
+        self.genre.eq_ignore_ascii_case(genre)
+    }
+
+    pub fn is_decade(&self, decade: &i32) -> bool {
+        self.decade.map(|d| d / 10) == Some(decade / 10)
+    }
+
+    pub fn is_min_rating(&self, rating: &i32) -> bool {
+        self.min_rating == Some(*rating as f32)
+    }
+
+    /// The movie page with these filters, up to the page number to append.
+    pub fn page_url(&self) -> String {
+        let decade = self.decade.map(|decade| decade.to_string());
+        let min_rating = self.min_rating.map(|rating| rating.to_string());
+        let filters = [
+            ("q", Some(self.q.clone())),
+            ("genre", Some(self.genre.clone())),
+            ("decade", decade),
+            ("min_rating", min_rating),
+        ];
+        let filters: Vec<(&str, String)> = filters
+            .into_iter()
+            .filter_map(|(name, value)| value.filter(|v| !v.is_empty()).map(|v| (name, v)))
+            .collect();
+
+        match serde_urlencoded::to_string(&filters) {
+            Ok(query) if !query.is_empty() => format!("/movies?{}&page=", query),
+            _ => "/movies?page=".to_string(),
+        }
+    }
+}
+
+/// One award or nomination row of the movie form; `person` is the recipient
+/// or the nominee.
+#[derive(Debug, Clone, Default)]
+pub struct AwardRowViewModel {
+    pub name: String,
+    pub category: String,
+    pub year: String,
+    pub person: String,
+}
+
+impl AwardRowViewModel {
+    fn is_blank(&self) -> bool {
+        self.name.is_empty()
+            && self.category.is_empty()
+            && self.year.is_empty()
+            && self.person.is_empty()
+    }
+}
+
+/// The movie form as typed, so it can be shown again with its errors.
+#[derive(Debug, Clone, Default)]
+pub struct MovieFormViewModel {
     pub title: String,
+    pub release_year: String,
+    pub genre: String,
+    pub plot: String,
+    pub runtime: String,
+    pub rating: String,
+    pub release_date: String,
+    pub image_url: String,
+    pub youtube_id: String,
+    pub production_details: String,
+    pub budget: String,
+    pub directors: Vec<String>,
+    pub producers: Vec<String>,
+    pub actors: Vec<String>,
+    pub writers: Vec<String>,
+    pub awards: Vec<AwardRowViewModel>,
+    pub nominations: Vec<AwardRowViewModel>,
+}
+
+impl MovieFormViewModel {
+    pub fn from_movie(movie: &MovieViewModel) -> Self {
+        Self {
+            title: movie.title.clone(),
+            release_year: movie.release_year.to_string(),
+            genre: movie.genre.clone(),
+            plot: movie.plot.clone(),
+            runtime: movie.runtime.to_string(),
+            rating: movie.rating.to_string(),
+            release_date: movie.release_date.to_string(),
+            image_url: movie.image_url.clone(),
+            youtube_id: movie.youtube_id.clone(),
+            production_details: movie.production_details.clone(),
+            budget: movie.budget.clone(),
+            directors: movie.directors.clone(),
+            producers: movie.producers.clone(),
+            actors: movie.actors.clone(),
+            writers: movie.writers.clone(),
+            awards: movie
+                .awards
+                .iter()
+                .map(|award| AwardRowViewModel {
+                    name: award.name.clone(),
+                    category: award.category.clone(),
+                    year: award.year.to_string(),
+                    person: award.recipient.clone().unwrap_or_default(),
+                })
+                .collect(),
+            nominations: movie
+                .nominations
+                .iter()
+                .map(|nomination| AwardRowViewModel {
+                    name: nomination.name.clone(),
+                    category: nomination.category.clone(),
+                    year: nomination.year.to_string(),
+                    person: nomination.nominee.clone().unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads the form; each row list comes from its repeated inputs, such
+    /// as `award_name`, `award_category`, `award_year` and `award_person`.
+    pub fn from_form(form: &FormData) -> Self {
+        let rows = |prefix: &str| {
+            let names = form.values(&format!("{}_name", prefix));
+            let categories = form.values(&format!("{}_category", prefix));
+            let years = form.values(&format!("{}_year", prefix));
+            let people = form.values(&format!("{}_person", prefix));
+            (0..names.len())
+                .map(|i| AwardRowViewModel {
+                    name: names[i].clone(),
+                    category: categories.get(i).cloned().unwrap_or_default(),
+                    year: years.get(i).cloned().unwrap_or_default(),
+                    person: people.get(i).cloned().unwrap_or_default(),
+                })
+                .collect()
+        };
+
+        Self {
+            title: form.value("title"),
+            release_year: form.value("release_year"),
+            genre: form.value("genre"),
+            plot: form.value("plot"),
+            runtime: form.value("runtime"),
+            rating: form.value("rating"),
+            release_date: form.value("release_date"),
+            image_url: form.value("image_url"),
+            youtube_id: form.value("youtube_id"),
+            production_details: form.value("production_details"),
+            budget: form.value("budget"),
+            directors: form.values("directors"),
+            producers: form.values("producers"),
+            actors: form.values("actors"),
+            writers: form.values("writers"),
+            awards: rows("award"),
+            nominations: rows("nomination"),
+        }
+    }
+
+    /// `(input name, label message id, names)` of the cast and crew lists, in
+    /// form order.
+    pub fn people_lists(&self) -> [(&'static str, &'static str, &[String]); 4] {
+        [
+            ("directors", "field-directors", &self.directors),
+            ("producers", "field-producers", &self.producers),
+            ("writers", "field-writers", &self.writers),
+            ("actors", "field-actors", &self.actors),
+        ]
+    }
+
+    /// Drops the rows left blank, so removing a person is clearing their input.
+    pub fn without_blank_rows(mut self) -> Self {
+        for names in [
+            &mut self.directors,
+            &mut self.producers,
+            &mut self.actors,
+            &mut self.writers,
+        ] {
+            names.retain(|name| !name.is_empty());
+        }
+        self.awards.retain(|row| !row.is_blank());
+        self.nominations.retain(|row| !row.is_blank());
+        self
+    }
+
+    /// Adds an empty row to every list, for the next person or award.
+    pub fn with_blank_rows(mut self) -> Self {
+        for names in [
+            &mut self.directors,
+            &mut self.producers,
+            &mut self.actors,
+            &mut self.writers,
+        ] {
+            names.push(String::new());
+        }
+        self.awards.push(AwardRowViewModel::default());
+        self.nominations.push(AwardRowViewModel::default());
+        self
+    }
+
+    /// The request the API would take, or the errors to show next to the inputs.
+    pub fn to_request(&self) -> Result<CreateMovieRequest, FormErrors> {
+        let form = self.clone().without_blank_rows();
+        let mut errors = FormErrors::default();
+
+        let release_year = errors.parse(
+            "release_year",
+            &form.release_year,
+            "Release year must be a number",
+        );
+        let runtime = errors.parse(
+            "runtime",
+            &form.runtime,
+            "Runtime must be a number of minutes",
+        );
+        let rating = errors.parse("rating", &form.rating, "Rating must be a number");
+        let release_date = NaiveDate::parse_from_str(&form.release_date, "%Y-%m-%d")
+            .unwrap_or_else(|_| {
+                errors.add("release_date", "Release date must be a date");
+                NaiveDate::default()
+            });
+
+        let mut awards = Vec::new();
+        for row in &form.awards {
+            awards.push(CreateMovieAwardRequest {
+                name: row.name.clone(),
+                category: row.category.clone(),
+                year: errors.parse("awards", &row.year, "Every award needs a year"),
+                recipient: row.person.clone(),
+            });
+        }
+        let mut nominations = Vec::new();
+        for row in &form.nominations {
+            nominations.push(CreateMovieAwardNominationRequest {
+                name: row.name.clone(),
+                category: row.category.clone(),
+                year: errors.parse("nominations", &row.year, "Every nomination needs a year"),
+                nominee: row.person.clone(),
+            });
+        }
+        if form
+            .awards
+            .iter()
+            .any(|row| row.name.is_empty() || row.category.is_empty())
+        {
+            errors.add("awards", "Every award needs a name and a category");
+        }
+        if form
+            .nominations
+            .iter()
+            .any(|row| row.name.is_empty() || row.category.is_empty())
+        {
+            errors.add(
+                "nominations",
+                "Every nomination needs a name and a category",
+            );
+        }
+
+        let request = CreateMovieRequest {
+            title: form.title,
+            release_year,
+            genre: form.genre,
+            plot: form.plot,
+            runtime,
+            rating,
+            release_date,
+            image_url: form.image_url,
+            youtube_id: form.youtube_id,
+            production_details: form.production_details,
+            budget: form.budget,
+            directors: form.directors,
+            producers: form.producers,
+            actors: form.actors,
+            writers: form.writers,
+            awards,
+            nominations,
+        };
+        if let Err(validation) = request.validate() {
+            errors.merge(&validation);
+        }
+        errors.into_result().map(|_| request)
+    }
 }
@@ -1,13 +1,20 @@
-use crate::movies::data::repository::MovieRepository;
-use crate::movies::web::errors::MoviesWebError;
+use crate::movies::api::service::ApiService;
+use crate::movies::data::repository::{MovieFilter, MoviePage, MovieRepository};
+use crate::web_error::WebError;
 use crate::movies::web::models::{
-    MovieAwardNominationViewModel, MovieAwardViewModel, MovieViewModel,
+    MovieAwardNominationViewModel, MovieAwardViewModel, MovieFiltersViewModel, MovieFormViewModel,
+    MovieGridParams, MovieStillViewModel, MovieViewModel,
 };
+use crate::media::{data::repository::MediaRepository, thumbnail_url};
 
+use crate::pagination::Pagination;
 use futures::TryStreamExt;
 use futures::stream::{self, StreamExt};
 use sqlx::PgPool;
 
+/// Movies per page of the web lists
+pub const MOVIES_PER_PAGE: i64 = 12;
+
 pub struct WebService {
     repo: MovieRepository,
 }
@@ -18,7 +25,7 @@ impl WebService {
             repo: MovieRepository::new(pool),
         }
     }
-    pub async fn get_movie(&self, movie_id: i32) -> Result<Option<MovieViewModel>, MoviesWebError> {
+    pub async fn get_movie(&self, movie_id: i32) -> Result<Option<MovieViewModel>, WebError> {
         // 1. Movie
         let movie = match self.repo.get_movie_by_id(movie_id).await? {
             None => return Ok(None),
@@ -67,9 +74,48 @@ impl WebService {
         Ok(Some(movie_view_model))
     }
 
-    pub async fn get_movies(&self) -> Result<Vec<MovieViewModel>, MoviesWebError> {
-        let movies_ids = self.repo.get_all_movies_ids().await?;
-        let concurrency = movies_ids.len();
+    /// One page of the movies that match the grid's filters, oldest first.
+    pub async fn find_movies(
+        &self,
+        params: &MovieGridParams,
+        page: i64,
+    ) -> Result<(Vec<MovieViewModel>, Pagination), WebError> {
+        let pagination = Pagination::new(page, MOVIES_PER_PAGE);
+        let filter = MovieFilter {
+            text: params.text(),
+            genre: params.genre(),
+            decade: params.decade(),
+            min_rating: params.min_rating(),
+            ..MovieFilter::default()
+        };
+        let movie_page = MoviePage {
+            sort: "release_year",
+            descending: false,
+            limit: Some(pagination.limit()),
+            offset: pagination.offset(),
+        };
+        let (movies_ids, total) = self.repo.find_movies_ids(&filter, &movie_page).await?;
+        let movies = self.get_movies_by_ids(movies_ids).await?;
+
+        Ok((movies, pagination.with_total(total)))
+    }
+
+    /// The grid's filter form, with the genres and decades there are movies for.
+    pub async fn movie_filters(
+        &self,
+        params: &MovieGridParams,
+    ) -> Result<MovieFiltersViewModel, WebError> {
+        let genres = self.repo.get_genres_names().await?;
+        let decades = self.repo.get_release_decades().await?;
+
+        Ok(MovieFiltersViewModel::new(params, genres, decades))
+    }
+
+    async fn get_movies_by_ids(
+        &self,
+        movies_ids: Vec<i32>,
+    ) -> Result<Vec<MovieViewModel>, WebError> {
+        let concurrency = movies_ids.len().max(1);
 
         let movies = stream::iter(movies_ids)
             .map(|movie_id| self.get_movie(movie_id))
@@ -83,25 +129,25 @@ impl WebService {
         Ok(movies)
     }
 
-    pub async fn get_movie_actors(&self, movie_id: i32) -> Result<Vec<String>, MoviesWebError> {
+    pub async fn get_movie_actors(&self, movie_id: i32) -> Result<Vec<String>, WebError> {
         let actors = self.repo.get_movie_actors_names(movie_id).await?;
 
         Ok(actors)
     }
 
-    pub async fn get_movie_directors(&self, movie_id: i32) -> Result<Vec<String>, MoviesWebError> {
+    pub async fn get_movie_directors(&self, movie_id: i32) -> Result<Vec<String>, WebError> {
         let directors = self.repo.get_movie_directors_names(movie_id).await?;
 
         Ok(directors)
     }
 
-    pub async fn get_movie_writers(&self, movie_id: i32) -> Result<Vec<String>, MoviesWebError> {
+    pub async fn get_movie_writers(&self, movie_id: i32) -> Result<Vec<String>, WebError> {
         let writers = self.repo.get_movie_writers_names(movie_id).await?;
 
         Ok(writers)
     }
 
-    pub async fn get_movie_producers(&self, movie_id: i32) -> Result<Vec<String>, MoviesWebError> {
+    pub async fn get_movie_producers(&self, movie_id: i32) -> Result<Vec<String>, WebError> {
         let producers = self.repo.get_movie_producers_names(movie_id).await?;
 
         Ok(producers)
@@ -110,7 +156,7 @@ impl WebService {
     pub async fn get_movie_awards(
         &self,
         movie_id: i32,
-    ) -> Result<Vec<MovieAwardViewModel>, MoviesWebError> {
+    ) -> Result<Vec<MovieAwardViewModel>, WebError> {
         let awards = self
             .repo
             .get_movie_awards_won(movie_id)
@@ -130,7 +176,7 @@ impl WebService {
     pub async fn get_movie_nominations(
         &self,
         movie_id: i32,
-    ) -> Result<Vec<MovieAwardNominationViewModel>, MoviesWebError> {
+    ) -> Result<Vec<MovieAwardNominationViewModel>, WebError> {
         let nominations = self
             .repo
             .get_movie_awards_nominations(movie_id)
@@ -146,4 +192,52 @@ impl WebService {
 
         Ok(nominations)
     }
+
+    pub async fn get_movie_stills(
+        &self,
+        movie_id: i32,
+    ) -> Result<Vec<MovieStillViewModel>, WebError> {
+        let stills = MediaRepository::new(&self.repo.pool)
+            .get_movie_stills(movie_id)
+            .await?
+            .into_iter()
+            .map(|still| MovieStillViewModel {
+                thumbnail_url: thumbnail_url(&still.image_url),
+                image_url: still.image_url,
+            })
+            .collect();
+
+        Ok(stills)
+    }
+
+    /// Saves a new movie from the form, returning its id.
+    pub async fn create_movie(&self, form: &MovieFormViewModel) -> Result<i32, WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        let movie_id = ApiService::new(&self.repo.pool)
+            .create_movie(request)
+            .await?;
+
+        Ok(movie_id)
+    }
+
+    pub async fn update_movie(
+        &self,
+        movie_id: i32,
+        form: &MovieFormViewModel,
+    ) -> Result<(), WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        ApiService::new(&self.repo.pool)
+            .update_movie(movie_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_movie(&self, movie_id: i32) -> Result<(), WebError> {
+        ApiService::new(&self.repo.pool)
+            .delete_movie(movie_id)
+            .await?;
+
+        Ok(())
+    }
 }
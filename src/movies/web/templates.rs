@@ -1,39 +1,95 @@
-use crate::movies::web::models::{ErrorViewModel, MovieViewModel};
+use crate::forms::FormErrors;
+use crate::htmx::impl_page;
+use crate::i18n::Locale;
+use crate::movies::web::models::{
+    AwardRowViewModel, MovieFiltersViewModel, MovieFormViewModel, MovieMetaViewModel,
+    MovieStillViewModel, MovieViewModel,
+};
+use crate::pagination::Pagination;
 use askama::Template;
 #[derive(Template)]
 #[template(path = "base.html")]
-pub struct BaseTemplate;
+pub struct BaseTemplate {
+    pub locale: Locale,
+}
 
 #[derive(Template)]
 #[template(path = "pages/home.html")]
-pub struct HomeTemplate;
+pub struct HomeTemplate {
+    pub locale: Locale,
+}
 
 #[derive(Template)]
-#[template(path = "pages/movie_detail.html")]
+#[template(path = "pages/movie_detail.html", blocks = ["title", "main"])]
 pub struct MovieDetailsTemplate {
+    pub locale: Locale,
     pub movie: MovieViewModel,
+    pub stills: Vec<MovieStillViewModel>,
+    pub meta: MovieMetaViewModel,
 }
 
 #[derive(Template)]
-#[template(path = "pages/movies.html")]
+#[template(path = "pages/movies.html", blocks = ["title", "main"])]
 pub struct MoviesTemplate {
+    pub locale: Locale,
     pub movies: Vec<MovieViewModel>,
+    pub filters: MovieFiltersViewModel,
+    pub pagination: Pagination,
 }
 
+/// Only the grid of the movie page, swapped in when a filter changes.
 #[derive(Template)]
-#[template(path = "pages/htmx/movie_detail.html")]
-pub struct HtmxMovieDetailsTemplate {
-    pub movie: MovieViewModel,
+#[template(path = "partials/htmx/movie_grid.html")]
+pub struct HtmxMovieGridTemplate {
+    pub locale: Locale,
+    pub movies: Vec<MovieViewModel>,
+    pub filters: MovieFiltersViewModel,
+    pub pagination: Pagination,
 }
 
+/// The next page of grid cards, added after the last one on screen.
 #[derive(Template)]
-#[template(path = "pages/htmx/movies.html")]
-pub struct HtmxMoviesTemplate {
+#[template(path = "partials/htmx/movie_cards.html")]
+pub struct HtmxMovieCardsTemplate {
+    pub locale: Locale,
     pub movies: Vec<MovieViewModel>,
+    pub filters: MovieFiltersViewModel,
+    pub pagination: Pagination,
+}
+
+/// A movie card sent to the open pages when the movie is saved elsewhere.
+#[derive(Template)]
+#[template(path = "partials/live/movie.html")]
+pub struct LiveMovieTemplate {
+    pub movie: MovieViewModel,
+    /// Added at the top of the grid rather than replacing its card
+    pub created: bool,
+    pub locale: Locale,
+}
+
+#[derive(Template)]
+#[template(path = "pages/movie_form.html", blocks = ["title", "main"])]
+pub struct MovieFormTemplate {
+    pub locale: Locale,
+    pub heading: String,
+    pub form: MovieFormViewModel,
+    pub errors: FormErrors,
+    /// Where the form is sent
+    pub action: String,
+    /// `post` or `put`; forms without htmx always post
+    pub method: &'static str,
+    pub cancel: String,
+    pub htmx: bool,
 }
 
+/// A blank row appended to one of the repeatable lists of the movie form.
 #[derive(Template)]
-#[template(path = "pages/error.html")]
-pub struct ErrorTemplate {
-    pub error: ErrorViewModel,
+#[template(path = "partials/forms/movie_row.html")]
+pub struct HtmxMovieRowTemplate {
+    pub locale: Locale,
+    /// `directors`, `producers`, `actors`, `writers`, `awards` or `nominations`
+    pub list: String,
+    pub row: AwardRowViewModel,
 }
+
+impl_page!(MovieDetailsTemplate, MoviesTemplate, MovieFormTemplate);
@@ -0,0 +1,97 @@
+//! Page numbers of the web lists. The repositories do the slicing with
+//! `LIMIT`/`OFFSET`; this only turns `?page=` into those and back into links.
+
+use serde::Deserialize;
+
+/// `?page=` of a web list. Kept as text so a missing or stray value means the
+/// first page rather than a rejected request.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PageQuery {
+    pub page: String,
+}
+
+impl PageQuery {
+    pub fn number(&self) -> i64 {
+        page_number(&self.page)
+    }
+}
+
+/// A 1-based page number, or 1 when `page` is not one.
+pub fn page_number(page: &str) -> i64 {
+    page.parse().ok().filter(|page| *page >= 1).unwrap_or(1)
+}
+
+/// Where a page sits in the whole list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    /// 1-based
+    pub page: i64,
+    pub per_page: i64,
+    /// Records across all pages
+    pub total: i64,
+}
+
+impl Pagination {
+    /// A page whose total is not known yet.
+    pub fn new(page: i64, per_page: i64) -> Self {
+        Self {
+            page: page.max(1),
+            per_page,
+            total: 0,
+        }
+    }
+
+    pub fn with_total(self, total: i64) -> Self {
+        Self { total, ..self }
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.per_page
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+
+    pub fn last_page(&self) -> i64 {
+        ((self.total + self.per_page - 1) / self.per_page).max(1)
+    }
+
+    pub fn has_previous(&self) -> bool {
+        self.page > 1
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.page < self.last_page()
+    }
+
+    pub fn previous(&self) -> i64 {
+        (self.page - 1).max(1)
+    }
+
+    pub fn next(&self) -> i64 {
+        self.page + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_cover_the_total() {
+        let first = Pagination::new(page_number(""), 12).with_total(25);
+        assert_eq!((first.offset(), first.limit()), (0, 12));
+        assert_eq!(first.last_page(), 3);
+        assert!(!first.has_previous() && first.has_next());
+
+        let last = Pagination::new(page_number("3"), 12).with_total(25);
+        assert_eq!(last.offset(), 24);
+        assert!(last.has_previous() && !last.has_next());
+
+        assert_eq!(page_number("0"), 1);
+        assert_eq!(page_number("two"), 1);
+        assert_eq!(Pagination::new(1, 12).last_page(), 1);
+    }
+}
@@ -30,6 +30,9 @@ pub enum QuotesApiError {
     #[error("quote not found")]
     NotFound(i32),
 
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
     #[error("Validation failed")]
     Validation(#[from] ValidationErrors),
 
@@ -44,6 +47,7 @@ impl QuotesApiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::InvalidQuery(_) => StatusCode::BAD_REQUEST,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
             Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -53,6 +57,7 @@ impl QuotesApiError {
     pub fn user_message(&self) -> String {
         match self {
             Self::NotFound(id) => format!("Quote with ID {} not found", id),
+            Self::InvalidQuery(reason) => reason.clone(),
             Self::Validation(_) => "Request validation failed".to_string(),
             Self::DatabaseError(_) => "A database error occurred".to_string(),
             Self::InternalError(_) => "An internal error occurred".to_string(),
@@ -62,6 +67,7 @@ impl QuotesApiError {
     pub fn validation_details(&self) -> Option<ValidationDetails> {
         match self {
             Self::NotFound(id) => None,
+            Self::InvalidQuery(_) => None,
             Self::DatabaseError(_) => None,
             Self::InternalError(_) => None,
             Self::Validation(errors) => {
@@ -1,5 +1,10 @@
 use crate::quotes::api::errors::QuotesApiError;
-use axum::{Json, body::Body, extract::FromRequest, http::Request};
+use axum::{
+    Json,
+    body::Body,
+    extract::{FromRequest, FromRequestParts, Query},
+    http::{Request, request::Parts},
+};
 use serde::de::DeserializeOwned;
 use validator::Validate;
 
@@ -24,3 +29,23 @@ where
         Ok(ValidatedJson(value))
     }
 }
+
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = QuotesApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|err| QuotesApiError::InvalidQuery(err.body_text()))?;
+
+        value.validate()?;
+
+        Ok(ValidatedQuery(value))
+    }
+}
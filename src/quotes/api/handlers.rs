@@ -1,9 +1,9 @@
 // use crate::quotes::api::errors::{ApiErrorResponse, QuotesApiError};
 
 use crate::quotes::api::errors::{QuoteApiErrorResponse, QuotesApiError};
-use crate::quotes::api::extractors::ValidatedJson;
+use crate::quotes::api::extractors::{ValidatedJson, ValidatedQuery};
 use crate::quotes::api::models::{
-    CreateQuoteRequest, QuoteListResponse, QuoteMessage, QuoteResponse,
+    CreateQuoteRequest, QuoteListParams, QuoteListResponse, QuoteMessage, QuoteResponse,
 };
 use crate::quotes::api::service::ApiService;
 
@@ -12,7 +12,7 @@ use axum::Json;
 use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use tracing::{error, info, instrument};
+use tracing::{Span, error, info, instrument};
 use utoipa::OpenApi;
 use crate::store::Store;
 
@@ -23,24 +23,30 @@ use crate::store::Store;
     path = "/quotes",
     request_body = CreateQuoteRequest,
     responses(
-        (status = 201, description = "Quote Created", body = CreateQuoteRequest),
+        (status = 201, description = "Quote Created", body = QuoteMessage),
         (status = 400, description = "Request Validation Error", body = QuoteApiErrorResponse),
         (status = 500, description = "Internal server error", body = QuoteApiErrorResponse)
     ),
     tag = "Quotes API"
 )]
-#[instrument]
+#[instrument(skip_all)]
 pub async fn add_new_quote(
     Extension(store): Extension<Store>,
     ValidatedJson(new_quote): ValidatedJson<CreateQuoteRequest>,
 ) -> impl IntoResponse {
     let service = ApiService::new(&store.connection);
     let quote_text = new_quote.text.clone();
-    let _ = service.create_quote(new_quote).await.unwrap();
+    let id = service.create_quote(new_quote).await.unwrap();
 
     let message = format!("Quote '{}' added ", quote_text);
     info!(%message);
-    (StatusCode::CREATED, Json(QuoteMessage { message }))
+    (
+        StatusCode::CREATED,
+        Json(QuoteMessage {
+            message,
+            id: Some(id),
+        }),
+    )
 }
 
 /// Get quote by ID
@@ -54,7 +60,7 @@ pub async fn add_new_quote(
     ),
     tag = "Quotes API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(quote_id = quote_id))]
 pub async fn get_quote(
     Extension(store): Extension<Store>,
     Path(quote_id): Path<i32>,
@@ -72,23 +78,27 @@ pub async fn get_quote(
     Ok(Json(quote))
 }
 
-/// Get list of quotes
+/// Get list of quotes, optionally paginated
 #[utoipa::path(
     get,
     path = "/quotes",
+    params(QuoteListParams),
     responses(
         (status = 200, description = "List of Quotes", body = QuoteListResponse),
+        (status = 400, description = "Invalid query parameters", body = QuoteApiErrorResponse),
         (status = 500, description = "Database server error", body = QuoteApiErrorResponse)
     ),
     tag = "Quotes API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(count))]
 pub async fn get_quotes(
     Extension(store): Extension<Store>,
+    ValidatedQuery(params): ValidatedQuery<QuoteListParams>,
 ) -> Result<impl IntoResponse, QuotesApiError> {
     let service = ApiService::new(&store.connection);
 
-    let quotes = service.get_quotes().await?;
+    let quotes = service.get_quotes_page(&params).await?;
+    Span::current().record("count", quotes.quotes.len());
 
     info!("queried all quotes");
     Ok(Json(quotes))
@@ -105,7 +115,7 @@ pub async fn get_quotes(
     ),
     tag = "Quotes API"
 )]
-#[instrument]
+#[instrument(skip_all, fields(quote_id = quote_id))]
 pub async fn remove_quote(
     Extension(store): Extension<Store>,
     Path(quote_id): Path<i32>,
@@ -117,7 +127,7 @@ pub async fn remove_quote(
     let message = format!("Quote {quote_id} deleted");
 
     info!(%message);
-    Ok(Json(QuoteMessage { message }))
+    Ok(Json(QuoteMessage { message, id: None }))
 }
 
 #[derive(OpenApi)]
@@ -1,8 +1,10 @@
 use crate::quotes::api::errors::QuotesApiError;
-use crate::quotes::api::models::{CreateQuoteRequest, QuoteListResponse, QuoteResponse};
+use crate::quotes::api::models::{
+    CreateQuoteRequest, QuoteListParams, QuoteListResponse, QuoteResponse,
+};
 use crate::quotes::data::repository::QuoteRepository;
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
 pub struct ApiService {
     repo: QuoteRepository,
@@ -15,14 +17,25 @@ impl ApiService {
         }
     }
 
-    pub async fn create_quote(&self, new_quote: CreateQuoteRequest) -> Result<(), QuotesApiError> {
+    pub async fn create_quote(&self, new_quote: CreateQuoteRequest) -> Result<i32, QuotesApiError> {
         let mut tx = self.repo.pool.begin().await?;
 
-        let _ = self.repo.create_quote(&mut tx, new_quote.text).await?;
+        let id = self.insert_quote(&mut tx, new_quote).await?;
 
         tx.commit().await?;
 
-        Ok(())
+        Ok(id)
+    }
+
+    /// Inserts a quote inside `tx`, returning the new quote id.
+    pub async fn insert_quote(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        new_quote: CreateQuoteRequest,
+    ) -> Result<i32, QuotesApiError> {
+        let quote = self.repo.create_quote(tx, new_quote.text).await?;
+
+        Ok(quote.id)
     }
 
     pub async fn get_quote(&self, quote_id: i32) -> Result<Option<QuoteResponse>, QuotesApiError> {
@@ -30,20 +43,52 @@ impl ApiService {
 
         match quote {
             None => Ok(None),
-            Some(q) => Ok(Some(QuoteResponse { text: q.text })),
+            Some(q) => Ok(Some(QuoteResponse {
+                id: q.id,
+                text: q.text,
+            })),
         }
     }
 
-    pub async fn get_quotes(&self) -> Result<QuoteListResponse, QuotesApiError> {
+    pub async fn get_quotes_page(
+        &self,
+        params: &QuoteListParams,
+    ) -> Result<QuoteListResponse, QuotesApiError> {
         let quotes = self
             .repo
-            .get_quotes()
+            .get_quotes_page(params.limit, params.offset.unwrap_or(0))
             .await?
             .into_iter()
-            .map(|q| QuoteResponse { text: q.text })
+            .map(|q| QuoteResponse {
+                id: q.id,
+                text: q.text,
+            })
             .collect();
 
-        Ok(QuoteListResponse { quotes })
+        let total = self.repo.count_quotes().await?;
+
+        Ok(QuoteListResponse { quotes, total })
+    }
+
+    pub async fn update_quote(
+        &self,
+        quote_id: i32,
+        quote: CreateQuoteRequest,
+    ) -> Result<(), QuotesApiError> {
+        let _ = self
+            .get_quote(quote_id)
+            .await?
+            .ok_or(QuotesApiError::NotFound(quote_id))?;
+
+        let mut tx = self.repo.pool.begin().await?;
+
+        self.repo
+            .update_quote(&mut tx, quote_id, quote.text)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
     }
 
     pub async fn delete_quote(&self, quote_id: i32) -> Result<(), QuotesApiError> {
@@ -1,7 +1,9 @@
 use crate::quotes::data::entities::Quote;
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::NaiveDateTime;
 use sqlx::{PgPool, Postgres, Transaction};
 
+use crate::store::traced_query;
+
 pub struct QuoteRepository {
     pub pool: PgPool,
 }
@@ -16,39 +18,168 @@ impl QuoteRepository {
         tx: &mut Transaction<'_, Postgres>,
         text: String,
     ) -> Result<Quote, sqlx::Error> {
-        sqlx::query_as!(
-            Quote,
-            r#"
-            INSERT INTO quote (text ) VALUES ($1)
-            ON CONFLICT (text) DO UPDATE SET text = EXCLUDED.text
-            RETURNING *
-            "#,
-            text
+        traced_query(
+            "create_quote",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                INSERT INTO quote (text ) VALUES ($1)
+                ON CONFLICT (text) DO UPDATE SET text = EXCLUDED.text
+                RETURNING *
+                "#,
+                text
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_one(&mut **tx)
         .await
     }
 
     pub async fn get_quote(&self, quote_id: i32) -> Result<Option<Quote>, sqlx::Error> {
-        sqlx::query_as!(
-            Quote,
-            r#"
-            SELECT * FROM quote WHERE id = $1
-            "#,
-            quote_id
+        traced_query(
+            "get_quote",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                SELECT * FROM quote WHERE id = $1
+                "#,
+                quote_id
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn get_quote_by_text(&self, text: &str) -> Result<Option<Quote>, sqlx::Error> {
+        traced_query(
+            "get_quote_by_text",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                SELECT * FROM quote WHERE text = $1
+                "#,
+                text
+            )
+            .fetch_optional(&self.pool),
+        )
+        .await
+    }
+
+    /// Whether a quote with this exact text exists, checked within `tx`.
+    pub async fn quote_text_exists(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        text: &str,
+    ) -> Result<bool, sqlx::Error> {
+        traced_query(
+            "quote_text_exists",
+            sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(SELECT 1 FROM quote WHERE text = $1) AS "exists!"
+                "#,
+                text
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_optional(&self.pool)
         .await
     }
 
     pub async fn get_quotes(&self) -> Result<Vec<Quote>, sqlx::Error> {
-        sqlx::query_as!(
-            Quote,
-            r#"
-            SELECT * FROM quote ORDER BY text ASC
-            "#,
+        traced_query(
+            "get_quotes",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                SELECT * FROM quote ORDER BY text ASC
+                "#,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// One page of quotes in text order; `None` returns every quote after `offset`.
+    pub async fn get_quotes_page(
+        &self,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> Result<Vec<Quote>, sqlx::Error> {
+        traced_query(
+            "get_quotes_page",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                SELECT * FROM quote ORDER BY text ASC LIMIT $1 OFFSET $2
+                "#,
+                limit,
+                offset,
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    /// The `limit` most recently added quotes, newest first.
+    pub async fn get_latest_quotes(&self, limit: i64) -> Result<Vec<Quote>, sqlx::Error> {
+        traced_query(
+            "get_latest_quotes",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                SELECT * FROM quote ORDER BY created_at DESC, id DESC LIMIT $1
+                "#,
+                limit
+            )
+            .fetch_all(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn count_quotes(&self) -> Result<i64, sqlx::Error> {
+        traced_query(
+            "count_quotes",
+            sqlx::query_scalar!(
+                r#"
+                SELECT COUNT(*) AS "count!" FROM quote
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    /// When a quote was last added or edited; `None` without any.
+    pub async fn get_last_update(&self) -> Result<Option<NaiveDateTime>, sqlx::Error> {
+        traced_query(
+            "get_last_quote_update",
+            sqlx::query_scalar!(
+                r#"
+                SELECT MAX(updated_at) FROM quote
+                "#,
+            )
+            .fetch_one(&self.pool),
+        )
+        .await
+    }
+
+    pub async fn update_quote(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        quote_id: i32,
+        text: String,
+    ) -> Result<Quote, sqlx::Error> {
+        traced_query(
+            "update_quote",
+            sqlx::query_as!(
+                Quote,
+                r#"
+                UPDATE quote SET text = $2 WHERE id = $1
+                RETURNING *
+                "#,
+                quote_id,
+                text
+            )
+            .fetch_one(&mut **tx),
         )
-        .fetch_all(&self.pool)
         .await
     }
 
@@ -57,13 +188,16 @@ impl QuoteRepository {
         tx: &mut Transaction<'_, Postgres>,
         quote_id: i32,
     ) -> Result<(), sqlx::Error> {
-        sqlx::query!(
-            r#"
-            DELETE FROM quote WHERE id = $1
-            "#,
-            quote_id
+        traced_query(
+            "delete_quote",
+            sqlx::query!(
+                r#"
+                DELETE FROM quote WHERE id = $1
+                "#,
+                quote_id
+            )
+            .execute(&mut **tx),
         )
-        .execute(&mut **tx)
         .await?;
 
         Ok(())
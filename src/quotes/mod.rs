@@ -4,7 +4,7 @@ pub mod web;
 
 use axum::{
     Extension, Router,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
 };
 
 
@@ -25,17 +25,19 @@ pub fn rest_api_router(quote_store: &Store) -> Router {
 pub fn web_router(quote_store: &Store) -> Router {
     let router = Router::new()
         .route("/", get(web::handlers::list_quotes))
-        // .route("/{quote_id}", get(web::handlers::movie_details))
+        .route("/", post(web::handlers::create_quote))
+        .route("/new", get(web::handlers::new_quote_form))
+        .route("/{quote_id}", post(web::handlers::update_quote))
+        .route("/{quote_id}", put(web::handlers::update_quote))
+        .route("/{quote_id}", delete(web::handlers::delete_quote))
+        .route("/{quote_id}/edit", get(web::handlers::edit_quote_form))
+        .route(
+            "/{quote_id}/delete",
+            get(web::handlers::confirm_delete_quote),
+        )
+        .route("/{quote_id}/delete", post(web::handlers::delete_quote))
         .layer(Extension(quote_store.clone()));
         // .with_state(quote_store);
 
     router
 }
-
-pub fn htmx_web_router(quote_store: &Store) -> Router {
-    let router = Router::new()
-        .route("/", get(web::handlers::htmx_list_quotes))
-        .layer(Extension(quote_store.clone()));
-
-    router
-}
@@ -1 +1,23 @@
+use crate::forms::FormErrors;
+use crate::quotes::api::errors::QuotesApiError;
+use crate::web_error::WebError;
 
+impl From<QuotesApiError> for WebError {
+    fn from(error: QuotesApiError) -> Self {
+        match error {
+            QuotesApiError::NotFound(id) => Self::not_found("Quote", id),
+            QuotesApiError::Validation(validation) => {
+                let mut errors = FormErrors::default();
+                errors.merge(&validation);
+                Self::InvalidForm(errors)
+            }
+            QuotesApiError::DatabaseError(e) => {
+                match FormErrors::from_unique_violation(&e, &["text"]) {
+                    Some(errors) => Self::InvalidForm(errors),
+                    None => Self::DatabaseError(e),
+                }
+            }
+            other => Self::InternalError(other.to_string()),
+        }
+    }
+}
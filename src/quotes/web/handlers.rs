@@ -1,36 +1,230 @@
-use crate::quotes::{
-
-    web::{
-        models::QuoteViewModel,
-        service::WebService,
-        templates::{HtmxQuotesTemplate, QuotesTemplate},
-    },
+use crate::forms::{ConfirmDeleteTemplate, FormData, FormErrors, FormTarget};
+use crate::htmx::{HtmxRequest, fragment};
+use crate::i18n::Locale;
+use crate::pagination::PageQuery;
+use crate::quotes::web::{
+    models::QuoteFormViewModel,
+    service::WebService,
+    templates::{HtmxQuoteCardsTemplate, QuoteFormTemplate, QuotesTemplate},
 };
 use crate::store::Store;
+use crate::web_error::WebError;
 use askama::Template;
-use axum::{Extension, extract::Path, http::StatusCode, response::Html};
-use tracing::{info, instrument};
+use axum::{
+    Extension,
+    extract::{Path, Query},
+    response::{IntoResponse, Response},
+};
+use tracing::{Span, info, instrument};
+
+/// The quote page; for htmx, later pages are only the cards, added after the
+/// last one as it scrolls into view.
+#[instrument(skip_all, fields(count, page))]
+pub async fn list_quotes(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Query(page): Query<PageQuery>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let (quotes, pagination) = service.get_quotes_page(page.number()).await?;
+    Span::current().record("count", quotes.len());
+    Span::current().record("page", pagination.page);
+
+    let quotes_page = if htmx.is_partial() && pagination.page > 1 {
+        fragment(
+            HtmxQuoteCardsTemplate {
+                quotes,
+                pagination,
+                locale,
+            }
+            .render()?,
+        )
+    } else {
+        htmx.render(&QuotesTemplate {
+            quotes,
+            pagination,
+            locale,
+        })?
+    };
+
+    info!("queried quotes page {}", pagination.page);
+    Ok(quotes_page)
+}
+
+fn render_form(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: QuoteFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, askama::Error> {
+    htmx.render(&QuoteFormTemplate {
+        heading: target.heading,
+        form,
+        errors,
+        action: target.action,
+        method: target.method,
+        cancel: target.cancel,
+        htmx: target.htmx,
+        locale,
+    })
+}
+
+/// Shows the form again with the errors next to its inputs.
+fn form_errors(
+    htmx: &HtmxRequest,
+    locale: Locale,
+    target: FormTarget,
+    form: QuoteFormViewModel,
+    errors: FormErrors,
+) -> Result<Response, WebError> {
+    let status = target.error_status();
+    let page = render_form(htmx, locale, target, form, errors)?;
+
+    Ok((status, page).into_response())
+}
+
+fn create_target(htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    FormTarget::create("quotes", locale.t("quote-form-add"), htmx.is_partial())
+}
+
+fn edit_target(quote_id: i32, htmx: &HtmxRequest, locale: Locale) -> FormTarget {
+    let heading = locale.t("quote-form-edit");
+    FormTarget::edit("quotes", quote_id, heading, "", htmx.is_partial())
+}
 
-#[instrument]
-pub async fn list_quotes(Extension(store): Extension<Store>) -> Html<String> {
+#[instrument(skip_all)]
+pub async fn new_quote_form(htmx: HtmxRequest, locale: Locale) -> Result<Response, WebError> {
+    let form = QuoteFormViewModel::default();
+
+    let target = create_target(&htmx, locale);
+    let page = render_form(&htmx, locale, target, form, FormErrors::default())?;
+
+    Ok(page)
+}
+
+#[instrument(skip_all, fields(quote_id))]
+pub async fn create_quote(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    form: FormData,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
+    let quote_form = QuoteFormViewModel::from_form(&form);
 
-    let quotes = service.get_quotes().await;
+    match service.create_quote(&quote_form).await {
+        Ok(quote_id) => {
+            Span::current().record("quote_id", quote_id);
+            info!("created quote {}", quote_id);
+            Ok(htmx.redirect("/quotes"))
+        }
+        Err(WebError::InvalidForm(errors)) => form_errors(
+            &htmx,
+            locale,
+            create_target(&htmx, locale),
+            quote_form,
+            errors,
+        ),
+        Err(e) => Err(e),
+    }
+}
 
-    let quotes_template = QuotesTemplate { quotes }.render().unwrap();
+#[instrument(skip_all, fields(quote_id = quote_id))]
+pub async fn edit_quote_form(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(quote_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let quote = service
+        .get_quote(quote_id)
+        .await?
+        .ok_or(WebError::not_found("Quote", quote_id))?;
 
-    info!("queried all quotes");
-    Html(quotes_template)
+    let form = QuoteFormViewModel::from_quote(&quote);
+    let page = render_form(
+        &htmx,
+        locale,
+        edit_target(quote_id, &htmx, locale),
+        form,
+        FormErrors::default(),
+    )?;
+
+    Ok(page)
 }
 
-#[instrument]
-pub async fn htmx_list_quotes(Extension(store): Extension<Store>) -> Html<String> {
+/// Saves the edit form, sent as a `POST` without htmx and a `PUT` with it.
+#[instrument(skip_all, fields(quote_id = quote_id))]
+pub async fn update_quote(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(quote_id): Path<i32>,
+    form: FormData,
+) -> Result<Response, WebError> {
     let service = WebService::new(&store.connection);
+    let quote_form = QuoteFormViewModel::from_form(&form);
 
-    let quotes = service.get_quotes().await;
+    match service.update_quote(quote_id, &quote_form).await {
+        Ok(()) => {
+            info!("updated quote {}", quote_id);
+            Ok(htmx.redirect("/quotes"))
+        }
+        Err(WebError::InvalidForm(errors)) => {
+            let target = edit_target(quote_id, &htmx, locale);
+            form_errors(&htmx, locale, target, quote_form, errors)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[instrument(skip_all, fields(quote_id = quote_id))]
+pub async fn confirm_delete_quote(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    locale: Locale,
+    Path(quote_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
+
+    let quote = service
+        .get_quote(quote_id)
+        .await?
+        .ok_or(WebError::not_found("Quote", quote_id))?;
+
+    let action = if htmx.is_partial() {
+        format!("/quotes/{}", quote_id)
+    } else {
+        format!("/quotes/{}/delete", quote_id)
+    };
+    let confirm_page = htmx.render(&ConfirmDeleteTemplate {
+        kind: "quote",
+        label: quote.text,
+        action,
+        cancel: "/quotes".to_string(),
+        htmx: htmx.is_partial(),
+        locale,
+    })?;
+
+    Ok(confirm_page)
+}
+
+/// Deletes a quote, sent as a `POST` without htmx and a `DELETE` with it.
+#[instrument(skip_all, fields(quote_id = quote_id))]
+pub async fn delete_quote(
+    Extension(store): Extension<Store>,
+    htmx: HtmxRequest,
+    Path(quote_id): Path<i32>,
+) -> Result<Response, WebError> {
+    let service = WebService::new(&store.connection);
 
-    let htmx_quotes_template = HtmxQuotesTemplate { quotes }.render().unwrap();
+    service.delete_quote(quote_id).await?;
 
-    info!("queried all quotes");
-    Html(htmx_quotes_template)
+    info!("deleted quote {}", quote_id);
+    Ok(htmx.redirect("/quotes"))
 }
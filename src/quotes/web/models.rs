@@ -1,7 +1,41 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use crate::forms::{FormData, FormErrors};
+use crate::quotes::api::models::CreateQuoteRequest;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct QuoteViewModel {
+    pub id: i32,
     pub text: String,
 }
+
+/// The quote form as typed, so it can be shown again with its errors.
+#[derive(Debug, Clone, Default)]
+pub struct QuoteFormViewModel {
+    pub text: String,
+}
+
+impl QuoteFormViewModel {
+    pub fn from_quote(quote: &QuoteViewModel) -> Self {
+        Self {
+            text: quote.text.clone(),
+        }
+    }
+
+    pub fn from_form(form: &FormData) -> Self {
+        Self {
+            text: form.value("text"),
+        }
+    }
+
+    pub fn to_request(&self) -> Result<CreateQuoteRequest, FormErrors> {
+        let request = CreateQuoteRequest {
+            text: self.text.clone(),
+        };
+        let mut errors = FormErrors::default();
+        if let Err(validation) = request.validate() {
+            errors.merge(&validation);
+        }
+        errors.into_result().map(|_| request)
+    }
+}
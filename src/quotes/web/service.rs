@@ -1,9 +1,14 @@
+use crate::forms::FormErrors;
+use crate::pagination::Pagination;
+use crate::quotes::api::service::ApiService;
 use crate::quotes::data::repository::QuoteRepository;
-// use crate::movies::web::errors::MoviesWebError;
-use crate::quotes::web::models::QuoteViewModel;
+use crate::web_error::WebError;
+use crate::quotes::web::models::{QuoteFormViewModel, QuoteViewModel};
 
 use sqlx::PgPool;
 
+pub const QUOTES_PER_PAGE: i64 = 10;
+
 pub struct WebService {
     repo: QuoteRepository,
 }
@@ -15,18 +20,74 @@ impl WebService {
         }
     }
 
-    pub async fn get_quotes(&self) -> Vec<QuoteViewModel> {
+    pub async fn get_quotes_page(
+        &self,
+        page: i64,
+    ) -> Result<(Vec<QuoteViewModel>, Pagination), WebError> {
+        let pagination = Pagination::new(page, QUOTES_PER_PAGE);
         let quotes = self
             .repo
-            .get_quotes()
-            .await
-            .unwrap()
-            .iter()
+            .get_quotes_page(Some(pagination.limit()), pagination.offset())
+            .await?
+            .into_iter()
             .map(|q| QuoteViewModel {
-                text: q.text.clone(),
+                id: q.id,
+                text: q.text,
             })
             .collect();
+        let total = self.repo.count_quotes().await?;
+
+        Ok((quotes, pagination.with_total(total)))
+    }
+
+    pub async fn get_quote(&self, quote_id: i32) -> Result<Option<QuoteViewModel>, WebError> {
+        let quote = self
+            .repo
+            .get_quote(quote_id)
+            .await?
+            .map(|q| QuoteViewModel {
+                id: q.id,
+                text: q.text,
+            });
+
+        Ok(quote)
+    }
+
+    /// Saves a new quote from the form, returning its id. The API quietly
+    /// keeps the existing quote when the text is already there; the form
+    /// says so instead.
+    pub async fn create_quote(&self, form: &QuoteFormViewModel) -> Result<i32, WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        if self.repo.get_quote_by_text(&request.text).await?.is_some() {
+            let mut errors = FormErrors::default();
+            errors.add("text", "This quote is already in the collection");
+            return Err(WebError::InvalidForm(errors));
+        }
+        let quote_id = ApiService::new(&self.repo.pool)
+            .create_quote(request)
+            .await?;
+
+        Ok(quote_id)
+    }
+
+    pub async fn update_quote(
+        &self,
+        quote_id: i32,
+        form: &QuoteFormViewModel,
+    ) -> Result<(), WebError> {
+        let request = form.to_request().map_err(WebError::InvalidForm)?;
+        ApiService::new(&self.repo.pool)
+            .update_quote(quote_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_quote(&self, quote_id: i32) -> Result<(), WebError> {
+        ApiService::new(&self.repo.pool)
+            .delete_quote(quote_id)
+            .await?;
 
-        quotes
+        Ok(())
     }
 }
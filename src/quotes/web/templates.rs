@@ -1,14 +1,47 @@
-use crate::quotes::web::models::QuoteViewModel;
+use crate::forms::FormErrors;
+use crate::htmx::impl_page;
+use crate::i18n::Locale;
+use crate::pagination::Pagination;
+use crate::quotes::web::models::{QuoteFormViewModel, QuoteViewModel};
 use askama::Template;
 
 #[derive(Template)]
-#[template(path = "pages/quotes.html")]
+#[template(path = "pages/quotes.html", blocks = ["title", "main"])]
 pub struct QuotesTemplate {
+    pub locale: Locale,
     pub quotes: Vec<QuoteViewModel>,
+    pub pagination: Pagination,
 }
 
 #[derive(Template)]
-#[template(path = "pages/htmx/quotes.html")]
-pub struct HtmxQuotesTemplate {
+#[template(path = "partials/htmx/quote_cards.html")]
+pub struct HtmxQuoteCardsTemplate {
+    pub locale: Locale,
     pub quotes: Vec<QuoteViewModel>,
+    pub pagination: Pagination,
 }
+
+/// A quote card sent to the open pages when the quote is saved elsewhere.
+#[derive(Template)]
+#[template(path = "partials/live/quote.html")]
+pub struct LiveQuoteTemplate {
+    pub locale: Locale,
+    pub quote: QuoteViewModel,
+    /// Added at the top of the list rather than replacing its card
+    pub created: bool,
+}
+
+#[derive(Template)]
+#[template(path = "pages/quote_form.html", blocks = ["title", "main"])]
+pub struct QuoteFormTemplate {
+    pub locale: Locale,
+    pub heading: String,
+    pub form: QuoteFormViewModel,
+    pub errors: FormErrors,
+    pub action: String,
+    pub method: &'static str,
+    pub cancel: String,
+    pub htmx: bool,
+}
+
+impl_page!(QuotesTemplate, QuoteFormTemplate);
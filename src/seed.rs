@@ -0,0 +1,113 @@
+//! Bundled Tarantino filmography used to populate new databases.
+//!
+//! The dataset is an export archive (see `export`), loaded through the archive
+//! import so running it twice only skips what is already there.
+
+use crate::export::api::models::ArchiveFormat;
+use crate::import::api::errors::ImportApiError;
+use crate::import::api::models::ArchiveImportReport;
+use crate::import::api::service::ApiService as ImportApiService;
+use crate::store::traced_query;
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// The bundled seed archive.
+pub const SEED_ARCHIVE: &str = include_str!("../seed/tarantino.json");
+
+/// Loads the bundled dataset, optionally wiping the catalogue first.
+///
+/// The wipe and the import share one transaction, so a seed that fails
+/// leaves the existing catalogue untouched.
+pub async fn seed(pool: &PgPool, wipe_first: bool) -> Result<ArchiveImportReport, ImportApiError> {
+    let mut tx = pool.begin().await?;
+
+    if wipe_first {
+        wipe(&mut tx).await?;
+    }
+
+    let report = ImportApiService::new(pool)
+        .import_archive_in(&mut tx, ArchiveFormat::Json, SEED_ARCHIVE)
+        .await?;
+
+    if report.committed {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+    }
+
+    Ok(report)
+}
+
+/// Deletes every movie, person, award, quote and interview and restarts ids
+/// at 1. Roles are reference data from the migrations and are kept.
+pub async fn wipe(tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::Error> {
+    traced_query(
+        "wipe_catalogue",
+        sqlx::query(
+            r#"
+            TRUNCATE TABLE
+                movie_still, movie_nomination, movie_award, award_category, award,
+                movie_genre, genre, movie_role, person, movie,
+                quote, interview
+            RESTART IDENTITY CASCADE
+            "#,
+        )
+        .execute(&mut **tx),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::api::parsers::parse_archive;
+    use crate::test_support::{create_database, drop_database};
+    use validator::Validate;
+
+    #[test]
+    fn test_seed_archive_is_valid() {
+        let archive = parse_archive(SEED_ARCHIVE, ArchiveFormat::Json).unwrap();
+
+        assert!(!archive.movies.is_empty());
+        for movie in &archive.movies {
+            let movie = movie.as_ref().unwrap();
+            assert!(
+                movie.validate().is_ok(),
+                "invalid seed movie {}",
+                movie.title
+            );
+        }
+        for quote in &archive.quotes {
+            assert!(quote.as_ref().unwrap().validate().is_ok());
+        }
+        for interview in &archive.interviews {
+            assert!(interview.as_ref().unwrap().validate().is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seed_wipe_replaces_the_catalogue() {
+        let name = format!("tarantinors_seed_{}", uuid::Uuid::new_v4().simple());
+        let pool = create_database(&name).await;
+
+        assert!(seed(&pool, false).await.unwrap().committed);
+        sqlx::query("INSERT INTO quote (text) VALUES ('Not in the seed')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = seed(&pool, true).await.unwrap();
+
+        assert!(report.committed);
+        assert_eq!(report.movies.created, 10);
+        let quotes: Vec<String> = sqlx::query_scalar("SELECT text FROM quote")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert!(!quotes.contains(&"Not in the seed".to_string()));
+        assert_eq!(quotes.len(), 6);
+
+        drop_database(pool, &name).await;
+    }
+}
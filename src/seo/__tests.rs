@@ -0,0 +1,176 @@
+use super::*;
+use crate::i18n::Locale;
+use crate::movies::web::models::{MovieMetaViewModel, MovieViewModel, excerpt};
+use crate::test_support::{create_database, drop_database, send};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+
+/// A request to `https://films.example`, as forwarded by a proxy.
+fn request(uri: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .header(header::HOST, "films.example")
+        .header("X-Forwarded-Proto", "https")
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn movie() -> MovieViewModel {
+    MovieViewModel {
+        id: 3,
+        title: "Jackie Brown".to_string(),
+        release_year: 1997,
+        genre: "Crime".to_string(),
+        plot: "A flight attendant caught smuggling money for an arms dealer plays the \
+               police, the dealer and his men against each other, with the help of a \
+               bail bondsman, to walk away with half a million dollars </script>."
+            .to_string(),
+        runtime: 154,
+        rating: 7.6,
+        release_date: chrono::NaiveDate::from_ymd_opt(1997, 12, 25).unwrap(),
+        image_url: "/media/posters/jackie-brown.jpg".to_string(),
+        youtube_id: String::new(),
+        production_details: String::new(),
+        budget: String::new(),
+        directors: vec!["Quentin Tarantino".to_string()],
+        producers: vec![],
+        actors: vec!["Pam Grier".to_string(), "Robert Forster".to_string()],
+        writers: vec![],
+        awards: vec![crate::movies::web::models::MovieAwardViewModel {
+            name: "Silver Bear".to_string(),
+            category: "Best Actor".to_string(),
+            year: 1998,
+            recipient: Some("Samuel L. Jackson".to_string()),
+        }],
+        nominations: vec![],
+    }
+}
+
+#[test]
+fn configured_site_url_wins_over_the_request_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::HOST, "evil.example".parse().unwrap());
+    headers.insert("X-Forwarded-Proto", "gopher".parse().unwrap());
+
+    assert_eq!(
+        resolve_site_url(Some("https://films.example/"), &headers),
+        "https://films.example"
+    );
+    // Development falls back to where the request went
+    assert_eq!(resolve_site_url(None, &headers), "gopher://evil.example");
+    assert_eq!(
+        resolve_site_url(Some(" "), &headers),
+        "gopher://evil.example"
+    );
+    assert_eq!(
+        resolve_site_url(None, &HeaderMap::new()),
+        "http://localhost:3000"
+    );
+}
+
+#[test]
+fn excerpts_are_cut_at_a_word() {
+    assert_eq!(excerpt("  Short enough. ", 20), "Short enough.");
+    assert_eq!(excerpt("Say what again, I dare you", 16), "Say what again…");
+}
+
+#[test]
+fn movie_meta_describes_the_movie_for_previews_and_search_engines() {
+    let meta = MovieMetaViewModel::new(&movie(), "https://films.example", &Locale::Fr);
+
+    assert_eq!(meta.url, "https://films.example/fr/movies/3");
+    assert_eq!(
+        meta.image,
+        "https://films.example/media/posters/jackie-brown.jpg"
+    );
+    assert!(meta.description.chars().count() <= 160);
+    assert!(meta.description.ends_with('…'));
+    assert_eq!(
+        meta.alternates[0],
+        ("x-default", "https://films.example/movies/3".to_string())
+    );
+    assert_eq!(meta.alternates.len(), 1 + Locale::ALL.len());
+
+    // Nothing in the plot may close the script tag the JSON-LD sits in
+    assert!(!meta.json_ld.contains("</"));
+    let json_ld: serde_json::Value = serde_json::from_str(&meta.json_ld).unwrap();
+    assert_eq!(json_ld["@type"], "Movie");
+    assert_eq!(json_ld["duration"], "PT154M");
+    assert_eq!(json_ld["director"][0]["name"], "Quentin Tarantino");
+    assert_eq!(json_ld["actor"][1]["name"], "Robert Forster");
+    assert_eq!(json_ld["aggregateRating"]["ratingValue"], 7.6);
+    assert_eq!(
+        json_ld["award"][0],
+        "Silver Bear Best Actor, Samuel L. Jackson (1998)"
+    );
+}
+
+#[tokio::test]
+async fn sitemap_lists_every_page_with_its_translations() {
+    let name = "tarantinors_seo_sitemap";
+    let pool = create_database(name).await;
+    sqlx::query(
+        "INSERT INTO movie (title, release_year, plot, runtime, rating, release_date,
+                            image_url, youtube_id, production_details, budget, updated_at)
+         VALUES ('Death Proof', 2007, 'A stuntman and his car.', 113, 7.0,
+                 '2007-04-06', 'death-proof.jpg', '', '', '', '2026-03-01 09:30:00')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    for page in 0..=QUOTES_PER_PAGE {
+        sqlx::query("INSERT INTO quote (text) VALUES ($1)")
+            .bind(format!("Quote {}", page))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let movie_id: i32 = sqlx::query_scalar("SELECT id FROM movie")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let router = web_router(&Store {
+        connection: pool.clone(),
+    });
+
+    let response = send(&router, request("/sitemap.xml")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+        response.header(header::CONTENT_TYPE),
+        "application/xml; charset=utf-8"
+    );
+    let body = response.body;
+    let movie_url = format!("https://films.example/movies/{}", movie_id);
+    for url in [
+        "https://films.example/",
+        "https://films.example/movies",
+        movie_url.as_str(),
+        "https://films.example/quotes",
+        "https://films.example/quotes?page=2",
+        "https://films.example/interviews",
+    ] {
+        assert!(
+            body.contains(&format!("<loc>{}</loc>", url)),
+            "{} missing",
+            url
+        );
+    }
+    assert!(!body.contains("/quotes?page=3"));
+    assert!(body.contains(&format!(
+        r#"hreflang="es" href="https://films.example/es/movies/{}""#,
+        movie_id
+    )));
+    assert!(body.contains(r#"hreflang="fr" href="https://films.example/fr""#));
+    assert!(body.contains("<lastmod>2026-03-01T09:30:00Z</lastmod>"));
+
+    let response = send(&router, request("/robots.txt")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.contains("Disallow: /api/"));
+    assert!(
+        response
+            .body
+            .contains("Sitemap: https://films.example/sitemap.xml")
+    );
+
+    drop_database(pool, name).await;
+}
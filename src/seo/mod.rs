@@ -0,0 +1,192 @@
+//! What search engines and link previews read: the `/sitemap.xml` of every
+//! page, with its translations, and `/robots.txt`. The tags of each page are
+//! in its template.
+
+#[cfg(test)]
+mod __tests;
+
+use crate::interviews::{data::repository::InterviewRepository, web::service::INTERVIEWS_PER_PAGE};
+use crate::movies::data::repository::MovieRepository;
+use crate::quotes::{data::repository::QuoteRepository, web::service::QUOTES_PER_PAGE};
+use crate::store::Store;
+use crate::web_error::WebError;
+use askama::Template;
+use axum::{
+    Extension, Router,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{NaiveDateTime, SecondsFormat};
+use sqlx::PgPool;
+use std::sync::OnceLock;
+
+/// The address the site is reached at. Feeds, sitemaps and link previews
+/// need absolute links.
+///
+/// Read once from `SITE_URL`, such as `https://tarantino.example`. Without it,
+/// as in development, the request's `Host` header and, behind a proxy,
+/// `X-Forwarded-Proto` are used instead; clients control both, so a public
+/// site sets `SITE_URL` to keep forged links out of shared caches.
+pub fn site_url(headers: &HeaderMap) -> String {
+    static CONFIGURED: OnceLock<Option<String>> = OnceLock::new();
+
+    let configured = CONFIGURED.get_or_init(|| std::env::var("SITE_URL").ok());
+    resolve_site_url(configured.as_deref(), headers)
+}
+
+/// `configured` without a trailing `/`, or the address the request was sent to.
+fn resolve_site_url(configured: Option<&str>, headers: &HeaderMap) -> String {
+    let configured = configured
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty());
+    if let Some(url) = configured {
+        return url.to_string();
+    }
+
+    let value = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let host = value(header::HOST.as_str()).unwrap_or("localhost:3000");
+    let scheme = value("X-Forwarded-Proto").unwrap_or("http");
+
+    format!("{}://{}", scheme, host)
+}
+
+/// `url` made absolute when it is a path on the site.
+pub fn absolute_url(site: &str, url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{}", site, url)
+    } else {
+        url.to_string()
+    }
+}
+
+/// A page of the sitemap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapUrl {
+    /// Path without a locale prefix, such as `/movies/3` or `/quotes?page=2`
+    pub path: String,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+impl SitemapUrl {
+    fn new(path: impl Into<String>, last_modified: Option<NaiveDateTime>) -> Self {
+        Self {
+            path: path.into(),
+            last_modified,
+        }
+    }
+
+    /// `lastmod` in the W3C date format the protocol asks for.
+    pub fn lastmod(&self) -> Option<String> {
+        self.last_modified
+            .map(|date| date.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+}
+
+/// The pages of a list split in pages of `per_page` records.
+fn list_pages(
+    path: &str,
+    total: i64,
+    per_page: i64,
+    last_modified: Option<NaiveDateTime>,
+) -> Vec<SitemapUrl> {
+    let pages = ((total + per_page - 1) / per_page).max(1);
+    (1..=pages)
+        .map(|page| match page {
+            1 => SitemapUrl::new(path, last_modified),
+            page => SitemapUrl::new(format!("{}?page={}", path, page), last_modified),
+        })
+        .collect()
+}
+
+/// Every public page: the home page, each movie and the pages of the movie,
+/// quote and interview lists.
+pub async fn sitemap_urls(pool: &PgPool) -> Result<Vec<SitemapUrl>, WebError> {
+    let movies = MovieRepository::new(pool).get_movies_updates().await?;
+    let quotes = QuoteRepository::new(pool);
+    let interviews = InterviewRepository::new(pool);
+    let movies_update = movies.iter().map(|(_, updated_at)| *updated_at).max();
+    let quotes_update = quotes.get_last_update().await?;
+    let interviews_update = interviews.get_last_update().await?;
+    let site_update = [movies_update, quotes_update, interviews_update]
+        .into_iter()
+        .flatten()
+        .max();
+
+    let mut urls = vec![
+        SitemapUrl::new("/", site_update),
+        SitemapUrl::new("/movies", movies_update),
+    ];
+    urls.extend(
+        movies
+            .into_iter()
+            .map(|(id, updated_at)| SitemapUrl::new(format!("/movies/{}", id), Some(updated_at))),
+    );
+    urls.extend(list_pages(
+        "/quotes",
+        quotes.count_quotes().await?,
+        QUOTES_PER_PAGE,
+        quotes_update,
+    ));
+    urls.extend(list_pages(
+        "/interviews",
+        interviews.count_interviews().await?,
+        INTERVIEWS_PER_PAGE,
+        interviews_update,
+    ));
+
+    Ok(urls)
+}
+
+#[derive(Template)]
+#[template(path = "seo/sitemap.xml")]
+pub struct SitemapTemplate {
+    pub site: String,
+    pub urls: Vec<SitemapUrl>,
+}
+
+#[derive(Template)]
+#[template(path = "seo/robots.txt")]
+pub struct RobotsTemplate {
+    pub site: String,
+}
+
+/// `GET /sitemap.xml`: every page, each with its translations.
+pub async fn sitemap(
+    Extension(store): Extension<Store>,
+    headers: HeaderMap,
+) -> Result<Response, WebError> {
+    let sitemap = SitemapTemplate {
+        site: site_url(&headers),
+        urls: sitemap_urls(&store.connection).await?,
+    }
+    .render()?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        sitemap,
+    )
+        .into_response())
+}
+
+/// `GET /robots.txt`: crawl the pages, not the forms, the API or the
+/// streams, and find them in the sitemap.
+pub async fn robots(headers: HeaderMap) -> Result<Response, WebError> {
+    let robots = RobotsTemplate {
+        site: site_url(&headers),
+    }
+    .render()?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        robots,
+    )
+        .into_response())
+}
+
+pub fn web_router(store: &Store) -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(sitemap))
+        .route("/robots.txt", get(robots))
+        .layer(Extension(store.clone()))
+}
@@ -1,4 +1,10 @@
 use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::future::IntoFuture;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, field, warn};
+
+const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 250;
 
 async fn init_dbpool() -> Result<sqlx::Pool<sqlx::Postgres>, sqlx::Error> {
     let db_connection_str = std::env::var("DATABASE_URL")
@@ -31,3 +37,43 @@ impl Store {
         }
     }
 }
+
+/// Queries running longer than this are logged as a warning.
+///
+/// Read once from `SLOW_QUERY_THRESHOLD_MS`, defaults to 250ms.
+pub fn slow_query_threshold() -> Duration {
+    static THRESHOLD: OnceLock<Duration> = OnceLock::new();
+
+    *THRESHOLD.get_or_init(|| {
+        let millis = std::env::var("SLOW_QUERY_THRESHOLD_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+        Duration::from_millis(millis)
+    })
+}
+
+/// Runs a database query inside a `db.query` span tagged with `statement`,
+/// recording how long it took and warning when it exceeds the slow query threshold.
+pub async fn traced_query<Q>(statement: &'static str, query: Q) -> Q::Output
+where
+    Q: IntoFuture,
+{
+    let span = tracing::info_span!("db.query", statement, elapsed_ms = field::Empty);
+
+    let started = Instant::now();
+    let result = query.into_future().instrument(span.clone()).await;
+    let elapsed = started.elapsed();
+
+    span.record("elapsed_ms", elapsed.as_millis() as u64);
+    if elapsed >= slow_query_threshold() {
+        warn!(
+            parent: &span,
+            statement,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "slow query"
+        );
+    }
+
+    result
+}
@@ -0,0 +1,127 @@
+//! Optional OpenTelemetry trace export.
+//!
+//! Built with the `otel` cargo feature and switched on at runtime by setting
+//! `OTEL_EXPORTER_OTLP_ENDPOINT` (for example `http://localhost:4318`).
+//! Without either, every function here is a no-op.
+
+use axum::http::HeaderMap;
+use tracing::Span;
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::registry::LookupSpan;
+
+#[cfg(feature = "otel")]
+mod otel {
+    use std::sync::OnceLock;
+
+    use axum::http::HeaderMap;
+    use opentelemetry::global;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing::{Span, Subscriber};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::registry::LookupSpan;
+
+    static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+    pub fn layer<S>(service_name: &'static str) -> Option<impl Layer<S>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+        let service_name =
+            std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| service_name.to_string());
+
+        let exporter = match SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+            .build()
+        {
+            Ok(exporter) => exporter,
+            Err(e) => {
+                eprintln!("Failed to build OTLP exporter: {}", e);
+                return None;
+            }
+        };
+
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(service_name.clone()).build())
+            .build();
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+        let tracer = provider.tracer(service_name);
+        let _ = TRACER_PROVIDER.set(provider);
+
+        Some(tracing_opentelemetry::layer().with_tracer(tracer))
+    }
+
+    pub fn set_parent_from_headers(span: &Span, headers: &HeaderMap) {
+        let parent_context =
+            global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+        let _ = span.set_parent(parent_context);
+    }
+
+    pub fn inject_context(span: &Span, headers: &mut HeaderMap) {
+        let context = span.context();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut HeaderInjector(headers))
+        });
+    }
+
+    pub fn shutdown() {
+        if let Some(provider) = TRACER_PROVIDER.get()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to flush OTLP spans: {}", e);
+        }
+    }
+}
+
+/// Layer exporting spans over OTLP/HTTP, `None` when export is not configured.
+#[cfg(feature = "otel")]
+pub fn otel_layer<S>(service_name: &'static str) -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    otel::layer(service_name)
+}
+
+/// Layer exporting spans over OTLP/HTTP, `None` when export is not configured.
+#[cfg(not(feature = "otel"))]
+pub fn otel_layer<S>(_service_name: &'static str) -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    None::<tracing_subscriber::layer::Identity>
+}
+
+/// Continues the trace of an incoming W3C `traceparent` header, if any.
+pub fn set_parent_from_headers(span: &Span, headers: &HeaderMap) {
+    #[cfg(feature = "otel")]
+    otel::set_parent_from_headers(span, headers);
+
+    #[cfg(not(feature = "otel"))]
+    let _ = (span, headers);
+}
+
+/// Writes the `traceparent` of `span` into outgoing request headers.
+pub fn inject_context(span: &Span, headers: &mut HeaderMap) {
+    #[cfg(feature = "otel")]
+    otel::inject_context(span, headers);
+
+    #[cfg(not(feature = "otel"))]
+    let _ = (span, headers);
+}
+
+/// Flushes spans still waiting in the batch exporter.
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    otel::shutdown();
+}
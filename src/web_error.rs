@@ -0,0 +1,302 @@
+//! Errors of the web pages, shared by movies, quotes and interviews. A browser
+//! gets the themed error page; an htmx swap gets a small alert, retargeted
+//! above `<main>` so a failed request never drops a whole document into it.
+
+use crate::forms::FormErrors;
+use crate::htmx::{HtmxRequest, impl_page};
+use crate::i18n::{Locale, Message};
+use askama::Template;
+use axum::{
+    body::to_bytes,
+    extract::Request,
+    http::{StatusCode, Uri, header},
+    middleware::Next,
+    response::{Html, IntoResponse, Response},
+};
+use thiserror::Error;
+
+/// Where htmx puts the alert of a failed request.
+pub const HTMX_ERRORS_TARGET: &str = "#htmx-errors";
+
+/// Longest rejection text shown as the error message.
+const MAX_MESSAGE_BYTES: usize = 4096;
+
+#[derive(Debug, Error)]
+pub enum WebError {
+    #[error("Template rendering failed: {0}")]
+    RenderError(#[from] askama::Error),
+
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+
+    /// `kind` is the capitalized name of the record, such as "Movie"
+    #[error("{kind} not found: {id}")]
+    NotFound { kind: &'static str, id: i32 },
+
+    /// No web route matches the path
+    #[error("Page not found: {0}")]
+    PageNotFound(String),
+
+    #[error("Invalid input: {0}")]
+    ValidationError(String),
+
+    /// The form has errors to show next to its inputs
+    #[error("Invalid form: {} error(s)", .0.len())]
+    InvalidForm(FormErrors),
+
+    #[error("Internal server error: {0}")]
+    InternalError(String),
+}
+
+impl WebError {
+    pub fn not_found(kind: &'static str, id: i32) -> Self {
+        Self::NotFound { kind, id }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound { .. } => StatusCode::NOT_FOUND,
+            Self::PageNotFound(_) => StatusCode::NOT_FOUND,
+            Self::ValidationError(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidForm(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::RenderError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn user_message(&self) -> Message {
+        match self {
+            Self::NotFound { kind, id } => Message::Id(
+                "error-not-found",
+                vec![("kind", kind.to_lowercase()), ("id", id.to_string())],
+            ),
+            Self::PageNotFound(path) => Message::Id("error-no-page", vec![("path", path.clone())]),
+            Self::ValidationError(msg) => Message::Text(msg.clone()),
+            Self::InvalidForm(_) => Message::id("error-invalid-form"),
+            Self::DatabaseError(_) => Message::id("error-database"),
+            Self::RenderError(_) => Message::id("error-render"),
+            Self::InternalError(_) => Message::id("error-internal"),
+        }
+    }
+
+    pub fn log_message(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// Renders the full error page in English. The view model also rides along
+/// in the response extensions, for [`htmx_errors`] to render in the locale
+/// of the request, or as an alert.
+impl IntoResponse for WebError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let log_msg = self.log_message();
+
+        if status.is_server_error() {
+            tracing::error!(error = %log_msg, status = %status, "Web error occurred");
+        } else {
+            tracing::warn!(error = %log_msg, status = %status, "Web error occurred");
+        }
+
+        let details = cfg!(debug_assertions)
+            .then_some(log_msg)
+            .filter(|_| status.is_server_error());
+        let error = ErrorViewModel::new(status, self.user_message(), details);
+
+        let mut response = error.render(&HtmxRequest::default(), Locale::default());
+        response.extensions_mut().insert(error);
+        response
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ErrorViewModel {
+    pub code: u16,
+    pub message: Message,
+    pub details: Option<String>,
+    pub show_suggestions: bool,
+    pub title: Message,
+}
+
+impl ErrorViewModel {
+    pub fn new(status: StatusCode, message: Message, details: Option<String>) -> Self {
+        let title = match status {
+            StatusCode::NOT_FOUND => "error-title-not-found",
+            status if status.is_client_error() => "error-title-client",
+            _ => "error-title-server",
+        };
+
+        Self {
+            code: status.as_u16(),
+            message,
+            details,
+            show_suggestions: status == StatusCode::NOT_FOUND,
+            title: Message::id(title),
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.code == StatusCode::NOT_FOUND.as_u16()
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// The error page, or for an htmx swap the alert and the headers sending
+    /// it to [`HTMX_ERRORS_TARGET`].
+    pub fn render(&self, htmx: &HtmxRequest, locale: Locale) -> Response {
+        let status = self.status();
+        let rendered = if htmx.is_partial() {
+            ErrorAlertTemplate {
+                error: self.clone(),
+                locale,
+            }
+            .render()
+            .map(|html| {
+                let headers = [
+                    ("HX-Retarget", HTMX_ERRORS_TARGET),
+                    ("HX-Reswap", "innerHTML"),
+                ];
+                (status, headers, Html(html)).into_response()
+            })
+        } else {
+            htmx.render(&ErrorTemplate {
+                error: self.clone(),
+                locale,
+            })
+            .map(|page| (status, page).into_response())
+        };
+
+        rendered.unwrap_or_else(|e| {
+            // Fallback if the error templates fail
+            tracing::error!("Failed to render error template: {}", e);
+            let message = locale.message(&self.message);
+            (status, Html(format!("<p>Error {}: {}</p>", self.code, message))).into_response()
+        })
+    }
+}
+
+#[derive(Template)]
+#[template(path = "pages/error.html", blocks = ["title", "main"])]
+pub struct ErrorTemplate {
+    pub error: ErrorViewModel,
+    pub locale: Locale,
+}
+
+impl_page!(ErrorTemplate);
+
+/// The inline alert shown when an htmx request fails.
+#[derive(Template)]
+#[template(path = "partials/error_alert.html")]
+pub struct ErrorAlertTemplate {
+    pub error: ErrorViewModel,
+    pub locale: Locale,
+}
+
+/// Middleware for the web routes. Errors are shown in the locale of the
+/// request, as alerts for htmx requests, and bare error responses, such as
+/// extractor rejections and `405`s, get the error page like any [`WebError`].
+pub async fn htmx_errors(
+    htmx: HtmxRequest,
+    locale: Locale,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let mut allow = None;
+    let error = match response.extensions().get::<ErrorViewModel>() {
+        Some(error) => error.clone(),
+        // Pages of their own, such as a form shown again with its errors
+        None if content_type.starts_with("text/html") => return response,
+        None => {
+            allow = response.headers().get(header::ALLOW).cloned();
+            let body = to_bytes(response.into_body(), MAX_MESSAGE_BYTES)
+                .await
+                .unwrap_or_default();
+            let message = Some(String::from_utf8_lossy(&body).trim().to_string())
+                .filter(|text| content_type.starts_with("text/plain") && !text.is_empty())
+                .unwrap_or_else(|| status.canonical_reason().unwrap_or("Error").to_string());
+            ErrorViewModel::new(status, Message::Text(message), None)
+        }
+    };
+
+    let mut response = error.render(&htmx, locale);
+    if let Some(allow) = allow {
+        response.headers_mut().insert(header::ALLOW, allow);
+    }
+    response
+}
+
+/// The themed 404 page for paths no web route matches.
+pub async fn not_found(uri: Uri) -> WebError {
+    WebError::PageNotFound(uri.path().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, extract::Path, middleware, routing::get};
+    use tower::ServiceExt;
+
+    async fn send(htmx: bool, uri: &str) -> (StatusCode, Option<String>, String) {
+        let app = Router::new()
+            .route(
+                "/movies/{id}",
+                get(|Path(id): Path<i32>| async move { WebError::not_found("Movie", id) }),
+            )
+            .fallback(not_found)
+            .layer(middleware::from_fn(htmx_errors));
+        let mut request = Request::builder().uri(uri);
+        if htmx {
+            request = request.header("HX-Request", "true");
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let retarget = response
+            .headers()
+            .get("HX-Retarget")
+            .map(|value| value.to_str().unwrap().to_string());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, retarget, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn htmx_requests_get_an_alert_instead_of_the_page() {
+        let (status, retarget, body) = send(false, "/movies/7").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(retarget, None);
+        assert!(body.starts_with("<!DOCTYPE html>"));
+        assert!(body.contains("Movie with ID 7 not found"));
+
+        let (status, retarget, body) = send(true, "/movies/7").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(retarget.as_deref(), Some(HTMX_ERRORS_TARGET));
+        assert!(body.contains(r#"role="alert""#));
+        assert!(!body.contains("<html"));
+
+        let (status, _, body) = send(false, "/nowhere").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body.contains("There is no page at /nowhere"));
+
+        let (status, retarget, body) = send(true, "/movies/abc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(retarget.as_deref(), Some(HTMX_ERRORS_TARGET));
+        assert!(body.contains("Cannot parse"));
+    }
+}